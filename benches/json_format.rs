@@ -0,0 +1,55 @@
+use std::time::Instant;
+
+#[path = "../src/tools/json_beautifier/format.rs"]
+mod format;
+
+const SIZES_MB: [usize; 2] = [10, 100];
+
+fn document(size: usize) -> String {
+    let mut output = String::with_capacity(size + 1024);
+    output.push('[');
+    let mut i = 0;
+    while output.len() < size {
+        if i > 0 {
+            output.push(',');
+        }
+        output.push_str(&format!(
+            r#"{{"id":{},"uuid":"0192f1c4-6b5e-7a3d-9c1e-{:012x}","name":"user {}","active":{},"score":{}.5,"tags":["alpha","beta"],"address":{{"street":"Main Street {}","zip":"{:05}"}}}}"#,
+            i,
+            i,
+            i,
+            i % 2 == 0,
+            i % 100,
+            i,
+            i % 100000
+        ));
+        i += 1;
+    }
+    output.push(']');
+    output
+}
+
+fn main() {
+    for size_mb in SIZES_MB {
+        let input = document(size_mb * 1024 * 1024);
+        let megabytes = input.len() as f64 / (1024.0 * 1024.0);
+
+        let start = Instant::now();
+        let parsed = format::parse(&input).expect("benchmark document is valid JSON");
+        let parse_time = start.elapsed();
+
+        let start = Instant::now();
+        let formatted = format::pretty(&parsed, 4).expect("benchmark document can be formatted");
+        let pretty_time = start.elapsed();
+
+        println!(
+            "{:>4} MB: parse {:>8.2?} ({:>7.1} MB/s), format {:>8.2?} ({:>7.1} MB/s), output {:.1} MB",
+            size_mb,
+            parse_time,
+            megabytes / parse_time.as_secs_f64(),
+            pretty_time,
+            megabytes / pretty_time.as_secs_f64(),
+            formatted.len() as f64 / (1024.0 * 1024.0)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::io;
+use std::sync::atomic::AtomicBool;
+use std::time::Instant;
+
+#[allow(dead_code)]
+#[path = "../src/random.rs"]
+mod random;
+
+#[allow(dead_code)]
+#[path = "../src/tools/uuid_generator"]
+mod uuid_generator {
+    pub mod format;
+    pub mod generate;
+    pub mod ids;
+    pub mod version;
+}
+
+use uuid_generator::format::{Layout, OutputFormat};
+use uuid_generator::generate::{self, Settings};
+use uuid_generator::ids::IdType;
+use uuid_generator::version::Version;
+
+const AMOUNT: u32 = 10_000_000;
+
+fn main() {
+    let layouts = [Layout::Lines, Layout::JsonArray, Layout::Template];
+    for version in [Version::V4, Version::V7] {
+        for layout in layouts {
+            let settings = Settings {
+                id_type: IdType::Uuid,
+                amount: AMOUNT,
+                version,
+                node_id: [0; 6],
+                namespace: uuid::Uuid::nil(),
+                names: Vec::new(),
+                custom_data: None,
+                nanoid: Default::default(),
+                snowflake: Default::default(),
+                seed: None,
+                format: OutputFormat {
+                    layout,
+                    template: String::from("INSERT INTO t (id) VALUES ('{uuid}');"),
+                    ..OutputFormat::default()
+                },
+            };
+
+            let start = Instant::now();
+            let mut bytes = 0;
+            let written = generate::write(
+                &settings,
+                CountingSink(&mut bytes),
+                &AtomicBool::new(false),
+                |_| (),
+            )
+            .expect("writing to a sink can't fail");
+            let elapsed = start.elapsed();
+
+            println!(
+                "{:<30} {:<16} {} UUIDs in {:>8.2?} ({:>6.2} M UUIDs/s, {:>7.1} MB/s)",
+                version.to_string(),
+                layout.to_string(),
+                written,
+                elapsed,
+                written as f64 / elapsed.as_secs_f64() / 1_000_000.0,
+                bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64()
+            );
+        }
+    }
+}
+
+/// Discards the output, counting how many bytes would have been written.
+struct CountingSink<'a>(&'a mut usize);
+
+impl io::Write for CountingSink<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        *self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -44,7 +44,7 @@ impl Launcher {
         }
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
         let input_app = text_input("Search a tool...", &self.search_text)
             .on_input(Message::Search)
             .on_submit(Message::SearchSubmitted)
@@ -57,11 +57,11 @@ impl Launcher {
                     mouse_area(
                         container(text(search_match.as_str()).width(Length::Fill))
                             .padding(2)
-                            .style(|_theme| container::Style {
+                            .style(move |_theme| container::Style {
                                 background: if self
                                     .result_hovered
                                     .as_ref()
-                                    .is_some_and(|v| *v == search_match.to_string())
+                                    .is_some_and(|v| v == search_match)
                                 {
                                     Some(
                                         Color {
@@ -124,33 +124,24 @@ impl Launcher {
                 self.search_text = application;
                 let binding = self.search_text.to_owned();
 
-                self.search_matches = Pattern::parse(
-                    &binding.as_str(),
-                    CaseMatching::Ignore,
-                    Normalization::Smart,
-                )
-                .match_list(Tool::ALL, &mut self.matcher)
-                .into_iter()
-                .map(|m| m.0.to_string())
-                .collect();
+                self.search_matches =
+                    Pattern::parse(binding.as_str(), CaseMatching::Ignore, Normalization::Smart)
+                        .match_list(Tool::ALL, &mut self.matcher)
+                        .into_iter()
+                        .map(|m| m.0.to_string())
+                        .collect();
 
                 None
             }
             Message::SearchSubmitted => {
-                if self.search_matches.len() >= 1 {
-                    let best_match = self.search_matches.get(0).unwrap().as_str();
-                    match Tool::from_str(best_match) {
-                        Ok(v) => Some(v),
-                        Err(_) => None,
-                    }
+                if !self.search_matches.is_empty() {
+                    let best_match = self.search_matches.first().unwrap().as_str();
+                    Tool::from_str(best_match).ok()
                 } else {
                     None
                 }
             }
-            Message::SearchClicked(search_match) => match Tool::from_str(search_match.as_str()) {
-                Ok(v) => Some(v),
-                Err(_) => None,
-            },
+            Message::SearchClicked(search_match) => Tool::from_str(search_match.as_str()).ok(),
             Message::ResultEntered(search_match) => {
                 self.result_hovered = Some(search_match);
                 None
@@ -2,16 +2,22 @@ use iced::event::{self};
 use iced::keyboard::{self};
 use iced::widget::{self, column, container, horizontal_space, row, text};
 use iced::Alignment::Center;
-use iced::{Element, Event, Subscription, Task, Theme};
+use iced::{window, Element, Event, Subscription, Task, Theme};
 use launcher::Launcher;
 use modal::modal;
-use tools::{Base64Converter, JsonBeautifier, RandomDataGenerator, UuidGenerator};
+use tools::{
+    base64_converter, hash_generator, Base64Converter, EncodingConverter, HashGenerator,
+    HmacGenerator, JsonBeautifier, JsonDiff, JwtDebugger, PasswordAnalyzer, PasswordGenerator,
+    RandomDataGenerator, TextEncodingConverter, UuidGenerator,
+};
 
 use scale_factor::ScaleFactor;
 use utils::{Message, Tool};
 
 mod launcher;
 mod modal;
+mod random;
+mod save;
 mod scale_factor;
 mod tools;
 mod utils;
@@ -33,11 +39,20 @@ pub struct DevTools {
     theme: Theme,
 }
 
+#[allow(clippy::large_enum_variant)]
 enum Screen {
     UuidGenerator(UuidGenerator),
     JsonBeautifier(JsonBeautifier),
+    JsonDiff(JsonDiff),
     Base64Converter(Base64Converter),
+    EncodingConverter(EncodingConverter),
+    TextEncodingConverter(TextEncodingConverter),
     RandomDataGenerator(RandomDataGenerator),
+    PasswordGenerator(PasswordGenerator),
+    PasswordAnalyzer(PasswordAnalyzer),
+    HashGenerator(HashGenerator),
+    HmacGenerator(HmacGenerator),
+    JwtDebugger(JwtDebugger),
 }
 
 impl Default for DevTools {
@@ -77,16 +92,80 @@ impl DevTools {
             }
             Message::JsonBeautifier(message) => {
                 if let Screen::JsonBeautifier(json_beautifier) = &mut self.screen {
-                    json_beautifier.update(message);
+                    json_beautifier.update(message).map(Message::JsonBeautifier)
+                } else {
                     Task::none()
+                }
+            }
+            Message::JsonDiff(message) => {
+                if let Screen::JsonDiff(json_diff) = &mut self.screen {
+                    json_diff.update(message).map(Message::JsonDiff)
                 } else {
                     Task::none()
                 }
             }
             Message::Base64Converter(message) => {
                 if let Screen::Base64Converter(base64_converter) = &mut self.screen {
-                    base64_converter.update(message);
+                    base64_converter
+                        .update(message)
+                        .map(Message::Base64Converter)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::EncodingConverter(message) => {
+                if let Screen::EncodingConverter(encoding_converter) = &mut self.screen {
+                    encoding_converter.update(message);
+                }
+                Task::none()
+            }
+            Message::TextEncodingConverter(message) => {
+                if let Screen::TextEncodingConverter(text_encoding_converter) = &mut self.screen {
+                    text_encoding_converter.update(message);
+                }
+                Task::none()
+            }
+            Message::RandomDataGenerator(message) => {
+                if let Screen::RandomDataGenerator(random_data_generator) = &mut self.screen {
+                    random_data_generator
+                        .update(message)
+                        .map(Message::RandomDataGenerator)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::PasswordGenerator(message) => {
+                if let Screen::PasswordGenerator(password_generator) = &mut self.screen {
+                    password_generator
+                        .update(message)
+                        .map(Message::PasswordGenerator)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::PasswordAnalyzer(message) => {
+                if let Screen::PasswordAnalyzer(password_analyzer) = &mut self.screen {
+                    password_analyzer.update(message);
+                }
+                Task::none()
+            }
+            Message::HashGenerator(message) => {
+                if let Screen::HashGenerator(hash_generator) = &mut self.screen {
+                    hash_generator.update(message).map(Message::HashGenerator)
+                } else {
                     Task::none()
+                }
+            }
+            Message::HmacGenerator(message) => {
+                if let Screen::HmacGenerator(hmac_generator) = &mut self.screen {
+                    hmac_generator.update(message).map(Message::HmacGenerator)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::JwtDebugger(message) => {
+                if let Screen::JwtDebugger(jwt_debugger) = &mut self.screen {
+                    jwt_debugger.update(message).map(Message::JwtDebugger)
                 } else {
                     Task::none()
                 }
@@ -103,10 +182,26 @@ impl DevTools {
                                 Tool::JsonBeautifier => {
                                     Screen::JsonBeautifier(JsonBeautifier::new())
                                 }
+                                Tool::JsonDiff => Screen::JsonDiff(JsonDiff::new()),
                                 Tool::UuidGenerator => Screen::UuidGenerator(UuidGenerator::new()),
                                 Tool::Base64Converter => {
                                     Screen::Base64Converter(Base64Converter::new())
                                 }
+                                Tool::EncodingConverter => {
+                                    Screen::EncodingConverter(EncodingConverter::new())
+                                }
+                                Tool::TextEncodingConverter => {
+                                    Screen::TextEncodingConverter(TextEncodingConverter::new())
+                                }
+                                Tool::PasswordGenerator => {
+                                    Screen::PasswordGenerator(PasswordGenerator::new())
+                                }
+                                Tool::PasswordAnalyzer => {
+                                    Screen::PasswordAnalyzer(PasswordAnalyzer::new())
+                                }
+                                Tool::HashGenerator => Screen::HashGenerator(HashGenerator::new()),
+                                Tool::HmacGenerator => Screen::HmacGenerator(HmacGenerator::new()),
+                                Tool::JwtDebugger => Screen::JwtDebugger(JwtDebugger::new()),
                                 Tool::RandomDataGenerator => {
                                     Screen::RandomDataGenerator(RandomDataGenerator::new())
                                 }
@@ -165,16 +260,24 @@ impl DevTools {
                     modifiers,
                     ..
                 }) if c.as_str() == "0" && modifiers.control() => {
-                    self.scale_factor.to_default();
+                    self.scale_factor.reset();
                     Task::none()
                 }
+                Event::Window(window::Event::FileDropped(path)) => match &mut self.screen {
+                    Screen::Base64Converter(base64_converter) => base64_converter
+                        .update(base64_converter::Message::FileDropped(path))
+                        .map(Message::Base64Converter),
+                    Screen::HashGenerator(hash_generator) => hash_generator
+                        .update(hash_generator::Message::FileDropped(path))
+                        .map(Message::HashGenerator),
+                    _ => Task::none(),
+                },
                 _ => Task::none(),
             },
-            _ => Task::none(),
         }
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let (content, title) = match &self.screen {
             Screen::UuidGenerator(uuid_generator) => (
                 uuid_generator.view().map(Message::UuidGenerator),
@@ -184,10 +287,43 @@ impl DevTools {
                 json_beautifier.view().map(Message::JsonBeautifier),
                 json_beautifier.title(),
             ),
+            Screen::JsonDiff(json_diff) => {
+                (json_diff.view().map(Message::JsonDiff), json_diff.title())
+            }
             Screen::Base64Converter(base64_converter) => (
                 base64_converter.view().map(Message::Base64Converter),
                 base64_converter.title(),
             ),
+            Screen::EncodingConverter(encoding_converter) => (
+                encoding_converter.view().map(Message::EncodingConverter),
+                encoding_converter.title(),
+            ),
+            Screen::TextEncodingConverter(text_encoding_converter) => (
+                text_encoding_converter
+                    .view()
+                    .map(Message::TextEncodingConverter),
+                text_encoding_converter.title(),
+            ),
+            Screen::PasswordGenerator(password_generator) => (
+                password_generator.view().map(Message::PasswordGenerator),
+                password_generator.title(),
+            ),
+            Screen::PasswordAnalyzer(password_analyzer) => (
+                password_analyzer.view().map(Message::PasswordAnalyzer),
+                password_analyzer.title(),
+            ),
+            Screen::HashGenerator(hash_generator) => (
+                hash_generator.view().map(Message::HashGenerator),
+                hash_generator.title(),
+            ),
+            Screen::HmacGenerator(hmac_generator) => (
+                hmac_generator.view().map(Message::HmacGenerator),
+                hmac_generator.title(),
+            ),
+            Screen::JwtDebugger(jwt_debugger) => (
+                jwt_debugger.view().map(Message::JwtDebugger),
+                jwt_debugger.title(),
+            ),
             Screen::RandomDataGenerator(random_data_generator) => (
                 random_data_generator
                     .view()
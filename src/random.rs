@@ -0,0 +1,117 @@
+use rand::rngs::{OsRng, ThreadRng};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha3::{Digest, Sha3_256};
+
+/// The source of randomness for generated data. With a seed the output is reproducible, which
+/// is what test fixtures need, but anyone who knows the seed can predict it, so seeded values
+/// must never be used as secrets.
+pub enum Random {
+    Entropy(ThreadRng),
+    Seeded(Box<ChaCha20Rng>),
+}
+
+impl Random {
+    /// Seeds a ChaCha20 generator with the SHA3-256 hash of `seed`, so any text works as a
+    /// seed. Without a seed, falls back to the thread-local generator.
+    pub fn new(seed: Option<&str>) -> Self {
+        match seed {
+            Some(seed) => Random::Seeded(Box::new(ChaCha20Rng::from_seed(
+                Sha3_256::digest(seed.as_bytes()).into(),
+            ))),
+            None => Random::Entropy(rand::thread_rng()),
+        }
+    }
+
+    pub fn is_seeded(&self) -> bool {
+        matches!(self, Random::Seeded(_))
+    }
+}
+
+impl RngCore for Random {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Random::Entropy(rng) => rng.next_u32(),
+            Random::Seeded(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Random::Entropy(rng) => rng.next_u64(),
+            Random::Seeded(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Random::Entropy(rng) => rng.fill_bytes(dest),
+            Random::Seeded(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            Random::Entropy(rng) => rng.try_fill_bytes(dest),
+            Random::Seeded(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+/// Randomness for secrets, read straight from the operating system's CSPRNG. Reads go through
+/// a small buffer so bulk generation doesn't make a system call for every value.
+pub struct SecureRandom {
+    buffer: [u8; 256],
+    position: usize,
+}
+
+impl SecureRandom {
+    pub fn new() -> Self {
+        Self {
+            buffer: [0; 256],
+            position: 256,
+        }
+    }
+}
+
+impl Default for SecureRandom {
+    fn default() -> Self {
+        SecureRandom::new()
+    }
+}
+
+impl RngCore for SecureRandom {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, mut dest: &mut [u8]) {
+        while !dest.is_empty() {
+            if self.position == self.buffer.len() {
+                OsRng.fill_bytes(&mut self.buffer);
+                self.position = 0;
+            }
+            let count = dest.len().min(self.buffer.len() - self.position);
+            dest[..count].copy_from_slice(&self.buffer[self.position..self.position + count]);
+            // Bytes handed out are wiped so they don't linger in memory.
+            self.buffer[self.position..self.position + count].fill(0);
+            self.position += count;
+            dest = &mut dest[count..];
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for SecureRandom {}
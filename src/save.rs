@@ -0,0 +1,73 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::{process, thread};
+
+use iced::futures::channel::mpsc;
+use rfd::AsyncFileDialog;
+
+#[derive(Debug, Clone)]
+pub enum SaveEvent {
+    Written(u64),
+    Finished(Result<u64, String>),
+}
+
+pub async fn pick_file() -> Option<PathBuf> {
+    AsyncFileDialog::new()
+        .set_directory("/")
+        .save_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+}
+
+/// Runs `write` on a separate thread, reporting its progress as it goes.
+///
+/// The output goes to a temporary file next to `path`, which only replaces `path` once `write`
+/// succeeds. A cancelled or failed save removes its own temporary file and nothing else, so it
+/// can't clobber the file of a later save to the same path.
+pub fn save_in_background<F>(
+    path: PathBuf,
+    cancelled: Arc<AtomicBool>,
+    write: F,
+) -> mpsc::UnboundedReceiver<SaveEvent>
+where
+    F: FnOnce(File, &AtomicBool, &mut dyn FnMut(u64)) -> Result<u64, String> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        let partial = partial_path(&path);
+        let mut progress = |written| {
+            let _ = sender.unbounded_send(SaveEvent::Written(written));
+        };
+        let result = File::create(&partial)
+            .map_err(|e| e.to_string())
+            .and_then(|file| write(file, &cancelled, &mut progress))
+            .and_then(|written| match cancelled.load(Ordering::Relaxed) {
+                true => Err(String::from("Saving cancelled")),
+                false => fs::rename(&partial, &path)
+                    .map(|_| written)
+                    .map_err(|e| e.to_string()),
+            });
+        if result.is_err() {
+            let _ = fs::remove_file(&partial);
+        }
+        let _ = sender.unbounded_send(SaveEvent::Finished(result));
+    });
+    receiver
+}
+
+// Unique to this save, so concurrent saves to the same path never share a file.
+fn partial_path(path: &Path) -> PathBuf {
+    static SAVES: AtomicU64 = AtomicU64::new(0);
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{}.{}-{}.part",
+        name,
+        process::id(),
+        SAVES.fetch_add(1, Ordering::Relaxed)
+    ))
+}
@@ -34,7 +34,7 @@ impl ScaleFactor {
         }
     }
 
-    pub fn to_default(&mut self) {
+    pub fn reset(&mut self) {
         self.0 = 1.0;
     }
 }
@@ -1,9 +1,25 @@
 pub mod base64_converter;
+pub mod encoding_converter;
+pub mod hash_generator;
+pub mod hmac_generator;
 pub mod json_beautifier;
+pub mod json_diff;
+pub mod jwt_debugger;
+pub mod password_analyzer;
+pub mod password_generator;
 pub mod random_data_generator;
+pub mod text_encoding_converter;
 pub mod uuid_generator;
 
 pub use base64_converter::Base64Converter;
+pub use encoding_converter::EncodingConverter;
+pub use hash_generator::HashGenerator;
+pub use hmac_generator::HmacGenerator;
 pub use json_beautifier::JsonBeautifier;
+pub use json_diff::JsonDiff;
+pub use jwt_debugger::JwtDebugger;
+pub use password_analyzer::PasswordAnalyzer;
+pub use password_generator::PasswordGenerator;
 pub use random_data_generator::RandomDataGenerator;
+pub use text_encoding_converter::TextEncodingConverter;
 pub use uuid_generator::UuidGenerator;
@@ -1,21 +1,91 @@
-use base64::{engine::general_purpose, Engine as _};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Read, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
 use iced::{
-    widget::{column, container, scrollable, text_editor},
-    Element, Length,
+    futures::channel::oneshot,
+    task::Handle,
+    widget::{
+        button, checkbox, column, container, horizontal_space, pick_list, progress_bar, row,
+        scrollable, text, text_editor,
+    },
+    Alignment, Element, Length, Task,
 };
+use rfd::AsyncFileDialog;
+use text_editor::Action;
+
+use crate::save::{self, SaveEvent};
+use binary::FileType;
+use codec::{Alphabet, LineWrap, Padding};
+use highlight::{ErrorHighlighter, Position, ERROR_COLOR};
+
+mod binary;
+pub mod codec;
+mod highlight;
 
 pub const NAME: &str = "Base64 Converter";
 
+// Larger files are streamed to a destination file, and the panels only show their beginning.
+const PREVIEW_LIMIT: u64 = 1024 * 1024;
+// A multiple of 3 bytes and of the MIME and PEM line lengths, like the codec's chunks.
+const PREVIEW_SIZE: u64 = 912 * 64;
+const MEGABYTE: f64 = 1024.0 * 1024.0;
+
+type EncodeResult = Arc<Result<(Vec<u8>, String), String>>;
+
 #[derive(Debug, Clone)]
 pub enum Message {
     DecodedTextChanged(text_editor::Action),
     EncodedTextChanged(text_editor::Action),
+    AlphabetSelected(Alphabet),
+    PaddingSelected(Padding),
+    LineWrapSelected(LineWrap),
+    AutoDetectToggled(bool),
+    DataUriToggled(bool),
+    HexDumpActionPerformed(text_editor::Action),
+    SaveToFileClicked,
+    FileSaved(Option<Result<String, String>>),
+    EncodeFileClicked,
+    FilePicked(Option<PathBuf>),
+    FileDropped(PathBuf),
+    FileEncoded(EncodeResult),
+    DestinationPicked(Option<PathBuf>),
+    EncodeProgress(SaveEvent),
+    CancelEncodeClicked,
 }
 
 pub struct Base64Converter {
     tool_name: String,
     encoded_input: text_editor::Content,
     decoded_input: text_editor::Content,
+    alphabet: Option<Alphabet>,
+    padding: Option<Padding>,
+    line_wrap: Option<LineWrap>,
+    auto_detect: bool,
+    detected_alphabet: Option<Alphabet>,
+    data_uri: bool,
+    data_uri_mime: Option<String>,
+    decoded_bytes: Vec<u8>,
+    hex_dump: Option<text_editor::Content>,
+    file_type: Option<FileType>,
+    save_status: Option<String>,
+    is_encoding_file: bool,
+    /// The file and its size, when the panels only show the beginning of a large file.
+    preview_of: Option<(PathBuf, u64)>,
+    streaming: Option<(Handle, Arc<AtomicBool>)>,
+    stream_total: u64,
+    stream_read: u64,
+    stream_status: Option<String>,
+    error_text: Option<String>,
+    decode_error: Option<String>,
+    error_position: Option<Position>,
 }
 
 impl Default for Base64Converter {
@@ -30,6 +100,26 @@ impl Base64Converter {
             tool_name: NAME.to_string(),
             encoded_input: text_editor::Content::new(),
             decoded_input: text_editor::Content::new(),
+            alphabet: Some(Alphabet::default()),
+            padding: Some(Padding::default()),
+            line_wrap: Some(LineWrap::default()),
+            auto_detect: true,
+            detected_alphabet: None,
+            data_uri: false,
+            data_uri_mime: None,
+            decoded_bytes: vec![],
+            hex_dump: None,
+            file_type: None,
+            save_status: None,
+            is_encoding_file: false,
+            preview_of: None,
+            streaming: None,
+            stream_total: 0,
+            stream_read: 0,
+            stream_status: None,
+            error_text: None,
+            decode_error: None,
+            error_position: None,
         }
     }
 
@@ -37,57 +127,487 @@ impl Base64Converter {
         self.tool_name.clone()
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
+        let configuration = row![
+            pick_list(&Alphabet::ALL[..], self.alphabet, Message::AlphabetSelected),
+            pick_list(&Padding::ALL[..], self.padding, Message::PaddingSelected),
+            pick_list(
+                &LineWrap::ALL[..],
+                self.line_wrap,
+                Message::LineWrapSelected
+            ),
+            checkbox("Auto-detect alphabet when decoding", self.auto_detect)
+                .on_toggle(Message::AutoDetectToggled),
+            checkbox("Emit data URI", self.data_uri).on_toggle(Message::DataUriToggled),
+            horizontal_space(),
+            button("Encode file...").on_press_maybe(
+                (!self.is_encoding_file && self.streaming.is_none())
+                    .then_some(Message::EncodeFileClicked)
+            ),
+        ]
+        .spacing(10)
+        .padding([10, 0]);
+
+        let mut details = vec![];
+        if let Some((path, size)) = &self.preview_of {
+            details.push(format!(
+                "Preview of the first {} KB of {} ({:.1} MB)",
+                PREVIEW_SIZE / 1024,
+                path.file_name().unwrap_or_default().to_string_lossy(),
+                *size as f64 / MEGABYTE
+            ));
+        }
+        if let Some(mime) = &self.data_uri_mime {
+            details.push(format!("MIME type: {}", mime));
+        }
+        if self.hex_dump.is_some() {
+            details.push(format!("Binary data, {} bytes", self.decoded_bytes.len()));
+            if let Some(file_type) = self.file_type {
+                details.push(format!("{} ({})", file_type.name, file_type.mime));
+            }
+        }
+        let decoded_editor = match &self.hex_dump {
+            Some(hex_dump) => text_editor(hex_dump)
+                .on_action(Message::HexDumpActionPerformed)
+                .font(iced::Font::MONOSPACE),
+            None => text_editor(&self.decoded_input).on_action(Message::DecodedTextChanged),
+        };
+        let stale = if self.decode_error.is_some() {
+            "Out of date: the encoded input has errors"
+        } else {
+            ""
+        };
         let decoded_panel = column![
-            "Decoded",
-            container(scrollable(
-                text_editor(&self.decoded_input).on_action(Message::DecodedTextChanged)
-            ))
-            .height(Length::Fill),
+            row![
+                "Decoded",
+                text(stale).color(ERROR_COLOR),
+                horizontal_space(),
+                text(details.join(", ")),
+                text(self.save_status.as_deref().unwrap_or_default()),
+                button("Save decoded bytes").on_press_maybe(
+                    (self.preview_of.is_none() && self.decode_error.is_none())
+                        .then_some(Message::SaveToFileClicked)
+                )
+            ]
+            .spacing(10),
+            container(scrollable(decoded_editor)).height(Length::Fill),
         ];
 
+        let status = if self.is_encoding_file {
+            String::from("Encoding file...")
+        } else {
+            match self.detected_alphabet {
+                Some(alphabet) if self.auto_detect => format!("Detected alphabet: {}", alphabet),
+                _ => String::new(),
+            }
+        };
         let encoded_panel = column![
-            "Encoded",
+            row![
+                "Encoded",
+                text(self.decode_error.clone().unwrap_or_default()).color(ERROR_COLOR),
+                horizontal_space(),
+                text(status)
+            ]
+            .spacing(10),
             container(scrollable(
-                text_editor(&self.encoded_input).on_action(Message::EncodedTextChanged)
+                text_editor(&self.encoded_input)
+                    .on_action(Message::EncodedTextChanged)
+                    .highlight_with::<ErrorHighlighter>(
+                        self.error_position.clone(),
+                        highlight::to_format
+                    )
             ))
             .height(Length::Fill),
         ];
 
-        let content = container(column![decoded_panel, encoded_panel]).padding(10);
-        content.into()
+        let mut content = column![
+            configuration,
+            self.stream_view(),
+            decoded_panel,
+            encoded_panel
+        ];
+        if let Some(v) = &self.error_text {
+            content = content.push(row![text(v.to_owned()), horizontal_space()].padding([10, 0]));
+        }
+        container(content).padding(10).into()
     }
 
-    pub fn update(&mut self, message: Message) {
+    pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::DecodedTextChanged(text_action) => {
                 let old_text = self.decoded_input.text().to_owned();
                 self.decoded_input.perform(text_action);
-                let mut new_text = self.decoded_input.text().to_owned();
-                let len = new_text.trim_end_matches(&['\r', '\n'][..]).len();
-                new_text.truncate(len);
+                let new_text = trim_trailing_newlines(self.decoded_input.text());
                 if old_text != new_text {
-                    let encoded = general_purpose::STANDARD.encode(new_text.as_bytes());
-                    self.encoded_input = text_editor::Content::with_text(&encoded);
+                    self.preview_of = None;
+                    self.decoded_bytes = new_text.into_bytes();
+                    self.data_uri_mime = None;
+                    self.encode();
                 }
             }
             Message::EncodedTextChanged(text_action) => {
                 let old_text = self.encoded_input.text().to_owned();
                 self.encoded_input.perform(text_action);
-                let mut new_text = self.encoded_input.text().to_owned();
-                let len = new_text.trim_end_matches(&['\r', '\n'][..]).len();
-                new_text.truncate(len);
+                let new_text = trim_trailing_newlines(self.encoded_input.text());
                 if old_text != new_text {
-                    match general_purpose::STANDARD.decode(new_text.as_str()) {
-                        Ok(decoded) => {
-                            self.decoded_input = text_editor::Content::with_text(
-                                String::from_utf8(decoded).unwrap().as_str(),
-                            )
+                    self.preview_of = None;
+                    self.decode();
+                }
+            }
+            Message::AlphabetSelected(alphabet) => {
+                self.alphabet = Some(alphabet);
+                self.encode();
+            }
+            Message::PaddingSelected(padding) => {
+                self.padding = Some(padding);
+                self.encode();
+            }
+            Message::LineWrapSelected(line_wrap) => {
+                self.line_wrap = Some(line_wrap);
+                self.encode();
+            }
+            Message::AutoDetectToggled(value) => {
+                self.auto_detect = value;
+                self.decode();
+            }
+            Message::DataUriToggled(value) => {
+                self.data_uri = value;
+                self.encode();
+            }
+            Message::HexDumpActionPerformed(action) => {
+                if let Some(hex_dump) = &mut self.hex_dump {
+                    match action {
+                        Action::SelectAll | Action::SelectLine | Action::SelectWord => {
+                            hex_dump.perform(action)
+                        }
+                        Action::Select(motion) => hex_dump.perform(Action::Select(motion)),
+                        Action::Click(point) => hex_dump.perform(Action::Click(point)),
+                        Action::Drag(point) => hex_dump.perform(Action::Drag(point)),
+                        Action::Move(motion) => hex_dump.perform(Action::Move(motion)),
+                        _ => (),
+                    }
+                }
+            }
+            Message::SaveToFileClicked => {
+                let extension = self.file_type.map(|file_type| file_type.extension);
+                return Task::perform(
+                    save_to_file(self.decoded_bytes.clone(), extension),
+                    Message::FileSaved,
+                );
+            }
+            Message::FileSaved(result) => {
+                self.save_status = result.map(|result| match result {
+                    Ok(path) => format!("Saved to {}", path),
+                    Err(e) => format!("Could not save the decoded bytes: {}", e),
+                });
+            }
+            Message::EncodeFileClicked => {
+                return Task::perform(pick_file(), Message::FilePicked);
+            }
+            Message::FilePicked(path) => {
+                if let Some(path) = path {
+                    return self.update(Message::FileDropped(path));
+                }
+            }
+            Message::FileDropped(path) => {
+                if self.is_encoding_file || self.streaming.is_some() {
+                    return Task::none();
+                }
+                self.is_encoding_file = true;
+                self.error_text = None;
+                self.stream_status = None;
+                let size = fs::metadata(&path).map(|m| m.len()).unwrap_or_default();
+                self.preview_of = (size > PREVIEW_LIMIT).then(|| (path.clone(), size));
+                return Task::perform(
+                    encode_in_background(
+                        path,
+                        match self.preview_of {
+                            Some(_) => PREVIEW_SIZE,
+                            None => u64::MAX,
+                        },
+                        self.alphabet.unwrap_or_default(),
+                        self.padding.unwrap_or_default(),
+                        self.encoded_line_wrap(),
+                    ),
+                    |result| Message::FileEncoded(Arc::new(result)),
+                );
+            }
+            Message::FileEncoded(result) => {
+                self.is_encoding_file = false;
+                match Arc::unwrap_or_clone(result) {
+                    Ok((mut bytes, encoded)) => {
+                        // A preview can end in the middle of a character, which shouldn't make
+                        // a text file look binary.
+                        if let (Some(_), Err(e)) = (&self.preview_of, std::str::from_utf8(&bytes)) {
+                            if e.error_len().is_none() {
+                                bytes.truncate(e.valid_up_to());
+                            }
+                        }
+                        self.data_uri_mime = None;
+                        self.show_decoded(bytes);
+                        self.set_encoded(encoded);
+                        if let Some((path, _)) = &self.preview_of {
+                            return Task::perform(
+                                pick_destination(path.clone()),
+                                Message::DestinationPicked,
+                            );
                         }
-                        Err(_) => (),
                     }
+                    Err(e) => {
+                        self.preview_of = None;
+                        self.error_text = Some(e);
+                    }
+                }
+            }
+            Message::DestinationPicked(destination) => match (destination, &self.preview_of) {
+                (Some(destination), Some((source, size))) => {
+                    return self.encode_to_file(source.clone(), *size, destination);
                 }
+                _ => {
+                    self.stream_status = Some(String::from(
+                        "Only the preview was encoded: no file was picked",
+                    ))
+                }
+            },
+            Message::EncodeProgress(SaveEvent::Written(read)) => self.stream_read = read,
+            Message::EncodeProgress(SaveEvent::Finished(result)) => {
+                self.streaming = None;
+                self.stream_status = Some(match result {
+                    Ok(read) => format!(
+                        "Encoded {:.1} MB into the destination file",
+                        read as f64 / MEGABYTE
+                    ),
+                    Err(e) => format!("Could not encode the file: {}", e),
+                });
+            }
+            Message::CancelEncodeClicked => {
+                if let Some((handle, cancelled)) = self.streaming.take() {
+                    cancelled.store(true, Ordering::Relaxed);
+                    handle.abort();
+                    self.stream_status = Some(String::from("Encoding cancelled"));
+                }
+            }
+        }
+        Task::none()
+    }
+
+    fn stream_view(&self) -> Element<'_, Message> {
+        match (&self.streaming, &self.stream_status) {
+            (Some(_), _) => row![
+                progress_bar(0.0..=self.stream_total as f32, self.stream_read as f32)
+                    .height(20)
+                    .width(300),
+                text(format!(
+                    "{:.1} / {:.1} MB",
+                    self.stream_read as f64 / MEGABYTE,
+                    self.stream_total as f64 / MEGABYTE
+                )),
+                button("Cancel").on_press(Message::CancelEncodeClicked),
+            ]
+            .spacing(10)
+            .padding([5, 0])
+            .align_y(Alignment::Center)
+            .into(),
+            (None, Some(status)) => row![text(status)].padding([5, 0]).into(),
+            (None, None) => column![].into(),
+        }
+    }
+
+    fn encode_to_file(
+        &mut self,
+        source: PathBuf,
+        size: u64,
+        destination: PathBuf,
+    ) -> Task<Message> {
+        let alphabet = self.alphabet.unwrap_or_default();
+        let padding = self.padding.unwrap_or_default();
+        let line_wrap = self.encoded_line_wrap();
+        // The MIME type is guessed from the preview, which holds the start of the file.
+        let prefix = if self.data_uri {
+            codec::data_uri(&self.mime(), "")
+        } else {
+            String::new()
+        };
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.stream_total = size;
+        self.stream_read = 0;
+        self.stream_status = None;
+        let (task, handle) = Task::run(
+            save::save_in_background(
+                destination,
+                cancelled.clone(),
+                move |file, cancelled, progress| {
+                    let source = File::open(&source)
+                        .map_err(|e| format!("Could not read {}: {}", source.display(), e))?;
+                    let mut writer = BufWriter::new(file);
+                    writer
+                        .write_all(prefix.as_bytes())
+                        .and_then(|_| {
+                            codec::encode_to_writer(
+                                source, writer, alphabet, padding, line_wrap, cancelled, progress,
+                            )
+                        })
+                        .map_err(|e| e.to_string())
+                },
+            ),
+            Message::EncodeProgress,
+        )
+        .abortable();
+        self.streaming = Some((handle, cancelled));
+        task
+    }
+
+    // Data URIs can't contain line breaks, so wrapping only applies to plain Base64 output.
+    fn encoded_line_wrap(&self) -> LineWrap {
+        if self.data_uri {
+            LineWrap::Unwrapped
+        } else {
+            self.line_wrap.unwrap_or_default()
+        }
+    }
+
+    fn mime(&self) -> String {
+        match (&self.data_uri_mime, self.file_type) {
+            (Some(mime), _) => mime.clone(),
+            (None, Some(file_type)) => file_type.mime.to_string(),
+            (None, None) if self.hex_dump.is_none() => String::from("text/plain;charset=utf-8"),
+            (None, None) => String::from("application/octet-stream"),
+        }
+    }
+
+    fn encode(&mut self) {
+        let encoded = codec::encode(
+            &self.decoded_bytes,
+            self.alphabet.unwrap_or_default(),
+            self.padding.unwrap_or_default(),
+            self.encoded_line_wrap(),
+        );
+        self.set_encoded(encoded);
+    }
+
+    fn set_encoded(&mut self, encoded: String) {
+        let encoded = if self.data_uri {
+            codec::data_uri(&self.mime(), &encoded)
+        } else {
+            encoded
+        };
+        self.encoded_input = text_editor::Content::with_text(&encoded);
+        self.decode_error = None;
+        self.error_position = None;
+    }
+
+    fn decode(&mut self) {
+        let input = trim_trailing_newlines(self.encoded_input.text());
+        let (mime, encoded) = match codec::parse_data_uri(&input) {
+            Some((mime, payload)) => (Some(mime.to_string()), payload),
+            None => (None, input.as_str()),
+        };
+        self.detected_alphabet = Alphabet::detect(encoded);
+        let alphabet = match self.detected_alphabet {
+            Some(detected) if self.auto_detect => detected,
+            _ => self.alphabet.unwrap_or_default(),
+        };
+        match codec::decode(encoded, alphabet, self.padding.unwrap_or_default()) {
+            Ok(decoded) => {
+                self.decode_error = None;
+                self.error_position = None;
+                self.data_uri_mime = mime;
+                self.show_decoded(decoded);
+            }
+            Err(e) => {
+                // `encoded` is a suffix of the input once a data URI header is stripped.
+                let offset = e.offset.map(|offset| input.len() - encoded.len() + offset);
+                self.error_position = offset.and_then(|offset| Position::locate(&input, offset));
+                self.decode_error = Some(match &self.error_position {
+                    Some(position) => format!(
+                        "{} at line {}, column {}",
+                        e.message,
+                        position.line + 1,
+                        input.lines().nth(position.line).map_or(0, |line| {
+                            line[..position.columns.start].chars().count() + 1
+                        })
+                    ),
+                    None => e.message,
+                });
+            }
+        }
+    }
+
+    fn show_decoded(&mut self, decoded: Vec<u8>) {
+        match std::str::from_utf8(&decoded) {
+            Ok(text) => {
+                self.decoded_input = text_editor::Content::with_text(text);
+                self.hex_dump = None;
+                self.file_type = None;
             }
+            Err(_) => {
+                self.hex_dump = Some(text_editor::Content::with_text(&binary::hex_dump(&decoded)));
+                self.file_type = binary::detect(&decoded);
+            }
+        }
+        if self.file_type.is_none() {
+            self.file_type = self.data_uri_mime.as_deref().and_then(binary::from_mime);
         }
+        self.decoded_bytes = decoded;
     }
 }
+
+fn trim_trailing_newlines(mut text: String) -> String {
+    let len = text.trim_end_matches(&['\r', '\n'][..]).len();
+    text.truncate(len);
+    text
+}
+
+async fn pick_file() -> Option<PathBuf> {
+    let file = AsyncFileDialog::new()
+        .set_directory("/")
+        .pick_file()
+        .await?;
+    Some(file.path().to_path_buf())
+}
+
+async fn pick_destination(source: PathBuf) -> Option<PathBuf> {
+    let name = source.file_name().unwrap_or_default().to_string_lossy();
+    AsyncFileDialog::new()
+        .set_directory("/")
+        .set_file_name(format!("{}.b64", name))
+        .save_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+}
+
+/// Encodes up to `limit` bytes from the start of the file on a separate thread.
+async fn encode_in_background(
+    path: PathBuf,
+    limit: u64,
+    alphabet: Alphabet,
+    padding: Padding,
+    line_wrap: LineWrap,
+) -> Result<(Vec<u8>, String), String> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let result = File::open(&path)
+            .and_then(|file| codec::encode_reader(file.take(limit), alphabet, padding, line_wrap))
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e));
+        let _ = sender.send(result);
+    });
+    receiver
+        .await
+        .unwrap_or_else(|_| Err(String::from("Encoding cancelled")))
+}
+
+async fn save_to_file(
+    bytes: Vec<u8>,
+    extension: Option<&'static str>,
+) -> Option<Result<String, String>> {
+    let file = AsyncFileDialog::new()
+        .set_directory("/")
+        .set_file_name(format!("decoded.{}", extension.unwrap_or("bin")))
+        .save_file()
+        .await?;
+    Some(
+        fs::write(file.path(), bytes)
+            .map(|_| file.path().display().to_string())
+            .map_err(|e| e.to_string()),
+    )
+}
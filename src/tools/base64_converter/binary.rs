@@ -0,0 +1,183 @@
+const HEX_DUMP_LIMIT: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileType {
+    pub name: &'static str,
+    pub mime: &'static str,
+    pub extension: &'static str,
+}
+
+const fn file_type(name: &'static str, mime: &'static str, extension: &'static str) -> FileType {
+    FileType {
+        name,
+        mime,
+        extension,
+    }
+}
+
+// (offset, magic bytes, file type)
+const SIGNATURES: [(usize, &[u8], FileType); 26] = [
+    (
+        0,
+        b"\x89PNG\r\n\x1a\n",
+        file_type("PNG image", "image/png", "png"),
+    ),
+    (
+        0,
+        b"\xff\xd8\xff",
+        file_type("JPEG image", "image/jpeg", "jpg"),
+    ),
+    (0, b"GIF87a", file_type("GIF image", "image/gif", "gif")),
+    (0, b"GIF89a", file_type("GIF image", "image/gif", "gif")),
+    (0, b"BM", file_type("BMP image", "image/bmp", "bmp")),
+    (
+        0,
+        b"\x00\x00\x01\x00",
+        file_type("ICO image", "image/x-icon", "ico"),
+    ),
+    (
+        0,
+        b"%PDF-",
+        file_type("PDF document", "application/pdf", "pdf"),
+    ),
+    (
+        0,
+        b"PK\x03\x04",
+        file_type("ZIP archive", "application/zip", "zip"),
+    ),
+    (
+        0,
+        b"\x1f\x8b",
+        file_type("Gzip archive", "application/gzip", "gz"),
+    ),
+    (
+        0,
+        b"BZh",
+        file_type("Bzip2 archive", "application/x-bzip2", "bz2"),
+    ),
+    (
+        0,
+        b"\xfd7zXZ\x00",
+        file_type("XZ archive", "application/x-xz", "xz"),
+    ),
+    (
+        0,
+        b"7z\xbc\xaf\x27\x1c",
+        file_type("7-Zip archive", "application/x-7z-compressed", "7z"),
+    ),
+    (
+        0,
+        b"\x28\xb5\x2f\xfd",
+        file_type("Zstandard archive", "application/zstd", "zst"),
+    ),
+    (
+        257,
+        b"ustar",
+        file_type("TAR archive", "application/x-tar", "tar"),
+    ),
+    (
+        0,
+        b"\x7fELF",
+        file_type("ELF executable", "application/x-elf", "elf"),
+    ),
+    (
+        0,
+        b"MZ",
+        file_type(
+            "Windows executable",
+            "application/vnd.microsoft.portable-executable",
+            "exe",
+        ),
+    ),
+    (
+        0,
+        b"\x00asm",
+        file_type("WebAssembly module", "application/wasm", "wasm"),
+    ),
+    (
+        0,
+        b"\xca\xfe\xba\xbe",
+        file_type("Java class file", "application/java-vm", "class"),
+    ),
+    (
+        0,
+        b"SQLite format 3\x00",
+        file_type("SQLite database", "application/vnd.sqlite3", "sqlite"),
+    ),
+    (0, b"ID3", file_type("MP3 audio", "audio/mpeg", "mp3")),
+    (0, b"OggS", file_type("Ogg media", "audio/ogg", "ogg")),
+    (0, b"fLaC", file_type("FLAC audio", "audio/flac", "flac")),
+    (4, b"ftyp", file_type("MP4 video", "video/mp4", "mp4")),
+    (
+        0,
+        b"\x1aE\xdf\xa3",
+        file_type("Matroska/WebM video", "video/webm", "webm"),
+    ),
+    (0, b"wOF2", file_type("WOFF2 font", "font/woff2", "woff2")),
+    (0, b"wOFF", file_type("WOFF font", "font/woff", "woff")),
+];
+
+// RIFF containers share their first four bytes and are told apart by the form type at offset 8.
+const RIFF_FORMS: [(&[u8], FileType); 3] = [
+    (b"WEBP", file_type("WebP image", "image/webp", "webp")),
+    (b"WAVE", file_type("WAV audio", "audio/wav", "wav")),
+    (b"AVI ", file_type("AVI video", "video/x-msvideo", "avi")),
+];
+
+pub fn detect(bytes: &[u8]) -> Option<FileType> {
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" {
+        return RIFF_FORMS
+            .iter()
+            .find(|(form, _)| &bytes[8..12] == *form)
+            .map(|(_, file_type)| *file_type);
+    }
+
+    SIGNATURES
+        .iter()
+        .find(|(offset, magic, _)| bytes.get(*offset..offset + magic.len()) == Some(*magic))
+        .map(|(_, _, file_type)| *file_type)
+}
+
+pub fn from_mime(mime: &str) -> Option<FileType> {
+    let essence = mime.split(';').next().unwrap_or_default().trim();
+    SIGNATURES
+        .iter()
+        .map(|(_, _, file_type)| file_type)
+        .chain(RIFF_FORMS.iter().map(|(_, file_type)| file_type))
+        .find(|file_type| file_type.mime.eq_ignore_ascii_case(essence))
+        .copied()
+}
+
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().min(HEX_DUMP_LIMIT) * 4);
+
+    for (line, chunk) in bytes[..bytes.len().min(HEX_DUMP_LIMIT)]
+        .chunks(16)
+        .enumerate()
+    {
+        output.push_str(&format!("{:08x}  ", line * 16));
+        for i in 0..16 {
+            match chunk.get(i) {
+                Some(byte) => output.push_str(&format!("{:02x} ", byte)),
+                None => output.push_str("   "),
+            }
+            if i == 7 {
+                output.push(' ');
+            }
+        }
+        output.push_str(" |");
+        output.extend(chunk.iter().map(|byte| match byte {
+            0x20..=0x7e => *byte as char,
+            _ => '.',
+        }));
+        output.push_str("|\n");
+    }
+
+    if bytes.len() > HEX_DUMP_LIMIT {
+        output.push_str(&format!(
+            "... {} more bytes not shown",
+            bytes.len() - HEX_DUMP_LIMIT
+        ));
+    }
+    output
+}
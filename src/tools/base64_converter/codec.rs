@@ -0,0 +1,451 @@
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use base64::{
+    alphabet,
+    engine::{
+        general_purpose::{GeneralPurpose, GeneralPurposeConfig},
+        DecodePaddingMode,
+    },
+    DecodeError, Engine as _,
+};
+
+// A multiple of 3 bytes and of the MIME (57 bytes) and PEM (48 bytes) line lengths, so every
+// full chunk encodes to whole, unpadded lines.
+const CHUNK_SIZE: usize = 912 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alphabet {
+    #[default]
+    Standard,
+    UrlSafe,
+}
+
+impl Alphabet {
+    pub const ALL: [Alphabet; 2] = [Alphabet::Standard, Alphabet::UrlSafe];
+
+    pub fn detect(encoded: &str) -> Option<Alphabet> {
+        if encoded.contains(['-', '_']) {
+            Some(Alphabet::UrlSafe)
+        } else if encoded.contains(['+', '/']) {
+            Some(Alphabet::Standard)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Alphabet::Standard => "Standard (+/)",
+                Alphabet::UrlSafe => "URL-safe (-_)",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Padding {
+    #[default]
+    Required,
+    Optional,
+    Omitted,
+}
+
+impl Padding {
+    pub const ALL: [Padding; 3] = [Padding::Required, Padding::Optional, Padding::Omitted];
+}
+
+impl std::fmt::Display for Padding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Padding::Required => "Padding required",
+                Padding::Optional => "Padding optional",
+                Padding::Omitted => "No padding",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineWrap {
+    #[default]
+    Unwrapped,
+    Mime,
+    Pem,
+}
+
+impl LineWrap {
+    pub const ALL: [LineWrap; 3] = [LineWrap::Unwrapped, LineWrap::Mime, LineWrap::Pem];
+
+    fn columns(&self) -> Option<usize> {
+        match self {
+            LineWrap::Unwrapped => None,
+            LineWrap::Mime => Some(76),
+            LineWrap::Pem => Some(64),
+        }
+    }
+}
+
+impl std::fmt::Display for LineWrap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LineWrap::Unwrapped => "No line wrapping",
+                LineWrap::Mime => "MIME (76 columns)",
+                LineWrap::Pem => "PEM (64 columns)",
+            }
+        )
+    }
+}
+
+fn engine(alphabet: Alphabet, padding: Padding) -> GeneralPurpose {
+    let config = GeneralPurposeConfig::new()
+        .with_encode_padding(padding != Padding::Omitted)
+        .with_decode_padding_mode(match padding {
+            Padding::Required => DecodePaddingMode::RequireCanonical,
+            Padding::Optional => DecodePaddingMode::Indifferent,
+            Padding::Omitted => DecodePaddingMode::RequireNone,
+        });
+    match alphabet {
+        Alphabet::Standard => GeneralPurpose::new(&alphabet::STANDARD, config),
+        Alphabet::UrlSafe => GeneralPurpose::new(&alphabet::URL_SAFE, config),
+    }
+}
+
+pub fn encode(bytes: &[u8], alphabet: Alphabet, padding: Padding, line_wrap: LineWrap) -> String {
+    let encoded = engine(alphabet, padding).encode(bytes);
+    match line_wrap.columns() {
+        Some(columns) => encoded
+            .as_bytes()
+            .chunks(columns)
+            .map(|line| String::from_utf8_lossy(line))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => encoded,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidInput {
+    pub message: String,
+    /// Byte offset of the offending character in the text passed to `decode`.
+    pub offset: Option<usize>,
+}
+
+pub fn decode(
+    encoded: &str,
+    alphabet: Alphabet,
+    padding: Padding,
+) -> Result<Vec<u8>, InvalidInput> {
+    let compact: String = encoded.chars().filter(|c| !c.is_whitespace()).collect();
+    engine(alphabet, padding)
+        .decode(&compact)
+        .map_err(|e| invalid_input(e, encoded, &compact))
+}
+
+fn invalid_input(error: DecodeError, encoded: &str, compact: &str) -> InvalidInput {
+    // The decoder only sees the input with whitespace stripped, so its offsets have to be
+    // mapped back onto the original text.
+    let original_offset = |offset: usize| {
+        encoded
+            .char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .scan(0, |position, (index, c)| {
+                let current = *position;
+                *position += c.len_utf8();
+                Some((current, index))
+            })
+            .find(|(position, _)| *position >= offset)
+            .map(|(_, index)| index)
+    };
+    let character = |offset: usize| {
+        compact[compact.floor_char_boundary(offset)..]
+            .chars()
+            .next()
+            .map(|c| c.escape_debug().to_string())
+            .unwrap_or_default()
+    };
+
+    match error {
+        DecodeError::InvalidByte(offset, _) => InvalidInput {
+            message: format!("Invalid character '{}'", character(offset)),
+            offset: original_offset(offset),
+        },
+        DecodeError::InvalidLastSymbol(offset, _) => InvalidInput {
+            message: format!(
+                "Invalid last symbol '{}': it has bits set that don't fit in the decoded output",
+                character(offset)
+            ),
+            offset: original_offset(offset),
+        },
+        DecodeError::InvalidLength(length) => InvalidInput {
+            message: format!("Invalid length: {} symbols can't be decoded", length),
+            offset: None,
+        },
+        DecodeError::InvalidPadding => InvalidInput {
+            message: String::from("Invalid padding for the selected padding mode"),
+            offset: compact.find('=').and_then(original_offset),
+        },
+    }
+}
+
+pub fn encode_reader(
+    reader: impl Read,
+    alphabet: Alphabet,
+    padding: Padding,
+    line_wrap: LineWrap,
+) -> io::Result<(Vec<u8>, String)> {
+    let mut bytes = Vec::new();
+    let mut encoded = String::new();
+    encode_chunks(
+        reader,
+        alphabet,
+        padding,
+        line_wrap,
+        |chunk, chunk_encoded| {
+            bytes.extend_from_slice(chunk);
+            encoded.push_str(chunk_encoded);
+            Ok(())
+        },
+    )?;
+    Ok((bytes, encoded))
+}
+
+/// Streams the encoding of `reader` into `writer`, stopping early once `cancelled` is set.
+/// Returns the number of bytes read, which is also what `progress` reports.
+pub fn encode_to_writer(
+    reader: impl Read,
+    mut writer: impl Write,
+    alphabet: Alphabet,
+    padding: Padding,
+    line_wrap: LineWrap,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(u64),
+) -> io::Result<u64> {
+    let mut read = 0;
+    encode_chunks(reader, alphabet, padding, line_wrap, |chunk, encoded| {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(io::Error::other("Encoding cancelled"));
+        }
+        writer.write_all(encoded.as_bytes())?;
+        read += chunk.len() as u64;
+        progress(read);
+        Ok(())
+    })?;
+    writer.flush()?;
+    Ok(read)
+}
+
+// Reads `reader` in chunks, passing each chunk and its encoding to `emit`. Line breaks between
+// chunks are part of the encoding, so the pieces can simply be concatenated.
+fn encode_chunks(
+    mut reader: impl Read,
+    alphabet: Alphabet,
+    padding: Padding,
+    line_wrap: LineWrap,
+    mut emit: impl FnMut(&[u8], &str) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut chunk = vec![0; CHUNK_SIZE];
+    let mut is_first = true;
+
+    loop {
+        let mut filled = 0;
+        while filled < CHUNK_SIZE {
+            match reader.read(&mut chunk[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        if filled == 0 {
+            break;
+        }
+        let mut encoded = String::new();
+        if !is_first && line_wrap.columns().is_some() {
+            encoded.push('\n');
+        }
+        encoded.push_str(&encode(&chunk[..filled], alphabet, padding, line_wrap));
+        emit(&chunk[..filled], &encoded)?;
+        is_first = false;
+        if filled < CHUNK_SIZE {
+            break;
+        }
+    }
+    Ok(())
+}
+
+pub fn data_uri(mime: &str, encoded: &str) -> String {
+    format!("data:{};base64,{}", mime, encoded)
+}
+
+/// Splits a `data:<mime>;base64,<payload>` URI into its MIME type and payload. Returns `None`
+/// for anything that isn't a Base64 data URI.
+pub fn parse_data_uri(uri: &str) -> Option<(&str, &str)> {
+    let (header, payload) = uri.trim_start().strip_prefix("data:")?.split_once(',')?;
+    let mime = header.strip_suffix(";base64")?;
+    if mime.is_empty() {
+        Some(("text/plain;charset=US-ASCII", payload))
+    } else {
+        Some((mime, payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc_4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (text, expected) in vectors {
+            let encoded = encode(
+                text.as_bytes(),
+                Alphabet::Standard,
+                Padding::Required,
+                LineWrap::Unwrapped,
+            );
+            assert_eq!(encoded, expected);
+            assert_eq!(
+                decode(expected, Alphabet::Standard, Padding::Required).unwrap(),
+                text.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn alphabets_and_padding() {
+        let bytes = [0xfb, 0xff];
+        let encode = |alphabet, padding| encode(&bytes, alphabet, padding, LineWrap::Unwrapped);
+        assert_eq!(encode(Alphabet::Standard, Padding::Required), "+/8=");
+        assert_eq!(encode(Alphabet::UrlSafe, Padding::Required), "-_8=");
+        assert_eq!(encode(Alphabet::UrlSafe, Padding::Omitted), "-_8");
+
+        assert!(decode("-_8", Alphabet::UrlSafe, Padding::Required).is_err());
+        assert!(decode("-_8=", Alphabet::UrlSafe, Padding::Omitted).is_err());
+        for encoded in ["-_8", "-_8="] {
+            assert_eq!(
+                decode(encoded, Alphabet::UrlSafe, Padding::Optional).unwrap(),
+                bytes
+            );
+        }
+        assert!(decode("+/8=", Alphabet::UrlSafe, Padding::Required).is_err());
+
+        assert_eq!(Alphabet::detect("-_8"), Some(Alphabet::UrlSafe));
+        assert_eq!(Alphabet::detect("+/8="), Some(Alphabet::Standard));
+        assert_eq!(Alphabet::detect("Zm9v"), None);
+    }
+
+    #[test]
+    fn line_wrapping() {
+        let bytes = [0u8; 100];
+        for (line_wrap, columns) in [(LineWrap::Mime, 76), (LineWrap::Pem, 64)] {
+            let encoded = encode(&bytes, Alphabet::Standard, Padding::Required, line_wrap);
+            let lines: Vec<&str> = encoded.lines().collect();
+            assert_eq!(lines.concat().len(), 136);
+            assert!(lines[..lines.len() - 1]
+                .iter()
+                .all(|line| line.len() == columns));
+            assert_eq!(
+                decode(&encoded, Alphabet::Standard, Padding::Required).unwrap(),
+                bytes
+            );
+        }
+    }
+
+    #[test]
+    fn errors_point_into_the_original_text() {
+        let encoded = "Zm9v\n Y!==";
+        let error = decode(encoded, Alphabet::Standard, Padding::Required).unwrap_err();
+        assert_eq!(error.offset, Some(7));
+        assert!(error.message.contains('!'), "{}", error.message);
+
+        let error = decode("Zm9vY", Alphabet::Standard, Padding::Required).unwrap_err();
+        assert_eq!(error.offset, None);
+
+        let error = decode("Zm8=", Alphabet::Standard, Padding::Omitted).unwrap_err();
+        assert_eq!(error.offset, Some(3));
+    }
+
+    #[test]
+    fn streamed_encoding_matches_encode() {
+        // Crosses chunk boundaries and ends with a partial group.
+        let bytes: Vec<u8> = (0..CHUNK_SIZE * 2 + 5).map(|i| (i * 7) as u8).collect();
+        for line_wrap in LineWrap::ALL {
+            let expected = encode(&bytes, Alphabet::Standard, Padding::Required, line_wrap);
+
+            let (read, encoded) = encode_reader(
+                bytes.as_slice(),
+                Alphabet::Standard,
+                Padding::Required,
+                line_wrap,
+            )
+            .unwrap();
+            assert_eq!(read, bytes);
+            assert_eq!(encoded, expected);
+
+            let mut written = Vec::new();
+            let mut reports = Vec::new();
+            let total = encode_to_writer(
+                bytes.as_slice(),
+                &mut written,
+                Alphabet::Standard,
+                Padding::Required,
+                line_wrap,
+                &AtomicBool::new(false),
+                |read| reports.push(read),
+            )
+            .unwrap();
+            assert_eq!(total, bytes.len() as u64);
+            assert_eq!(reports.last(), Some(&total));
+            assert_eq!(String::from_utf8(written).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn streamed_encoding_can_be_cancelled() {
+        let result = encode_to_writer(
+            [0u8; 10].as_slice(),
+            Vec::new(),
+            Alphabet::Standard,
+            Padding::Required,
+            LineWrap::Unwrapped,
+            &AtomicBool::new(true),
+            |_| (),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn data_uris() {
+        assert_eq!(data_uri("image/png", "AAAA"), "data:image/png;base64,AAAA");
+        assert_eq!(
+            parse_data_uri(" data:image/png;base64,AAAA"),
+            Some(("image/png", "AAAA"))
+        );
+        assert_eq!(
+            parse_data_uri("data:;base64,AAAA"),
+            Some(("text/plain;charset=US-ASCII", "AAAA"))
+        );
+        assert_eq!(parse_data_uri("data:text/plain,hello"), None);
+        assert_eq!(parse_data_uri("AAAA"), None);
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+use iced::advanced::text::highlighter::{self, Highlighter};
+use iced::{Color, Font, Theme};
+
+pub const ERROR_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+
+/// Position of a single offending character: the line it's on and its byte range in that line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub columns: Range<usize>,
+}
+
+impl Position {
+    pub fn locate(text: &str, offset: usize) -> Option<Position> {
+        let character = text.get(offset..)?.chars().next()?;
+        let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+        Some(Position {
+            line: text[..offset].matches('\n').count(),
+            columns: offset - line_start..offset - line_start + character.len_utf8(),
+        })
+    }
+}
+
+pub struct ErrorHighlighter {
+    position: Option<Position>,
+    current_line: usize,
+}
+
+impl Highlighter for ErrorHighlighter {
+    type Settings = Option<Position>;
+    type Highlight = ();
+
+    type Iterator<'a> = std::option::IntoIter<(Range<usize>, ())>;
+
+    fn new(settings: &Self::Settings) -> Self {
+        Self {
+            position: settings.clone(),
+            current_line: 0,
+        }
+    }
+
+    fn update(&mut self, new_settings: &Self::Settings) {
+        self.position = new_settings.clone();
+        self.current_line = 0;
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = self.current_line.min(line);
+    }
+
+    fn highlight_line(&mut self, _line: &str) -> Self::Iterator<'_> {
+        let line = self.current_line;
+        self.current_line += 1;
+        self.position
+            .as_ref()
+            .filter(|position| position.line == line)
+            .map(|position| (position.columns.clone(), ()))
+            .into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+pub fn to_format(_highlight: &(), _theme: &Theme) -> highlighter::Format<Font> {
+    highlighter::Format {
+        color: Some(ERROR_COLOR),
+        font: None,
+    }
+}
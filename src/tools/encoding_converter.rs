@@ -0,0 +1,194 @@
+use iced::{
+    widget::{column, container, horizontal_space, pick_list, row, scrollable, text, text_editor},
+    Color, Element, Length,
+};
+
+use codecs::{Codec, HexCase, Separator};
+
+mod codecs;
+
+pub const NAME: &str = "Encoding Converter";
+
+const ERROR_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    DecodedTextChanged(text_editor::Action),
+    EncodedTextChanged(text_editor::Action),
+    CodecSelected(Codec),
+    HexCaseSelected(HexCase),
+    SeparatorSelected(Separator),
+}
+
+pub struct EncodingConverter {
+    tool_name: String,
+    encoded_input: text_editor::Content,
+    decoded_input: text_editor::Content,
+    codec: Option<Codec>,
+    hex_case: Option<HexCase>,
+    separator: Option<Separator>,
+    encode_error: Option<String>,
+    decode_error: Option<String>,
+    decoded_bytes: Vec<u8>,
+    is_lossy: bool,
+}
+
+impl Default for EncodingConverter {
+    fn default() -> Self {
+        EncodingConverter::new()
+    }
+}
+
+impl EncodingConverter {
+    pub fn new() -> Self {
+        Self {
+            tool_name: NAME.to_string(),
+            encoded_input: text_editor::Content::new(),
+            decoded_input: text_editor::Content::new(),
+            codec: Some(Codec::default()),
+            hex_case: Some(HexCase::default()),
+            separator: Some(Separator::default()),
+            encode_error: None,
+            decode_error: None,
+            decoded_bytes: vec![],
+            is_lossy: false,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mut configuration = row![pick_list(
+            &Codec::ALL[..],
+            self.codec,
+            Message::CodecSelected
+        )]
+        .spacing(10)
+        .padding([10, 0]);
+        if self.codec == Some(Codec::Hex) {
+            configuration = configuration
+                .push(pick_list(
+                    &HexCase::ALL[..],
+                    self.hex_case,
+                    Message::HexCaseSelected,
+                ))
+                .push(pick_list(
+                    &Separator::ALL[..],
+                    self.separator,
+                    Message::SeparatorSelected,
+                ));
+        }
+
+        let decoded_status = if self.decode_error.is_some() {
+            "Out of date: the encoded input has errors"
+        } else if self.is_lossy {
+            "Decoded bytes aren't valid UTF-8, invalid sequences are shown as \u{fffd}"
+        } else {
+            ""
+        };
+        let decoded_panel = column![
+            row![
+                "Decoded",
+                text(decoded_status).color(ERROR_COLOR),
+                horizontal_space(),
+                text(self.encode_error.clone().unwrap_or_default()).color(ERROR_COLOR),
+            ]
+            .spacing(10),
+            container(scrollable(
+                text_editor(&self.decoded_input).on_action(Message::DecodedTextChanged)
+            ))
+            .height(Length::Fill),
+        ];
+
+        let encoded_panel = column![
+            row![
+                "Encoded",
+                text(self.decode_error.clone().unwrap_or_default()).color(ERROR_COLOR),
+                horizontal_space(),
+            ]
+            .spacing(10),
+            container(scrollable(
+                text_editor(&self.encoded_input).on_action(Message::EncodedTextChanged)
+            ))
+            .height(Length::Fill),
+        ];
+
+        let content = container(column![configuration, decoded_panel, encoded_panel]).padding(10);
+        content.into()
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::DecodedTextChanged(text_action) => {
+                let old_text = self.decoded_input.text();
+                self.decoded_input.perform(text_action);
+                if old_text != self.decoded_input.text() {
+                    self.decoded_bytes =
+                        trim_trailing_newlines(self.decoded_input.text()).into_bytes();
+                    self.is_lossy = false;
+                    self.encode();
+                }
+            }
+            Message::EncodedTextChanged(text_action) => {
+                let old_text = self.encoded_input.text();
+                self.encoded_input.perform(text_action);
+                if old_text != self.encoded_input.text() {
+                    self.decode();
+                }
+            }
+            Message::CodecSelected(codec) => {
+                self.codec = Some(codec);
+                self.encode();
+            }
+            Message::HexCaseSelected(hex_case) => {
+                self.hex_case = Some(hex_case);
+                self.encode();
+            }
+            Message::SeparatorSelected(separator) => {
+                self.separator = Some(separator);
+                self.encode();
+            }
+        }
+    }
+
+    fn encode(&mut self) {
+        // Encode the last decoded bytes rather than the editor text, which is lossy for non-UTF-8
+        // payloads.
+        match codecs::encode(
+            &self.decoded_bytes,
+            self.codec.unwrap_or_default(),
+            self.hex_case.unwrap_or_default(),
+            self.separator.unwrap_or_default(),
+        ) {
+            Ok(encoded) => {
+                self.encoded_input = text_editor::Content::with_text(&encoded);
+                self.encode_error = None;
+                self.decode_error = None;
+            }
+            Err(e) => self.encode_error = Some(e),
+        }
+    }
+
+    fn decode(&mut self) {
+        let encoded = trim_trailing_newlines(self.encoded_input.text());
+        match codecs::decode(&encoded, self.codec.unwrap_or_default()) {
+            Ok(decoded) => {
+                let text = String::from_utf8_lossy(&decoded);
+                self.is_lossy = std::str::from_utf8(&decoded).is_err();
+                self.decoded_input = text_editor::Content::with_text(&text);
+                self.decoded_bytes = decoded;
+                self.encode_error = None;
+                self.decode_error = None;
+            }
+            Err(e) => self.decode_error = Some(e),
+        }
+    }
+}
+
+fn trim_trailing_newlines(mut text: String) -> String {
+    let len = text.trim_end_matches(&['\r', '\n'][..]).len();
+    text.truncate(len);
+    text
+}
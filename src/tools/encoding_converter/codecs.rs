@@ -0,0 +1,722 @@
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+// Characters RFC 3986 allows in a URL outside of percent-encoding, on top of the unreserved ones.
+const URL_RESERVED: &[u8] = b":/?#[]@!$&'()*+,;=";
+
+const NAMED_ENTITIES: [(&str, char); 16] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("hellip", '…'),
+    ("mdash", '—'),
+    ("ndash", '–'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("euro", '€'),
+    ("deg", '°'),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Codec {
+    #[default]
+    Base32,
+    Base32Crockford,
+    Base58,
+    Ascii85,
+    Z85,
+    Hex,
+    PercentComponent,
+    PercentUrl,
+    HtmlEntities,
+}
+
+impl Codec {
+    pub const ALL: [Codec; 9] = [
+        Codec::Base32,
+        Codec::Base32Crockford,
+        Codec::Base58,
+        Codec::Ascii85,
+        Codec::Z85,
+        Codec::Hex,
+        Codec::PercentComponent,
+        Codec::PercentUrl,
+        Codec::HtmlEntities,
+    ];
+}
+
+impl std::fmt::Display for Codec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Codec::Base32 => "Base32 (RFC 4648)",
+                Codec::Base32Crockford => "Base32 (Crockford)",
+                Codec::Base58 => "Base58 (Bitcoin)",
+                Codec::Ascii85 => "Ascii85",
+                Codec::Z85 => "Z85",
+                Codec::Hex => "Hex",
+                Codec::PercentComponent => "Percent-encoding (URL component)",
+                Codec::PercentUrl => "Percent-encoding (full URL)",
+                Codec::HtmlEntities => "HTML entities",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexCase {
+    #[default]
+    Lower,
+    Upper,
+}
+
+impl HexCase {
+    pub const ALL: [HexCase; 2] = [HexCase::Lower, HexCase::Upper];
+}
+
+impl std::fmt::Display for HexCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                HexCase::Lower => "Lowercase",
+                HexCase::Upper => "Uppercase",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Separator {
+    #[default]
+    None,
+    Space,
+    Colon,
+    Dash,
+}
+
+impl Separator {
+    pub const ALL: [Separator; 4] = [
+        Separator::None,
+        Separator::Space,
+        Separator::Colon,
+        Separator::Dash,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Separator::None => "",
+            Separator::Space => " ",
+            Separator::Colon => ":",
+            Separator::Dash => "-",
+        }
+    }
+}
+
+impl std::fmt::Display for Separator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Separator::None => "No separator",
+                Separator::Space => "Space separated",
+                Separator::Colon => "Colon separated",
+                Separator::Dash => "Dash separated",
+            }
+        )
+    }
+}
+
+pub fn encode(
+    bytes: &[u8],
+    codec: Codec,
+    hex_case: HexCase,
+    separator: Separator,
+) -> Result<String, String> {
+    match codec {
+        Codec::Base32 => Ok(encode_base32(bytes, BASE32_ALPHABET, true)),
+        Codec::Base32Crockford => Ok(encode_base32(bytes, CROCKFORD_ALPHABET, false)),
+        Codec::Base58 => Ok(encode_base58(bytes)),
+        Codec::Ascii85 => Ok(encode_ascii85(bytes)),
+        Codec::Z85 => encode_z85(bytes),
+        Codec::Hex => Ok(encode_hex(bytes, hex_case, separator)),
+        Codec::PercentComponent => Ok(encode_percent(bytes, b"")),
+        Codec::PercentUrl => Ok(encode_percent(bytes, URL_RESERVED)),
+        Codec::HtmlEntities => Ok(encode_html(&String::from_utf8_lossy(bytes))),
+    }
+}
+
+pub fn decode(encoded: &str, codec: Codec) -> Result<Vec<u8>, String> {
+    match codec {
+        Codec::Base32 => decode_base32(encoded, false, |c| {
+            BASE32_ALPHABET
+                .iter()
+                .position(|&a| a == c.to_ascii_uppercase())
+        }),
+        Codec::Base32Crockford => decode_base32(encoded, true, |c| {
+            let c = match c.to_ascii_uppercase() {
+                b'O' => b'0',
+                b'I' | b'L' => b'1',
+                c => c,
+            };
+            CROCKFORD_ALPHABET.iter().position(|&a| a == c)
+        }),
+        Codec::Base58 => decode_base58(encoded),
+        Codec::Ascii85 => decode_ascii85(encoded),
+        Codec::Z85 => decode_z85(encoded),
+        Codec::Hex => decode_hex(encoded),
+        Codec::PercentComponent | Codec::PercentUrl => decode_percent(encoded),
+        Codec::HtmlEntities => decode_html(encoded).map(String::into_bytes),
+    }
+}
+
+fn invalid_character(c: char, position: usize) -> String {
+    format!(
+        "Invalid character '{}' at position {}",
+        c.escape_debug(),
+        position + 1
+    )
+}
+
+/// Yields the non-whitespace characters of `encoded` along with their 0-based character index.
+fn significant_chars(encoded: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    encoded
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_whitespace())
+}
+
+fn encode_base32(bytes: &[u8], alphabet: &[u8; 32], padding: bool) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    for chunk in bytes.chunks(5) {
+        let mut buffer = [0u8; 5];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let value = buffer.iter().fold(0u64, |acc, &b| acc << 8 | u64::from(b));
+        let symbols = (chunk.len() * 8).div_ceil(5);
+        for i in 0..symbols {
+            output.push(alphabet[(value >> (35 - i * 5)) as usize & 31] as char);
+        }
+        if padding {
+            output.extend(std::iter::repeat_n('=', 8 - symbols));
+        }
+    }
+    output
+}
+
+fn decode_base32(
+    encoded: &str,
+    skip_hyphens: bool,
+    lookup: impl Fn(u8) -> Option<usize>,
+) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer = 0u64;
+    let mut bits = 0;
+    let mut padding_at = None;
+
+    for (position, c) in significant_chars(encoded).filter(|(_, c)| !(skip_hyphens && *c == '-')) {
+        if c == '=' {
+            padding_at.get_or_insert(position);
+            continue;
+        }
+        let value = u8::try_from(c)
+            .ok()
+            .and_then(&lookup)
+            .filter(|_| padding_at.is_none())
+            .ok_or_else(|| invalid_character(c, position))?;
+        buffer = buffer << 5 | value as u64;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    if bits >= 5 {
+        return Err(String::from(
+            "Invalid length: trailing symbol doesn't form a byte",
+        ));
+    }
+    Ok(output)
+}
+
+fn encode_base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // Base-58 digits of the number, least significant first.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    std::iter::repeat_n('1', zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|&d| BASE58_ALPHABET[d as usize] as char),
+        )
+        .collect()
+}
+
+fn decode_base58(encoded: &str) -> Result<Vec<u8>, String> {
+    let mut zeros = 0;
+    let mut leading = true;
+    // Bytes of the number, least significant first.
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len());
+    for (position, c) in significant_chars(encoded) {
+        let value = u8::try_from(c)
+            .ok()
+            .and_then(|c| BASE58_ALPHABET.iter().position(|&a| a == c))
+            .ok_or_else(|| invalid_character(c, position))?;
+        if leading && value == 0 {
+            zeros += 1;
+            continue;
+        }
+        leading = false;
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    bytes.extend(std::iter::repeat_n(0, zeros));
+    bytes.reverse();
+    Ok(bytes)
+}
+
+fn encode_ascii85(bytes: &[u8]) -> String {
+    let mut output = String::from("<~");
+    for chunk in bytes.chunks(4) {
+        if chunk == [0, 0, 0, 0] {
+            output.push('z');
+            continue;
+        }
+        let mut buffer = [0u8; 4];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let symbols = base85_symbols(u32::from_be_bytes(buffer));
+        output.extend(
+            symbols[..chunk.len() + 1]
+                .iter()
+                .map(|&s| (s + b'!') as char),
+        );
+    }
+    output.push_str("~>");
+    output
+}
+
+fn decode_ascii85(encoded: &str) -> Result<Vec<u8>, String> {
+    let trimmed = encoded.trim();
+    let body = trimmed.strip_prefix("<~").unwrap_or(trimmed);
+    let body = body.strip_suffix("~>").unwrap_or(body);
+    let offset = encoded[..encoded.len() - encoded.trim_start().len()]
+        .chars()
+        .count()
+        + if trimmed.starts_with("<~") { 2 } else { 0 };
+
+    let mut output = Vec::with_capacity(body.len() * 4 / 5);
+    let mut group = Vec::with_capacity(5);
+    for (position, c) in significant_chars(body) {
+        match c {
+            'z' if group.is_empty() => output.extend_from_slice(&[0, 0, 0, 0]),
+            '!'..='u' => {
+                group.push(c as u8 - b'!');
+                if group.len() == 5 {
+                    output.extend_from_slice(&base85_group(&group, offset + position)?);
+                    group.clear();
+                }
+            }
+            _ => return Err(invalid_character(c, offset + position)),
+        }
+    }
+    match group.len() {
+        0 => (),
+        1 => {
+            return Err(String::from(
+                "Invalid length: a final group needs at least 2 symbols",
+            ))
+        }
+        n => {
+            let missing = 5 - n;
+            group.extend(std::iter::repeat_n(84, missing));
+            let bytes = base85_group(&group, body.chars().count())?;
+            output.extend_from_slice(&bytes[..4 - missing]);
+        }
+    }
+    Ok(output)
+}
+
+fn encode_z85(bytes: &[u8]) -> Result<String, String> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(format!(
+            "Z85 can only encode a multiple of 4 bytes, got {}",
+            bytes.len()
+        ));
+    }
+    Ok(bytes
+        .chunks(4)
+        .flat_map(|chunk| {
+            base85_symbols(u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        })
+        .map(|s| Z85_ALPHABET[s as usize] as char)
+        .collect())
+}
+
+fn decode_z85(encoded: &str) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(encoded.len() * 4 / 5);
+    let mut group = Vec::with_capacity(5);
+    for (position, c) in significant_chars(encoded) {
+        let value = u8::try_from(c)
+            .ok()
+            .and_then(|c| Z85_ALPHABET.iter().position(|&a| a == c))
+            .ok_or_else(|| invalid_character(c, position))?;
+        group.push(value as u8);
+        if group.len() == 5 {
+            output.extend_from_slice(&base85_group(&group, position)?);
+            group.clear();
+        }
+    }
+    if !group.is_empty() {
+        return Err(String::from(
+            "Invalid length: Z85 input must be a multiple of 5 characters",
+        ));
+    }
+    Ok(output)
+}
+
+fn base85_symbols(mut value: u32) -> [u8; 5] {
+    let mut symbols = [0u8; 5];
+    for symbol in symbols.iter_mut().rev() {
+        *symbol = (value % 85) as u8;
+        value /= 85;
+    }
+    symbols
+}
+
+fn base85_group(group: &[u8], position: usize) -> Result<[u8; 4], String> {
+    group
+        .iter()
+        .try_fold(0u32, |acc, &s| {
+            acc.checked_mul(85)?.checked_add(u32::from(s))
+        })
+        .map(u32::to_be_bytes)
+        .ok_or_else(|| {
+            format!(
+                "Group ending at position {} overflows 32 bits",
+                position + 1
+            )
+        })
+}
+
+fn encode_hex(bytes: &[u8], hex_case: HexCase, separator: Separator) -> String {
+    bytes
+        .iter()
+        .map(|b| match hex_case {
+            HexCase::Lower => format!("{:02x}", b),
+            HexCase::Upper => format!("{:02X}", b),
+        })
+        .collect::<Vec<_>>()
+        .join(separator.as_str())
+}
+
+fn decode_hex(encoded: &str) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(encoded.len() / 2);
+    let mut high = None;
+    for (position, c) in significant_chars(encoded).filter(|(_, c)| !matches!(c, ':' | '-')) {
+        let value = c
+            .to_digit(16)
+            .ok_or_else(|| invalid_character(c, position))? as u8;
+        match high.take() {
+            Some(high) => output.push(high << 4 | value),
+            None => high = Some(value),
+        }
+    }
+    if high.is_some() {
+        return Err(String::from("Invalid length: odd number of hex digits"));
+    }
+    Ok(output)
+}
+
+fn encode_percent(bytes: &[u8], keep: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() * 3);
+    for &b in bytes {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) || keep.contains(&b) {
+            output.push(b as char);
+        } else {
+            output.push_str(&format!("%{:02X}", b));
+        }
+    }
+    output
+}
+
+fn decode_percent(encoded: &str) -> Result<Vec<u8>, String> {
+    let bytes = encoded.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let value = encoded
+                .get(i + 1..i + 3)
+                // `from_str_radix` also accepts a leading sign, as in "%+1".
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid percent-escape at position {}",
+                        encoded[..i].chars().count() + 1
+                    )
+                })?;
+            output.push(value);
+            i += 3;
+        } else {
+            output.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(output)
+}
+
+fn encode_html(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+fn decode_html(encoded: &str) -> Result<String, String> {
+    let mut output = String::with_capacity(encoded.len());
+    let mut rest = encoded;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        let position = encoded[..encoded.len() - rest.len() + start]
+            .chars()
+            .count()
+            + 1;
+        let entity = rest[start + 1..].split_once(';').map(|(entity, _)| entity);
+        let decoded = match entity.map(|entity| (entity, entity.strip_prefix('#'))) {
+            // Anything starting with `&#` is meant as a character reference, so a bad one is an
+            // error. As with percent-escapes, the number parsers would take a leading sign too.
+            Some((entity, Some(number)))
+                if !number.is_empty()
+                    && !number.contains(|c: char| c.is_whitespace() || c == '&') =>
+            {
+                let decoded = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) if hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                        u32::from_str_radix(hex, 16).ok()
+                    }
+                    Some(_) => None,
+                    None if number.bytes().all(|b| b.is_ascii_digit()) => {
+                        number.parse::<u32>().ok()
+                    }
+                    None => None,
+                }
+                .and_then(char::from_u32)
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid character reference '&{};' at position {}",
+                        entity, position
+                    )
+                })?;
+                Some((entity, decoded))
+            }
+            Some((entity, None)) if entity.bytes().all(|b| b.is_ascii_alphanumeric()) => {
+                NAMED_ENTITIES
+                    .iter()
+                    .find(|(name, _)| *name == entity)
+                    .map(|(_, c)| (entity, *c))
+            }
+            _ => None,
+        };
+        // Any other `&`, like in `AT&T;` or `?a=1&b=2;`, is kept as is, like browsers do.
+        let Some((entity, decoded)) = decoded else {
+            output.push('&');
+            rest = &rest[start + 1..];
+            continue;
+        };
+        output.push(decoded);
+        rest = &rest[start + entity.len() + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(bytes: &[u8], codec: Codec) -> String {
+        encode(bytes, codec, HexCase::Lower, Separator::None).unwrap()
+    }
+
+    fn decoded(encoded: &str, codec: Codec) -> Vec<u8> {
+        decode(encoded, codec).unwrap_or_else(|e| panic!("{:?} '{}': {}", codec, encoded, e))
+    }
+
+    #[test]
+    fn base32_rfc_4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "MY======"),
+            ("fo", "MZXQ===="),
+            ("foo", "MZXW6==="),
+            ("foob", "MZXW6YQ="),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI======"),
+        ];
+        for (text, expected) in vectors {
+            assert_eq!(encoded(text.as_bytes(), Codec::Base32), expected);
+            assert_eq!(decoded(expected, Codec::Base32), text.as_bytes());
+        }
+        assert_eq!(decoded("mzxw6ytboi", Codec::Base32), b"foobar");
+        assert!(decode("MZ=XW6===", Codec::Base32).is_err());
+        assert!(decode("M", Codec::Base32).is_err());
+    }
+
+    #[test]
+    fn base32_crockford() {
+        assert_eq!(encoded(b"foobar", Codec::Base32Crockford), "CSQPYRK1E8");
+        assert_eq!(decoded("csqpyrk1e8", Codec::Base32Crockford), b"foobar");
+        assert_eq!(decoded("CSQP-YRKI-E8", Codec::Base32Crockford), b"foobar");
+        assert_eq!(decoded("OO", Codec::Base32Crockford), [0]);
+        assert!(decode("CSQPYRKUE8", Codec::Base32Crockford).is_err());
+    }
+
+    #[test]
+    fn base58_bitcoin_vectors() {
+        let vectors: [(&[u8], &str); 4] = [
+            (b"", ""),
+            (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+            (
+                b"The quick brown fox jumps over the lazy dog",
+                "7DdiPPYtxLjCD3wA1po2rvZHTDYjkZYiEtazrfiwJcwnKCizhGFhBGHeRdx",
+            ),
+            (&[0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+        ];
+        for (bytes, expected) in vectors {
+            assert_eq!(encoded(bytes, Codec::Base58), expected);
+            assert_eq!(decoded(expected, Codec::Base58), bytes);
+        }
+        assert!(decode("0OIl", Codec::Base58).is_err());
+    }
+
+    #[test]
+    fn ascii85_adobe_vectors() {
+        assert_eq!(
+            encoded(b"Man is distinguished", Codec::Ascii85),
+            "<~9jqo^BlbD-BleB1DJ+*+F(f,q~>"
+        );
+        assert_eq!(encoded(b"sure.", Codec::Ascii85), "<~F*2M7/c~>");
+        assert_eq!(encoded(b"\0\0\0\0ab", Codec::Ascii85), "<~z@:B~>");
+        assert_eq!(decoded("<~F*2M7/c~>", Codec::Ascii85), b"sure.");
+        assert_eq!(decoded("F*2M7 /c", Codec::Ascii85), b"sure.");
+        assert_eq!(decoded("<~z@:B~>", Codec::Ascii85), b"\0\0\0\0ab");
+        assert!(decode("<~F~>", Codec::Ascii85).is_err());
+        assert!(decode("<~uuuuu~>", Codec::Ascii85).is_err());
+    }
+
+    #[test]
+    fn z85_spec_vector() {
+        let bytes = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+        assert_eq!(encoded(&bytes, Codec::Z85), "HelloWorld");
+        assert_eq!(decoded("HelloWorld", Codec::Z85), bytes);
+        assert!(encode(b"abc", Codec::Z85, HexCase::Lower, Separator::None).is_err());
+        assert!(decode("Hello", Codec::Z85).is_ok());
+        assert!(decode("Hell", Codec::Z85).is_err());
+    }
+
+    #[test]
+    fn hex_cases_and_separators() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef];
+        assert_eq!(encoded(&bytes, Codec::Hex), "deadbeef");
+        assert_eq!(
+            encode(&bytes, Codec::Hex, HexCase::Upper, Separator::Colon).unwrap(),
+            "DE:AD:BE:EF"
+        );
+        assert_eq!(
+            encode(&bytes, Codec::Hex, HexCase::Lower, Separator::Space).unwrap(),
+            "de ad be ef"
+        );
+        assert_eq!(decoded("de:ad-BE ef", Codec::Hex), bytes);
+        assert!(decode("dea", Codec::Hex).is_err());
+        assert!(decode("+1", Codec::Hex).is_err());
+    }
+
+    #[test]
+    fn percent_encoding() {
+        let text = "a b/ü?";
+        assert_eq!(
+            encoded(text.as_bytes(), Codec::PercentComponent),
+            "a%20b%2F%C3%BC%3F"
+        );
+        assert_eq!(encoded(text.as_bytes(), Codec::PercentUrl), "a%20b/%C3%BC?");
+        assert_eq!(
+            decoded("a%20b%2f%C3%BC?", Codec::PercentComponent),
+            text.as_bytes()
+        );
+        for invalid in ["%", "%4", "%zz", "%+1", "%-1", "a%2"] {
+            assert!(
+                decode(invalid, Codec::PercentComponent).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn html_entities() {
+        assert_eq!(
+            encoded(br#"<a href="x">&'"#, Codec::HtmlEntities),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+        assert_eq!(
+            decoded("&lt;&#65;&#x42;&amp;&euro; & x", Codec::HtmlEntities),
+            "<AB&€ & x".as_bytes()
+        );
+        for literal in ["AT&T;", "?a=1&b=2;", "&bogus;", "a & b;", "&;", "&#;"] {
+            assert_eq!(
+                decoded(literal, Codec::HtmlEntities),
+                literal.as_bytes(),
+                "{}",
+                literal
+            );
+        }
+        assert_eq!(
+            decoded("AT&T; &lt;3", Codec::HtmlEntities),
+            "AT&T; <3".as_bytes()
+        );
+        for invalid in ["&#+65;", "&#x+41;", "&#xD800;", "&#12a;"] {
+            assert!(decode(invalid, Codec::HtmlEntities).is_err(), "{}", invalid);
+        }
+    }
+}
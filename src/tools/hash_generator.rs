@@ -0,0 +1,366 @@
+use std::fs::{self, File};
+use std::io::{self, Cursor, Read};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use iced::futures::channel::mpsc;
+use iced::task::Handle;
+use iced::widget::{
+    button, column, container, horizontal_space, pick_list, progress_bar, row, scrollable, text,
+    text_editor, text_input, Column,
+};
+use iced::{clipboard, Alignment, Color, Element, Font, Length, Task};
+use rfd::AsyncFileDialog;
+
+use algorithm::{Algorithm, Digests, Encoding};
+
+pub mod algorithm;
+
+pub const NAME: &str = "Hash Generator";
+
+const MATCH_COLOR: Color = Color::from_rgb(0.1, 0.55, 0.1);
+const ERROR_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+// Text longer than this is hashed on the background thread, like files, so typing into a large
+// text doesn't run every algorithm on the UI thread for each keystroke.
+const BACKGROUND_TEXT_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub enum HashEvent {
+    Read(u64),
+    Finished(Result<Digests, String>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    TextChanged(text_editor::Action),
+    EncodingSelected(Encoding),
+    HashFileClicked,
+    FilePicked(Option<PathBuf>),
+    FileDropped(PathBuf),
+    HashProgress(HashEvent),
+    CancelHashClicked,
+    HashTextClicked,
+    ExpectedChanged(String),
+    CopyClicked(Algorithm),
+}
+
+pub struct HashGenerator {
+    tool_name: String,
+    input: text_editor::Content,
+    file: Option<PathBuf>,
+    encoding: Option<Encoding>,
+    digests: Digests,
+    hashing: Option<(Handle, Arc<AtomicBool>)>,
+    hash_total: u64,
+    hash_read: u64,
+    error: Option<String>,
+    expected: String,
+}
+
+impl Default for HashGenerator {
+    fn default() -> Self {
+        HashGenerator::new()
+    }
+}
+
+impl HashGenerator {
+    pub fn new() -> Self {
+        Self {
+            tool_name: NAME.to_string(),
+            input: text_editor::Content::new(),
+            file: None,
+            encoding: Some(Encoding::default()),
+            digests: algorithm::hash(b""),
+            hashing: None,
+            hash_total: 0,
+            hash_read: 0,
+            error: None,
+            expected: String::new(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    // The editor always reports a trailing newline, so the lines are joined by hand to hash
+    // exactly what was typed.
+    fn input_text(&self) -> String {
+        self.input
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Stops the running hash, returning whether there was one.
+    fn stop_hashing(&mut self) -> bool {
+        match self.hashing.take() {
+            Some((handle, cancelled)) => {
+                cancelled.store(true, Ordering::Relaxed);
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn hash_text(&mut self) -> Task<Message> {
+        self.stop_hashing();
+        let text = self.input_text();
+        if text.len() <= BACKGROUND_TEXT_SIZE {
+            self.digests = algorithm::hash(text.as_bytes());
+            self.error = None;
+            return Task::none();
+        }
+        self.hash_total = text.len() as u64;
+        self.start_hashing(String::from("the text"), move || {
+            Ok(Cursor::new(text.into_bytes()))
+        })
+    }
+
+    fn hash_file(&mut self, path: PathBuf) -> Task<Message> {
+        self.stop_hashing();
+        self.hash_total = fs::metadata(&path).map(|m| m.len()).unwrap_or_default();
+        self.file = Some(path.clone());
+        self.start_hashing(path.display().to_string(), move || File::open(path))
+    }
+
+    fn start_hashing<R: Read>(
+        &mut self,
+        name: String,
+        open: impl FnOnce() -> io::Result<R> + Send + 'static,
+    ) -> Task<Message> {
+        self.hash_read = 0;
+        self.digests.clear();
+        self.error = None;
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (task, handle) = Task::run(
+            hash_in_background(name, open, cancelled.clone()),
+            Message::HashProgress,
+        )
+        .abortable();
+        self.hashing = Some((handle, cancelled));
+        task
+    }
+
+    fn matching_algorithm(&self) -> Option<Result<Algorithm, &'static str>> {
+        if self.expected.trim().is_empty() || self.digests.is_empty() {
+            return None;
+        }
+        let Some(expected) = algorithm::parse_expected(&self.expected) else {
+            return Some(Err("The expected checksum is not valid hex or Base64"));
+        };
+        Some(
+            self.digests
+                .iter()
+                .find(|(_, digest)| *digest == expected)
+                .map(|(algorithm, _)| *algorithm)
+                .ok_or("The expected checksum does not match any algorithm"),
+        )
+    }
+
+    fn input_view(&self) -> Element<'_, Message> {
+        match &self.file {
+            Some(path) => {
+                let size = match self.hash_total {
+                    1 => String::from("1 byte"),
+                    size => format!("{} bytes", size),
+                };
+                row![
+                    text(format!("File: {} ({})", path.display(), size)),
+                    horizontal_space(),
+                    button("Hash text instead").on_press(Message::HashTextClicked),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into()
+            }
+            None => column![
+                "Text",
+                container(scrollable(
+                    text_editor(&self.input).on_action(Message::TextChanged)
+                ))
+                .height(150),
+            ]
+            .spacing(5)
+            .into(),
+        }
+    }
+
+    fn progress_view(&self) -> Element<'_, Message> {
+        if self.hashing.is_none() {
+            return column![].into();
+        }
+        const MEGABYTE: f64 = 1024.0 * 1024.0;
+        row![
+            progress_bar(0.0..=self.hash_total as f32, self.hash_read as f32)
+                .height(20)
+                .width(300),
+            text(format!(
+                "{:.1} / {:.1} MB",
+                self.hash_read as f64 / MEGABYTE,
+                self.hash_total as f64 / MEGABYTE
+            )),
+            button("Cancel").on_press(Message::CancelHashClicked),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn digests_view(&self, matching: Option<Algorithm>) -> Element<'_, Message> {
+        let encoding = self.encoding.unwrap_or_default();
+        let rows = self.digests.iter().map(|(algorithm, digest)| {
+            let mut encoded = text(encoding.encode(digest)).font(Font::MONOSPACE);
+            if matching == Some(*algorithm) {
+                encoded = encoded.color(MATCH_COLOR);
+            }
+            row![
+                text(algorithm.to_string()).width(120),
+                encoded,
+                horizontal_space(),
+                button("Copy").on_press(Message::CopyClicked(*algorithm)),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into()
+        });
+        Column::with_children(rows).spacing(5).into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let configuration = row![
+            "Output: ",
+            pick_list(&Encoding::ALL[..], self.encoding, Message::EncodingSelected),
+            horizontal_space(),
+            button("Hash file...").on_press(Message::HashFileClicked),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let matching = self.matching_algorithm();
+        let comparison = match matching {
+            Some(Ok(algorithm)) => text(format!("Matches {}", algorithm)).color(MATCH_COLOR),
+            Some(Err(e)) => text(e).color(ERROR_COLOR),
+            None => text(""),
+        };
+        let expected = column![
+            row![
+                "Expected checksum: ",
+                text_input("Paste a checksum in hex or Base64", &self.expected)
+                    .on_input(Message::ExpectedChanged)
+                    .font(Font::MONOSPACE),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            comparison,
+        ]
+        .spacing(5);
+
+        let mut content = column![
+            configuration,
+            self.input_view(),
+            self.progress_view(),
+            expected,
+            self.digests_view(matching.and_then(Result::ok)),
+        ]
+        .spacing(10)
+        .padding(10);
+        if let Some(e) = &self.error {
+            content = content.push(text(e.as_str()).color(ERROR_COLOR));
+        }
+        container(scrollable(content))
+            .padding(10)
+            .height(Length::Fill)
+            .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::TextChanged(action) => {
+                let is_edit = action.is_edit();
+                self.input.perform(action);
+                match is_edit {
+                    true => self.hash_text(),
+                    false => Task::none(),
+                }
+            }
+            Message::EncodingSelected(encoding) => {
+                self.encoding = Some(encoding);
+                Task::none()
+            }
+            Message::HashFileClicked => Task::perform(pick_file(), Message::FilePicked),
+            Message::FilePicked(path) => match path {
+                Some(path) => self.hash_file(path),
+                None => Task::none(),
+            },
+            Message::FileDropped(path) => self.hash_file(path),
+            Message::HashProgress(HashEvent::Read(read)) => {
+                self.hash_read = read;
+                Task::none()
+            }
+            Message::HashProgress(HashEvent::Finished(result)) => {
+                self.hashing = None;
+                match result {
+                    Ok(digests) => self.digests = digests,
+                    Err(e) => self.error = Some(e),
+                }
+                Task::none()
+            }
+            Message::CancelHashClicked => {
+                if self.stop_hashing() {
+                    self.error = Some(String::from("Hashing cancelled"));
+                }
+                Task::none()
+            }
+            Message::HashTextClicked => {
+                self.file = None;
+                self.hash_text()
+            }
+            Message::ExpectedChanged(expected) => {
+                self.expected = expected;
+                Task::none()
+            }
+            Message::CopyClicked(algorithm) => {
+                match self.digests.iter().find(|(a, _)| *a == algorithm) {
+                    Some((_, digest)) => {
+                        clipboard::write(self.encoding.unwrap_or_default().encode(digest))
+                    }
+                    None => Task::none(),
+                }
+            }
+        }
+    }
+}
+
+async fn pick_file() -> Option<PathBuf> {
+    let file = AsyncFileDialog::new()
+        .set_directory("/")
+        .pick_file()
+        .await?;
+    Some(file.path().to_path_buf())
+}
+
+/// Hashes what `open` returns on a separate thread, reporting progress as it goes.
+fn hash_in_background<R: Read>(
+    name: String,
+    open: impl FnOnce() -> io::Result<R> + Send + 'static,
+    cancelled: Arc<AtomicBool>,
+) -> mpsc::UnboundedReceiver<HashEvent> {
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        let result = open()
+            .and_then(|reader| {
+                algorithm::hash_reader(reader, &cancelled, |read| {
+                    let _ = sender.unbounded_send(HashEvent::Read(read));
+                })
+            })
+            .map_err(|e| format!("Could not hash {}: {}", name, e));
+        let _ = sender.unbounded_send(HashEvent::Finished(result));
+    });
+    receiver
+}
@@ -0,0 +1,343 @@
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use base64::{
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD},
+    Engine,
+};
+use blake2::{Blake2b512, Blake2s256};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use xxhash_rust::{xxh3::Xxh3, xxh32::Xxh32, xxh64::Xxh64};
+
+// Files are read in chunks this large, and every algorithm hashes them on its own thread.
+const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Blake2b,
+    Blake2s,
+    Blake3,
+    Crc32,
+    Xxh32,
+    Xxh64,
+    Xxh3_64,
+    Xxh3_128,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 18] = [
+        Algorithm::Md5,
+        Algorithm::Sha1,
+        Algorithm::Sha224,
+        Algorithm::Sha256,
+        Algorithm::Sha384,
+        Algorithm::Sha512,
+        Algorithm::Sha3_224,
+        Algorithm::Sha3_256,
+        Algorithm::Sha3_384,
+        Algorithm::Sha3_512,
+        Algorithm::Blake2b,
+        Algorithm::Blake2s,
+        Algorithm::Blake3,
+        Algorithm::Crc32,
+        Algorithm::Xxh32,
+        Algorithm::Xxh64,
+        Algorithm::Xxh3_64,
+        Algorithm::Xxh3_128,
+    ];
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Algorithm::Md5 => "MD5",
+                Algorithm::Sha1 => "SHA-1",
+                Algorithm::Sha224 => "SHA-224",
+                Algorithm::Sha256 => "SHA-256",
+                Algorithm::Sha384 => "SHA-384",
+                Algorithm::Sha512 => "SHA-512",
+                Algorithm::Sha3_224 => "SHA3-224",
+                Algorithm::Sha3_256 => "SHA3-256",
+                Algorithm::Sha3_384 => "SHA3-384",
+                Algorithm::Sha3_512 => "SHA3-512",
+                Algorithm::Blake2b => "BLAKE2b-512",
+                Algorithm::Blake2s => "BLAKE2s-256",
+                Algorithm::Blake3 => "BLAKE3",
+                Algorithm::Crc32 => "CRC32",
+                Algorithm::Xxh32 => "xxHash32",
+                Algorithm::Xxh64 => "xxHash64",
+                Algorithm::Xxh3_64 => "XXH3-64",
+                Algorithm::Xxh3_128 => "XXH3-128",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    #[default]
+    Hex,
+    UppercaseHex,
+    Base64,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 3] = [Encoding::Hex, Encoding::UppercaseHex, Encoding::Base64];
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Hex => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            Encoding::UppercaseHex => bytes.iter().map(|b| format!("{:02X}", b)).collect(),
+            Encoding::Base64 => STANDARD.encode(bytes),
+        }
+    }
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Encoding::Hex => "Hex",
+                Encoding::UppercaseHex => "Hex (uppercase)",
+                Encoding::Base64 => "Base64",
+            }
+        )
+    }
+}
+
+/// The running state of one algorithm.
+enum Hasher {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha224(Sha224),
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Sha3_224(Sha3_224),
+    Sha3_256(Sha3_256),
+    Sha3_384(Sha3_384),
+    Sha3_512(Sha3_512),
+    Blake2b(Blake2b512),
+    Blake2s(Blake2s256),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+    Xxh32(Xxh32),
+    Xxh64(Xxh64),
+    Xxh3_64(Box<Xxh3>),
+    Xxh3_128(Box<Xxh3>),
+}
+
+impl Hasher {
+    fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Md5 => Hasher::Md5(Md5::new()),
+            Algorithm::Sha1 => Hasher::Sha1(Sha1::new()),
+            Algorithm::Sha224 => Hasher::Sha224(Sha224::new()),
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Sha384 => Hasher::Sha384(Sha384::new()),
+            Algorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            Algorithm::Sha3_224 => Hasher::Sha3_224(Sha3_224::new()),
+            Algorithm::Sha3_256 => Hasher::Sha3_256(Sha3_256::new()),
+            Algorithm::Sha3_384 => Hasher::Sha3_384(Sha3_384::new()),
+            Algorithm::Sha3_512 => Hasher::Sha3_512(Sha3_512::new()),
+            Algorithm::Blake2b => Hasher::Blake2b(Blake2b512::new()),
+            Algorithm::Blake2s => Hasher::Blake2s(Blake2s256::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            Algorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+            Algorithm::Xxh32 => Hasher::Xxh32(Xxh32::new(0)),
+            Algorithm::Xxh64 => Hasher::Xxh64(Xxh64::new(0)),
+            Algorithm::Xxh3_64 => Hasher::Xxh3_64(Box::new(Xxh3::new())),
+            Algorithm::Xxh3_128 => Hasher::Xxh3_128(Box::new(Xxh3::new())),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Md5(hasher) => hasher.update(bytes),
+            Hasher::Sha1(hasher) => hasher.update(bytes),
+            Hasher::Sha224(hasher) => hasher.update(bytes),
+            Hasher::Sha256(hasher) => hasher.update(bytes),
+            Hasher::Sha384(hasher) => hasher.update(bytes),
+            Hasher::Sha512(hasher) => hasher.update(bytes),
+            Hasher::Sha3_224(hasher) => hasher.update(bytes),
+            Hasher::Sha3_256(hasher) => hasher.update(bytes),
+            Hasher::Sha3_384(hasher) => hasher.update(bytes),
+            Hasher::Sha3_512(hasher) => hasher.update(bytes),
+            Hasher::Blake2b(hasher) => hasher.update(bytes),
+            Hasher::Blake2s(hasher) => hasher.update(bytes),
+            Hasher::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+            Hasher::Crc32(hasher) => hasher.update(bytes),
+            Hasher::Xxh32(hasher) => hasher.update(bytes),
+            Hasher::Xxh64(hasher) => hasher.update(bytes),
+            Hasher::Xxh3_64(hasher) | Hasher::Xxh3_128(hasher) => hasher.update(bytes),
+        }
+    }
+
+    /// The digest as bytes. Checksums are big-endian, the way their tools print them.
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Md5(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha1(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha224(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha384(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_224(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_384(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Blake2b(hasher) => hasher.finalize().to_vec(),
+            Hasher::Blake2s(hasher) => hasher.finalize().to_vec(),
+            Hasher::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            Hasher::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            Hasher::Xxh32(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            Hasher::Xxh64(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            Hasher::Xxh3_64(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            Hasher::Xxh3_128(hasher) => hasher.digest128().to_be_bytes().to_vec(),
+        }
+    }
+}
+
+pub type Digests = Vec<(Algorithm, Vec<u8>)>;
+
+/// Hashes `bytes` with every algorithm.
+pub fn hash(bytes: &[u8]) -> Digests {
+    Algorithm::ALL
+        .iter()
+        .map(|algorithm| {
+            let mut hasher = Hasher::new(*algorithm);
+            hasher.update(bytes);
+            (*algorithm, hasher.finalize())
+        })
+        .collect()
+}
+
+/// Hashes everything `reader` yields with every algorithm, reporting the number of bytes read
+/// after each chunk.
+pub fn hash_reader(
+    mut reader: impl Read,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(u64),
+) -> io::Result<Digests> {
+    thread::scope(|scope| {
+        // One worker per algorithm for the whole read. The channels hold a single chunk each, so
+        // reading never gets more than a couple of chunks ahead of the slowest algorithm.
+        let (senders, workers): (Vec<_>, Vec<_>) = Algorithm::ALL
+            .iter()
+            .map(|&algorithm| {
+                let (sender, receiver) = mpsc::sync_channel::<Arc<Vec<u8>>>(1);
+                let worker = scope.spawn(move || {
+                    let mut hasher = Hasher::new(algorithm);
+                    for chunk in receiver {
+                        hasher.update(&chunk);
+                    }
+                    (algorithm, hasher.finalize())
+                });
+                (sender, worker)
+            })
+            .unzip();
+
+        let mut read = 0;
+        loop {
+            let mut chunk = vec![0; CHUNK_SIZE];
+            let length = read_chunk(&mut reader, &mut chunk)?;
+            if length == 0 {
+                break;
+            }
+            chunk.truncate(length);
+            let chunk = Arc::new(chunk);
+            for sender in &senders {
+                // A worker only hangs up by panicking, which `join` reports below.
+                let _ = sender.send(chunk.clone());
+            }
+            read += length as u64;
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "Hashing cancelled",
+                ));
+            }
+            progress(read);
+        }
+        drop(senders);
+        Ok(workers
+            .into_iter()
+            .map(|worker| worker.join().expect("a hashing thread panicked"))
+            .collect())
+    })
+}
+
+// Fills as much of `buffer` as the reader allows, so that every chunk but the last is full.
+fn read_chunk(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Reads an expected checksum as hex or Base64. Copies of `sha256sum` output lines and
+/// `sha256:` style prefixes are accepted.
+pub fn parse_expected(expected: &str) -> Option<Vec<u8>> {
+    let token = expected.split_whitespace().next()?;
+    let token = match token.split_once(':') {
+        Some((prefix, digest))
+            if prefix.chars().all(|c| c.is_ascii_alphanumeric())
+                && !prefix.chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            digest
+        }
+        _ => token,
+    };
+    let is_hex = |digits: &str| {
+        digits.len().is_multiple_of(2) && digits.chars().all(|c| c.is_ascii_hexdigit())
+    };
+    // Base64 may start with "0x" too, so the prefix only counts when hex digits follow it.
+    let hex = match token.strip_prefix("0x") {
+        Some(digits) if !digits.is_empty() && is_hex(digits) => Some(digits),
+        _ => Some(token).filter(|token| is_hex(token)),
+    };
+    if let Some(hex) = hex {
+        return (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect();
+    }
+    let standard: String = token
+        .trim_end_matches('=')
+        .chars()
+        .map(|c| match c {
+            '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    STANDARD_NO_PAD.decode(standard).ok()
+}
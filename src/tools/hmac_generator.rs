@@ -0,0 +1,301 @@
+use iced::widget::{
+    button, column, container, horizontal_space, pick_list, row, scrollable, text, text_editor,
+    text_input,
+};
+use iced::{clipboard, Alignment, Color, Element, Font, Length, Task};
+
+use mac::{Algorithm, Encoding, KeyFormat};
+
+pub mod mac;
+
+pub const NAME: &str = "HMAC Generator";
+
+const VALID_COLOR: Color = Color::from_rgb(0.1, 0.55, 0.1);
+const ERROR_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Generate,
+    Verify,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 2] = [Mode::Generate, Mode::Verify];
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Mode::Generate => "Generate",
+                Mode::Verify => "Verify",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ModeSelected(Mode),
+    AlgorithmSelected(Algorithm),
+    KeyChanged(String),
+    KeyFormatSelected(KeyFormat),
+    PayloadChanged(text_editor::Action),
+    EncodingSelected(Encoding),
+    SignatureChanged(String),
+    CopyClicked,
+}
+
+struct Verification {
+    is_valid: bool,
+    /// The signature the message should have had, in the selected encoding. Only computed for a
+    /// mismatch.
+    expected: Option<String>,
+    timestamp: Option<String>,
+}
+
+pub struct HmacGenerator {
+    tool_name: String,
+    mode: Option<Mode>,
+    algorithm: Option<Algorithm>,
+    key: String,
+    key_format: Option<KeyFormat>,
+    payload: text_editor::Content,
+    encoding: Option<Encoding>,
+    signature: String,
+    output: Result<String, String>,
+    verification: Option<Result<Verification, String>>,
+}
+
+impl Default for HmacGenerator {
+    fn default() -> Self {
+        HmacGenerator::new()
+    }
+}
+
+impl HmacGenerator {
+    pub fn new() -> Self {
+        let mut generator = Self {
+            tool_name: NAME.to_string(),
+            mode: Some(Mode::default()),
+            algorithm: Some(Algorithm::default()),
+            key: String::new(),
+            key_format: Some(KeyFormat::default()),
+            payload: text_editor::Content::new(),
+            encoding: Some(Encoding::default()),
+            signature: String::new(),
+            output: Ok(String::new()),
+            verification: None,
+        };
+        generator.refresh();
+        generator
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    // The editor always reports a trailing newline, so the lines are joined by hand to sign
+    // exactly what was typed.
+    fn payload_text(&self) -> String {
+        self.payload
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn key(&self) -> Result<Vec<u8>, String> {
+        self.key_format.unwrap_or_default().decode(&self.key)
+    }
+
+    // Signing runs here rather than in the views, so it happens once per change instead of once
+    // per frame.
+    fn refresh(&mut self) {
+        let algorithm = self.algorithm.unwrap_or_default();
+        let encoding = self.encoding.unwrap_or_default();
+        let key = self.key();
+        let payload = self.payload_text();
+        self.output = key
+            .clone()
+            .map(|key| encoding.encode(&algorithm.sign(&key, payload.as_bytes())));
+        self.verification = match self.signature.trim().is_empty() {
+            true => None,
+            false => Some(key.and_then(|key| {
+                let signature = mac::parse_signature(&self.signature)?;
+                let payload = signature.signed_payload(&payload);
+                let is_valid = algorithm.verify(&key, &payload, &signature.bytes);
+                Ok(Verification {
+                    is_valid,
+                    expected: match is_valid {
+                        true => None,
+                        false => Some(encoding.encode(&algorithm.sign(&key, &payload))),
+                    },
+                    timestamp: signature.timestamp,
+                })
+            })),
+        };
+    }
+
+    fn generate_view(&self) -> Element<'_, Message> {
+        let output: Element<_> = match &self.output {
+            Ok(output) => row![
+                text(output).font(Font::MONOSPACE),
+                horizontal_space(),
+                button("Copy").on_press(Message::CopyClicked),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into(),
+            Err(e) => text(e).color(ERROR_COLOR).into(),
+        };
+        column![
+            row![
+                "Output: ",
+                pick_list(&Encoding::ALL[..], self.encoding, Message::EncodingSelected),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            output,
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn verify_view(&self) -> Element<'_, Message> {
+        let input = row![
+            "Signature: ",
+            text_input(
+                "Hex, Base64, or a header like sha256=... or t=...,v1=...",
+                &self.signature
+            )
+            .on_input(Message::SignatureChanged)
+            .font(Font::MONOSPACE),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+        let Some(verification) = &self.verification else {
+            return input.into();
+        };
+
+        let mut details = column![input].spacing(10);
+        match verification {
+            Ok(verification) => {
+                details = details.push(match verification.is_valid {
+                    true => text("Valid signature").color(VALID_COLOR),
+                    false => text("The signature does not match").color(ERROR_COLOR),
+                });
+                if let Some(timestamp) = &verification.timestamp {
+                    details = details.push(text(format!(
+                        "Verified against the timestamp {} followed by a dot and the message",
+                        timestamp
+                    )));
+                }
+                if let Some(expected) = &verification.expected {
+                    details = details.push(
+                        row![
+                            "Expected: ",
+                            text(expected).font(Font::MONOSPACE),
+                            pick_list(&Encoding::ALL[..], self.encoding, Message::EncodingSelected),
+                        ]
+                        .spacing(10)
+                        .align_y(Alignment::Center),
+                    );
+                }
+            }
+            Err(e) => details = details.push(text(e).color(ERROR_COLOR)),
+        }
+        details.into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let configuration = row![
+            pick_list(&Mode::ALL[..], self.mode, Message::ModeSelected),
+            pick_list(
+                &Algorithm::ALL[..],
+                self.algorithm,
+                Message::AlgorithmSelected
+            ),
+        ]
+        .spacing(10);
+
+        let key = row![
+            "Key: ",
+            text_input("Secret key", &self.key)
+                .on_input(Message::KeyChanged)
+                .font(Font::MONOSPACE),
+            pick_list(
+                &KeyFormat::ALL[..],
+                self.key_format,
+                Message::KeyFormatSelected
+            ),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let message = column![
+            "Message",
+            container(scrollable(
+                text_editor(&self.payload).on_action(Message::PayloadChanged)
+            ))
+            .height(200),
+        ]
+        .spacing(5);
+
+        let result = match self.mode.unwrap_or_default() {
+            Mode::Generate => self.generate_view(),
+            Mode::Verify => self.verify_view(),
+        };
+
+        container(scrollable(
+            column![configuration, key, message, result]
+                .spacing(10)
+                .padding(10),
+        ))
+        .padding(10)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::ModeSelected(mode) => self.mode = Some(mode),
+            Message::AlgorithmSelected(algorithm) => {
+                self.algorithm = Some(algorithm);
+                self.refresh();
+            }
+            Message::KeyChanged(key) => {
+                self.key = key;
+                self.refresh();
+            }
+            Message::KeyFormatSelected(key_format) => {
+                self.key_format = Some(key_format);
+                self.refresh();
+            }
+            Message::PayloadChanged(action) => {
+                let is_edit = action.is_edit();
+                self.payload.perform(action);
+                if is_edit {
+                    self.refresh();
+                }
+            }
+            Message::EncodingSelected(encoding) => {
+                self.encoding = Some(encoding);
+                self.refresh();
+            }
+            Message::SignatureChanged(signature) => {
+                self.signature = signature;
+                self.refresh();
+            }
+            Message::CopyClicked => {
+                if let Ok(output) = &self.output {
+                    return clipboard::write(output.clone());
+                }
+            }
+        }
+        Task::none()
+    }
+}
@@ -0,0 +1,212 @@
+use base64::{
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha384, Sha512};
+
+use crate::tools::hash_generator::algorithm;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    HmacSha1,
+    #[default]
+    HmacSha256,
+    HmacSha384,
+    HmacSha512,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::HmacSha1,
+        Algorithm::HmacSha256,
+        Algorithm::HmacSha384,
+        Algorithm::HmacSha512,
+    ];
+
+    pub fn sign(&self, key: &[u8], message: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::HmacSha1 => sign::<Hmac<Sha1>>(key, message),
+            Algorithm::HmacSha256 => sign::<Hmac<Sha256>>(key, message),
+            Algorithm::HmacSha384 => sign::<Hmac<Sha384>>(key, message),
+            Algorithm::HmacSha512 => sign::<Hmac<Sha512>>(key, message),
+        }
+    }
+
+    /// Checks `signature` in constant time, so that how long the check takes says nothing
+    /// about how much of the signature was right.
+    pub fn verify(&self, key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        match self {
+            Algorithm::HmacSha1 => verify::<Hmac<Sha1>>(key, message, signature),
+            Algorithm::HmacSha256 => verify::<Hmac<Sha256>>(key, message, signature),
+            Algorithm::HmacSha384 => verify::<Hmac<Sha384>>(key, message, signature),
+            Algorithm::HmacSha512 => verify::<Hmac<Sha512>>(key, message, signature),
+        }
+    }
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Algorithm::HmacSha1 => "HMAC-SHA1",
+                Algorithm::HmacSha256 => "HMAC-SHA256",
+                Algorithm::HmacSha384 => "HMAC-SHA384",
+                Algorithm::HmacSha512 => "HMAC-SHA512",
+            }
+        )
+    }
+}
+
+fn sign<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn verify<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.verify_slice(signature).is_ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyFormat {
+    #[default]
+    Text,
+    Hex,
+    Base64,
+}
+
+impl KeyFormat {
+    pub const ALL: [KeyFormat; 3] = [KeyFormat::Text, KeyFormat::Hex, KeyFormat::Base64];
+
+    pub fn decode(&self, key: &str) -> Result<Vec<u8>, String> {
+        match self {
+            KeyFormat::Text => Ok(key.as_bytes().to_vec()),
+            KeyFormat::Hex => {
+                let digits: Vec<char> = key.chars().filter(|c| !c.is_whitespace()).collect();
+                if !digits.len().is_multiple_of(2) {
+                    return Err(String::from("Invalid hex key: odd number of digits"));
+                }
+                digits
+                    .chunks(2)
+                    .map(|pair| {
+                        let pair: String = pair.iter().collect();
+                        u8::from_str_radix(&pair, 16)
+                            .map_err(|_| format!("Invalid hex key: '{}' is not a hex byte", pair))
+                    })
+                    .collect()
+            }
+            KeyFormat::Base64 => {
+                let key: String = key.chars().filter(|c| !c.is_whitespace()).collect();
+                let key = key.trim_end_matches('=');
+                STANDARD_NO_PAD
+                    .decode(key)
+                    .or_else(|_| URL_SAFE_NO_PAD.decode(key))
+                    .map_err(|e| format!("Invalid Base64 key: {}", e))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for KeyFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                KeyFormat::Text => "Text key",
+                KeyFormat::Hex => "Hex key",
+                KeyFormat::Base64 => "Base64 key",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    #[default]
+    Hex,
+    Base64,
+    Base64Url,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 3] = [Encoding::Hex, Encoding::Base64, Encoding::Base64Url];
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Hex => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            Encoding::Base64 => STANDARD.encode(bytes),
+            Encoding::Base64Url => URL_SAFE_NO_PAD.encode(bytes),
+        }
+    }
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Encoding::Hex => "Hex",
+                Encoding::Base64 => "Base64",
+                Encoding::Base64Url => "Base64url",
+            }
+        )
+    }
+}
+
+/// A signature to verify, and the timestamp its payload was prefixed with, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub bytes: Vec<u8>,
+    pub timestamp: Option<String>,
+}
+
+impl Signature {
+    /// The bytes that were signed: Stripe signs the timestamp, a dot and the payload.
+    pub fn signed_payload(&self, message: &str) -> Vec<u8> {
+        match &self.timestamp {
+            Some(timestamp) => format!("{}.{}", timestamp, message).into_bytes(),
+            None => message.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Reads a signature in hex, Base64 or Base64url. Whole webhook headers are accepted too,
+/// like GitHub's `sha256=<hex>` or Stripe's `t=<timestamp>,v1=<hex>`.
+pub fn parse_signature(signature: &str) -> Result<Signature, String> {
+    let signature = signature.trim();
+    let fields: Vec<(&str, &str)> = signature
+        .split(',')
+        .filter_map(|field| field.trim().split_once('='))
+        .filter(|(name, value)| {
+            !value.is_empty()
+                && !value.starts_with('=')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                && !name.chars().all(|c| c.is_ascii_hexdigit())
+        })
+        .collect();
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| *value)
+    };
+
+    let (value, timestamp) = match (field("t"), field("v1")) {
+        (Some(timestamp), Some(value)) => (value, Some(timestamp.to_string())),
+        _ => match fields.as_slice() {
+            [(_, value)] => (*value, None),
+            _ => (signature, None),
+        },
+    };
+    algorithm::parse_expected(value)
+        .map(|bytes| Signature { bytes, timestamp })
+        .ok_or_else(|| String::from("The signature is not valid hex, Base64 or Base64url"))
+}
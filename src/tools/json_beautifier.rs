@@ -1,22 +1,89 @@
-use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
+use async_io::Timer;
 use iced::{
+    futures::channel::oneshot,
     highlighter,
+    task::Handle,
     widget::{
-        button, column, container, horizontal_space, row, scrollable, slider, text,
-        text_editor::{self, Action},
-        Space,
+        button, column, container, horizontal_space, pick_list, row, scrollable, slider, text,
+        text_editor::{self, Action, Motion},
+        text_input, Space, Text,
     },
     Element,
     Length::Fill,
+    Task,
 };
+use rfd::AsyncFileDialog;
+use serde_json::Value;
+
+use query::Syntax;
+use schema::{Draft, Violation};
+use types::Language;
+
+pub mod format;
+mod query;
+pub mod schema;
+mod types;
 
 pub const NAME: &str = "JSON Beautifier";
 
+const FORMAT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+type AnalysisResult = Arc<Result<Analysis, String>>;
+
+/// What the background job starts from: text to parse, or a document parsed earlier when only
+/// the query, schema or type settings changed.
+enum Input {
+    Text(String, u16),
+    Parsed(Arc<Value>, Arc<String>),
+}
+
+/// The settings of the side panels, captured when a job starts.
+struct Request {
+    query: String,
+    schema: String,
+    draft: Draft,
+    types: Option<(Language, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    document: Arc<Value>,
+    source: Arc<String>,
+    /// Only set when the job parsed the input text.
+    formatted: Option<String>,
+    query: Result<String, String>,
+    validation: Result<Vec<Violation>, String>,
+    types: Option<String>,
+}
+
 pub struct JsonBeautifier {
     input_content: text_editor::Content,
     output_content: text_editor::Content,
     error_text: Option<String>,
+    document: Option<Arc<Value>>,
+    /// The text `document` was parsed from, which validation maps errors back onto.
+    source: Arc<String>,
+    job: Option<(Handle, Arc<AtomicBool>)>,
+    job_generation: u64,
+    /// Set from an edit until the job that parses it finishes.
+    format_pending: bool,
+    query: String,
+    query_output: text_editor::Content,
+    query_error: Option<String>,
+    mode: Option<Mode>,
+    schema_content: text_editor::Content,
+    schema_draft: Option<Draft>,
+    schema_error: Option<String>,
+    violations: Vec<Violation>,
+    types_language: Option<Language>,
+    types_root_name: String,
+    types_output: text_editor::Content,
     theme: highlighter::Theme,
     indentation: u16,
     tool_name: String,
@@ -27,6 +94,46 @@ pub enum Message {
     InputActionPerformed(text_editor::Action),
     OutputActionPerformed(text_editor::Action),
     IndentationChanged(u16),
+    FormatDue(u64),
+    Analyzed(u64, AnalysisResult),
+    QueryChanged(String),
+    QueryOutputActionPerformed(text_editor::Action),
+    ModeSelected(Mode),
+    SchemaActionPerformed(text_editor::Action),
+    DraftSelected(Draft),
+    LoadSchemaClicked,
+    SchemaLoaded(Option<String>),
+    InferSchemaClicked,
+    ViolationClicked(usize),
+    LanguageSelected(Language),
+    RootNameChanged(String),
+    TypesOutputActionPerformed(text_editor::Action),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Query,
+    Schema,
+    Types,
+}
+
+impl Mode {
+    const ALL: [Mode; 3] = [Mode::Query, Mode::Schema, Mode::Types];
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Mode::Query => "Query",
+                Mode::Schema => "Schema",
+                Mode::Types => "Generate types",
+            }
+        )
+    }
 }
 
 impl JsonBeautifier {
@@ -35,6 +142,22 @@ impl JsonBeautifier {
             input_content: text_editor::Content::with_text("{}"),
             output_content: text_editor::Content::with_text("{}"),
             error_text: None,
+            document: Some(Arc::new(Value::Object(serde_json::Map::new()))),
+            source: Arc::new(String::from("{}")),
+            job: None,
+            job_generation: 0,
+            format_pending: false,
+            query: String::new(),
+            query_output: text_editor::Content::new(),
+            query_error: None,
+            mode: Some(Mode::Query),
+            schema_content: text_editor::Content::new(),
+            schema_draft: Some(Draft::default()),
+            schema_error: None,
+            violations: vec![],
+            types_language: Some(Language::default()),
+            types_root_name: String::from("Root"),
+            types_output: text_editor::Content::new(),
             theme: highlighter::Theme::InspiredGitHub,
             indentation: 4,
             tool_name: NAME.to_string(),
@@ -45,11 +168,13 @@ impl JsonBeautifier {
         self.tool_name.clone()
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
         let controls = row![
             slider(0..=8, self.indentation, Message::IndentationChanged),
-            horizontal_space()
+            horizontal_space(),
+            pick_list(&Mode::ALL[..], self.mode, Message::ModeSelected)
         ]
+        .spacing(10)
         .padding(20);
 
         let editor = container(scrollable(
@@ -60,6 +185,11 @@ impl JsonBeautifier {
         .height(Fill);
 
         let status = row![
+            text(match (self.format_pending, &self.job) {
+                (true, _) => "Formatting...",
+                (false, Some(_)) => "Updating...",
+                (false, None) => "",
+            }),
             horizontal_space(),
             text({
                 let (line, column) = self.input_content.cursor_position();
@@ -76,6 +206,38 @@ impl JsonBeautifier {
         ))
         .height(Fill);
 
+        let query_input = text_input(
+            "JSONPath ($.items[*].id) or jq (.items[] | .id)",
+            &self.query,
+        )
+        .on_input(Message::QueryChanged);
+
+        let query_output = container(scrollable(
+            iced::widget::text_editor(&self.query_output)
+                .on_action(Message::QueryOutputActionPerformed)
+                .highlight("js", self.theme),
+        ))
+        .height(Fill);
+
+        let mut query_panel = column![
+            row![text("Query"), self.stale_note(), horizontal_space()]
+                .spacing(10)
+                .height(50),
+            query_input,
+            Space::with_height(10),
+            query_output
+        ]
+        .padding(10);
+        if let Some(v) = &self.query_error {
+            query_panel = query_panel.push(row![text(v.to_owned()), horizontal_space()]);
+        }
+
+        let side_panel = match self.mode {
+            Some(Mode::Schema) => self.schema_panel(),
+            Some(Mode::Types) => self.types_panel(),
+            _ => query_panel.into(),
+        };
+
         let json_rows = row![
             column![
                 row![text("Input"), horizontal_space()].height(50),
@@ -95,43 +257,119 @@ impl JsonBeautifier {
                 output
             ]
             .padding(10),
+            side_panel,
         ]
         .padding(20);
 
         let mut all_content = column![controls, json_rows];
-        match &self.error_text {
-            Some(v) => {
-                all_content =
-                    all_content.push(row![text(v.to_owned()), horizontal_space()].padding(20));
-            }
-            None => (),
+        if let Some(v) = &self.error_text {
+            all_content =
+                all_content.push(row![text(v.to_owned()), horizontal_space()].padding(20));
         }
 
         all_content.into()
     }
 
-    pub fn update(&mut self, message: Message) {
+    // Without a document the side panels keep showing results for the last valid input.
+    fn stale_note(&self) -> Text<'_> {
+        text(match self.document {
+            Some(_) => "",
+            None => "Out of date: the input is not valid JSON",
+        })
+    }
+
+    fn schema_panel(&self) -> Element<'_, Message> {
+        let schema_editor = container(scrollable(
+            iced::widget::text_editor(&self.schema_content)
+                .on_action(Message::SchemaActionPerformed)
+                .highlight("js", self.theme),
+        ))
+        .height(Fill);
+
+        let report: Element<Message> = match &self.schema_error {
+            Some(v) => text(v.to_owned()).into(),
+            None if self.schema_content.text().trim().is_empty() => {
+                text("Paste, load or infer a schema").into()
+            }
+            None if self.violations.is_empty() => text("Input is valid").into(),
+            None => scrollable(self.violations.iter().fold(
+                column![text(format!("{} violation(s)", self.violations.len()))],
+                |violations, violation| {
+                    let location = match violation.line {
+                        Some(line) => format!("Line {} ", line + 1),
+                        None => String::new(),
+                    };
+                    let path = match violation.instance_path.as_str() {
+                        "" => "/",
+                        path => path,
+                    };
+                    violations.push(
+                        button(text(format!("{}{}: {}", location, path, violation.message)))
+                            .style(button::text)
+                            .padding(2)
+                            .on_press_maybe(violation.line.map(Message::ViolationClicked)),
+                    )
+                },
+            ))
+            .height(150)
+            .into(),
+        };
+
+        column![
+            row![
+                text("Schema"),
+                self.stale_note(),
+                horizontal_space(),
+                pick_list(&Draft::ALL[..], self.schema_draft, Message::DraftSelected),
+                button("Load schema").on_press(Message::LoadSchemaClicked),
+                button("Infer schema")
+                    .on_press_maybe(self.document.as_ref().map(|_| Message::InferSchemaClicked)),
+            ]
+            .spacing(10)
+            .height(50),
+            schema_editor,
+            Space::with_height(10),
+            report,
+        ]
+        .padding(10)
+        .into()
+    }
+
+    fn types_panel(&self) -> Element<'_, Message> {
+        let language = self.types_language.unwrap_or_default();
+        column![
+            row![
+                text("Types"),
+                self.stale_note(),
+                horizontal_space(),
+                pick_list(
+                    &Language::ALL[..],
+                    self.types_language,
+                    Message::LanguageSelected
+                ),
+            ]
+            .spacing(10)
+            .height(50),
+            text_input("Root type name", &self.types_root_name).on_input(Message::RootNameChanged),
+            Space::with_height(10),
+            container(scrollable(
+                iced::widget::text_editor(&self.types_output)
+                    .on_action(Message::TypesOutputActionPerformed)
+                    .highlight(language.extension(), self.theme),
+            ))
+            .height(Fill),
+        ]
+        .padding(10)
+        .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::InputActionPerformed(action) => {
-                let old_text = self.input_content.text().to_owned();
-
+                let is_edit = action.is_edit();
                 self.input_content.perform(action);
-
-                let new_text = self.input_content.text().to_owned();
-                if old_text != new_text {
-                    let text_content = new_text.as_str();
-                    match serde_json::from_str::<HashMap<String, serde_json::Value>>(text_content) {
-                        Ok(serialized_json) => match serde_json::to_string_pretty(&serialized_json)
-                        {
-                            Ok(formatted_json) => {
-                                self.error_text = None;
-                                self.output_content =
-                                    text_editor::Content::with_text(&formatted_json)
-                            }
-                            Err(e) => self.error_text = Some(e.to_string()),
-                        },
-                        Err(e) => self.error_text = Some(e.to_string()),
-                    }
+                if is_edit {
+                    return self.schedule_format();
                 }
             }
             Message::OutputActionPerformed(action) => match action {
@@ -140,7 +378,274 @@ impl JsonBeautifier {
                 }
                 _ => (),
             },
-            Message::IndentationChanged(indentation) => self.indentation = indentation,
+            Message::IndentationChanged(indentation) => {
+                self.indentation = indentation;
+                return self.schedule_format();
+            }
+            Message::FormatDue(generation) => {
+                if generation == self.job_generation {
+                    let input = Input::Text(self.input_content.text(), self.indentation);
+                    return self.start_job(input);
+                }
+            }
+            Message::Analyzed(generation, result) => {
+                if generation != self.job_generation {
+                    return Task::none();
+                }
+                self.job = None;
+                self.format_pending = false;
+                match Arc::unwrap_or_clone(result) {
+                    Ok(analysis) => self.show_analysis(analysis),
+                    Err(e) => {
+                        self.document = None;
+                        self.error_text = Some(e);
+                    }
+                }
+            }
+            Message::QueryChanged(query) => {
+                self.query = query;
+                return self.analyze_again();
+            }
+            Message::QueryOutputActionPerformed(action) => match action {
+                Action::SelectAll | Action::SelectLine | Action::SelectWord => {
+                    self.query_output.perform(action);
+                }
+                _ => (),
+            },
+            Message::ModeSelected(mode) => {
+                self.mode = Some(mode);
+                if mode == Mode::Types {
+                    return self.analyze_again();
+                }
+            }
+            Message::SchemaActionPerformed(action) => {
+                let is_edit = action.is_edit();
+                self.schema_content.perform(action);
+                if is_edit {
+                    return self.analyze_again();
+                }
+            }
+            Message::DraftSelected(draft) => {
+                self.schema_draft = Some(draft);
+                return self.analyze_again();
+            }
+            Message::LoadSchemaClicked => {
+                return Task::perform(load_schema(), Message::SchemaLoaded);
+            }
+            Message::SchemaLoaded(schema) => {
+                if let Some(schema) = schema {
+                    self.schema_content = text_editor::Content::with_text(&schema);
+                    return self.analyze_again();
+                }
+            }
+            Message::InferSchemaClicked => {
+                if let Some(document) = &self.document {
+                    let inferred = schema::infer(document);
+                    if let Ok(inferred) = serde_json::to_string_pretty(&inferred) {
+                        self.schema_content = text_editor::Content::with_text(&inferred);
+                        self.schema_draft = Some(Draft::Draft202012);
+                        return self.analyze_again();
+                    }
+                }
+            }
+            Message::ViolationClicked(line) => {
+                self.input_content
+                    .perform(Action::Move(Motion::DocumentStart));
+                for _ in 0..line {
+                    self.input_content.perform(Action::Move(Motion::Down));
+                }
+                self.input_content.perform(Action::SelectLine);
+            }
+            Message::LanguageSelected(language) => {
+                self.types_language = Some(language);
+                return self.analyze_again();
+            }
+            Message::RootNameChanged(name) => {
+                self.types_root_name = name;
+                return self.analyze_again();
+            }
+            Message::TypesOutputActionPerformed(action) => match action {
+                Action::SelectAll | Action::SelectLine | Action::SelectWord => {
+                    self.types_output.perform(action);
+                }
+                _ => (),
+            },
+        }
+        Task::none()
+    }
+
+    fn cancel_job(&mut self) {
+        if let Some((handle, cancelled)) = self.job.take() {
+            cancelled.store(true, Ordering::Relaxed);
+            handle.abort();
         }
+        self.job_generation += 1;
+    }
+
+    /// Restarts the debounce timer. The input is only read once it fires, so typing quickly
+    /// doesn't copy the document on every keystroke.
+    fn schedule_format(&mut self) -> Task<Message> {
+        self.cancel_job();
+        self.format_pending = true;
+        let generation = self.job_generation;
+        let (task, handle) = Task::perform(Timer::after(FORMAT_DEBOUNCE), move |_| {
+            Message::FormatDue(generation)
+        })
+        .abortable();
+        self.job = Some((handle, Arc::new(AtomicBool::new(false))));
+        task
+    }
+
+    /// Refreshes the side panels after their settings changed, reusing the parsed document.
+    fn analyze_again(&mut self) -> Task<Message> {
+        if self.format_pending {
+            // The pending job reads the settings when it starts, so it has to start over.
+            return self.schedule_format();
+        }
+        match &self.document {
+            Some(document) => {
+                let input = Input::Parsed(document.clone(), self.source.clone());
+                self.start_job(input)
+            }
+            None => Task::none(),
+        }
+    }
+
+    fn start_job(&mut self, input: Input) -> Task<Message> {
+        self.cancel_job();
+        let generation = self.job_generation;
+        let request = Request {
+            query: self.query.clone(),
+            schema: self.schema_content.text(),
+            draft: self.schema_draft.unwrap_or_default(),
+            types: (self.mode == Some(Mode::Types)).then(|| {
+                (
+                    self.types_language.unwrap_or_default(),
+                    self.types_root_name.clone(),
+                )
+            }),
+        };
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (task, handle) = Task::perform(
+            analyze_in_background(input, request, cancelled.clone()),
+            move |result| Message::Analyzed(generation, Arc::new(result)),
+        )
+        .abortable();
+        self.job = Some((handle, cancelled));
+        task
+    }
+
+    fn show_analysis(&mut self, analysis: Analysis) {
+        if let Some(formatted) = analysis.formatted {
+            self.error_text = None;
+            self.output_content = text_editor::Content::with_text(&formatted);
+        }
+        self.document = Some(analysis.document);
+        self.source = analysis.source;
+        match analysis.query {
+            Ok(formatted) => {
+                self.query_error = None;
+                self.query_output = text_editor::Content::with_text(&formatted);
+            }
+            Err(e) => self.query_error = Some(e),
+        }
+        match analysis.validation {
+            Ok(violations) => {
+                self.schema_error = None;
+                self.violations = violations;
+            }
+            Err(e) => self.schema_error = Some(e),
+        }
+        if let Some(types) = analysis.types {
+            self.types_output = text_editor::Content::with_text(&types);
+        }
+    }
+}
+
+async fn load_schema() -> Option<String> {
+    let file = AsyncFileDialog::new()
+        .add_filter("JSON Schema", &["json"])
+        .pick_file()
+        .await?;
+    fs::read_to_string(file.path()).ok()
+}
+
+/// Parses and formats the input if needed, then runs the query, the schema validation and the
+/// type generation on a separate thread.
+async fn analyze_in_background(
+    input: Input,
+    request: Request,
+    cancelled: Arc<AtomicBool>,
+) -> Result<Analysis, String> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let result = analyze(input, &request, &cancelled);
+        let _ = sender.send(result);
+    });
+    receiver
+        .await
+        .unwrap_or_else(|_| Err(String::from("Formatting cancelled")))
+}
+
+fn analyze(input: Input, request: &Request, cancelled: &AtomicBool) -> Result<Analysis, String> {
+    let check_cancelled = || match cancelled.load(Ordering::Relaxed) {
+        true => Err(String::from("Formatting cancelled")),
+        false => Ok(()),
+    };
+    let (document, source, formatted) = match input {
+        Input::Text(text, indentation) => {
+            let document = format::parse(&text)?;
+            check_cancelled()?;
+            let formatted = format::pretty(&document, indentation)?;
+            (Arc::new(document), Arc::new(text), Some(formatted))
+        }
+        Input::Parsed(document, source) => (document, source, None),
+    };
+    check_cancelled()?;
+    let query = run_query(&request.query, &document);
+    check_cancelled()?;
+    let validation = validate(&request.schema, request.draft, &document, &source);
+    check_cancelled()?;
+    let types = request
+        .types
+        .as_ref()
+        .map(|(language, root_name)| types::generate(&document, *language, root_name));
+    Ok(Analysis {
+        document,
+        source,
+        formatted,
+        query,
+        validation,
+        types,
+    })
+}
+
+fn run_query(query: &str, document: &Value) -> Result<String, String> {
+    if query.trim().is_empty() {
+        return Ok(String::new());
+    }
+    let results = query::evaluate(query, document).map_err(|e| e.to_string())?;
+    match Syntax::detect(query) {
+        Syntax::JsonPath => serde_json::to_string_pretty(&Value::Array(results)),
+        Syntax::Jq => results
+            .iter()
+            .map(serde_json::to_string_pretty)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|results| results.join("\n")),
+    }
+    .map_err(|e| e.to_string())
+}
+
+fn validate(
+    schema: &str,
+    draft: Draft,
+    document: &Value,
+    source: &str,
+) -> Result<Vec<Violation>, String> {
+    if schema.trim().is_empty() {
+        return Ok(vec![]);
     }
+    let schema =
+        serde_json::from_str::<Value>(schema).map_err(|e| format!("Invalid schema: {}", e))?;
+    schema::validate(&schema, draft, document, source)
 }
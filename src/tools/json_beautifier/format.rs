@@ -0,0 +1,19 @@
+use serde::Serialize;
+use serde_json::{ser::PrettyFormatter, Serializer, Value};
+
+const SPACES: &[u8] = b"        ";
+
+pub fn parse(input: &str) -> Result<Value, String> {
+    serde_json::from_str::<Value>(input).map_err(|e| e.to_string())
+}
+
+pub fn pretty(document: &Value, indentation: u16) -> Result<String, String> {
+    let indent = &SPACES[..usize::from(indentation).min(SPACES.len())];
+    let mut output = Vec::new();
+    let mut serializer =
+        Serializer::with_formatter(&mut output, PrettyFormatter::with_indent(indent));
+    document
+        .serialize(&mut serializer)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(output).map_err(|e| e.to_string())
+}
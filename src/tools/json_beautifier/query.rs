@@ -0,0 +1,1735 @@
+use std::cmp::Ordering;
+
+use serde_json::{Map, Number, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    message: String,
+    position: Option<usize>,
+}
+
+impl QueryError {
+    fn at(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position: Some(position),
+        }
+    }
+
+    fn runtime(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: None,
+        }
+    }
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{} at column {}", self.message, position + 1),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    JsonPath,
+    Jq,
+}
+
+impl Syntax {
+    pub fn detect(query: &str) -> Self {
+        if query.trim_start().starts_with('$') {
+            Syntax::JsonPath
+        } else {
+            Syntax::Jq
+        }
+    }
+}
+
+pub fn evaluate(query: &str, document: &Value) -> Result<Vec<Value>, QueryError> {
+    let tokens = tokenize(query)?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        end: query.chars().count(),
+    };
+    match Syntax::detect(query) {
+        Syntax::JsonPath => {
+            let path = parser.parse_json_path()?;
+            parser.expect_end()?;
+            Ok(select_path(&path, document, document)
+                .into_iter()
+                .cloned()
+                .collect())
+        }
+        Syntax::Jq => {
+            let filter = parser.parse_pipe()?;
+            parser.expect_end()?;
+            run(&filter, document)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    DotDot,
+    Dollar,
+    At,
+    Star,
+    Slash,
+    Plus,
+    Minus,
+    Question,
+    Bang,
+    Pipe,
+    Comma,
+    Colon,
+    Semicolon,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    And,
+    Or,
+    Cmp(CmpOp),
+    Ident(String),
+    Str(String),
+    Num(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '.' if next == Some('.') => {
+                i += 2;
+                Token::DotDot
+            }
+            '=' if next == Some('=') => {
+                i += 2;
+                Token::Cmp(CmpOp::Eq)
+            }
+            '!' if next == Some('=') => {
+                i += 2;
+                Token::Cmp(CmpOp::Ne)
+            }
+            '<' if next == Some('=') => {
+                i += 2;
+                Token::Cmp(CmpOp::Le)
+            }
+            '>' if next == Some('=') => {
+                i += 2;
+                Token::Cmp(CmpOp::Ge)
+            }
+            '&' if next == Some('&') => {
+                i += 2;
+                Token::And
+            }
+            '|' if next == Some('|') => {
+                i += 2;
+                Token::Or
+            }
+            '"' | '\'' => {
+                let (value, end) = read_string(&chars, i)?;
+                i = end;
+                Token::Str(value)
+            }
+            c if c.is_ascii_digit() => {
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(char::is_ascii_digit)
+                {
+                    i += 1;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+                if matches!(chars.get(i), Some('e' | 'E')) {
+                    let mut j = i + 1;
+                    if matches!(chars.get(j), Some('+' | '-')) {
+                        j += 1;
+                    }
+                    if chars.get(j).is_some_and(char::is_ascii_digit) {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let literal: String = chars[start..i].iter().collect();
+                match literal.parse::<f64>() {
+                    Ok(v) => Token::Num(v),
+                    Err(_) => return Err(QueryError::at("Invalid number", start)),
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                Token::Ident(chars[start..i].iter().collect())
+            }
+            _ => {
+                i += 1;
+                match c {
+                    '.' => Token::Dot,
+                    '$' => Token::Dollar,
+                    '@' => Token::At,
+                    '*' => Token::Star,
+                    '/' => Token::Slash,
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '?' => Token::Question,
+                    '!' => Token::Bang,
+                    '|' => Token::Pipe,
+                    ',' => Token::Comma,
+                    ':' => Token::Colon,
+                    ';' => Token::Semicolon,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '{' => Token::LBrace,
+                    '}' => Token::RBrace,
+                    '<' => Token::Cmp(CmpOp::Lt),
+                    '>' => Token::Cmp(CmpOp::Gt),
+                    _ => return Err(QueryError::at(format!("Unexpected '{}'", c), start)),
+                }
+            }
+        };
+        tokens.push((token, start));
+    }
+
+    Ok(tokens)
+}
+
+fn read_string(chars: &[char], start: usize) -> Result<(String, usize), QueryError> {
+    let quote = chars[start];
+    let mut value = String::new();
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        match chars[i] {
+            c if c == quote => return Ok((value, i + 1)),
+            '\\' => {
+                let escaped = match chars.get(i + 1) {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('r') => '\r',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('u') => {
+                        let hex: String = chars.iter().skip(i + 2).take(4).collect();
+                        match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                            Some(c) if hex.len() == 4 => {
+                                value.push(c);
+                                i += 6;
+                                continue;
+                            }
+                            _ => return Err(QueryError::at("Invalid unicode escape", i)),
+                        }
+                    }
+                    Some(c @ ('\\' | '/' | '"' | '\'')) => *c,
+                    _ => return Err(QueryError::at("Invalid escape sequence", i)),
+                };
+                value.push(escaped);
+                i += 2;
+            }
+            c => {
+                value.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Err(QueryError::at("Unterminated string", start))
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.index + n).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map(|(_, position)| *position)
+            .unwrap_or(self.end)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).map(|(token, _)| token.clone());
+        self.index += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token, description: &str) -> Result<(), QueryError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected {}", description)))
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), QueryError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("Unexpected token")),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> QueryError {
+        match self.peek() {
+            None => QueryError::at(
+                format!("{} (unexpected end of query)", message.into()),
+                self.end,
+            ),
+            Some(_) => QueryError::at(message, self.position()),
+        }
+    }
+
+    fn parse_integer(&mut self) -> Result<i64, QueryError> {
+        let negative = self.eat(&Token::Minus);
+        match self.peek() {
+            Some(Token::Num(n)) if n.fract() == 0.0 => {
+                let n = *n as i64;
+                self.index += 1;
+                Ok(if negative { -n } else { n })
+            }
+            _ => Err(self.error("Expected an integer")),
+        }
+    }
+}
+
+// JSONPath (RFC 9535 subset)
+
+#[derive(Debug, Clone)]
+struct PathExpr {
+    relative: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Child(Vec<Selector>),
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    Filter(Box<Predicate>),
+}
+
+#[derive(Debug, Clone)]
+enum Predicate {
+    Or(Box<Predicate>, Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+    Exists(PathExpr),
+    Compare(Operand, CmpOp, Operand),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Literal(Value),
+    Path(PathExpr),
+}
+
+impl Parser {
+    fn parse_json_path(&mut self) -> Result<PathExpr, QueryError> {
+        let relative = match self.advance() {
+            Some(Token::Dollar) => false,
+            Some(Token::At) => true,
+            _ => {
+                self.index -= 1;
+                return Err(self.error("Expected '$' or '@'"));
+            }
+        };
+
+        let mut segments = vec![];
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => {
+                    self.index += 1;
+                    segments.push(Segment::Child(vec![self.parse_dot_selector()?]));
+                }
+                Some(Token::DotDot) => {
+                    self.index += 1;
+                    if self.eat(&Token::LBracket) {
+                        segments.push(Segment::Descendant(self.parse_bracket_selectors()?));
+                    } else {
+                        segments.push(Segment::Descendant(vec![self.parse_dot_selector()?]));
+                    }
+                }
+                Some(Token::LBracket) => {
+                    self.index += 1;
+                    segments.push(Segment::Child(self.parse_bracket_selectors()?));
+                }
+                _ => break,
+            }
+        }
+
+        Ok(PathExpr { relative, segments })
+    }
+
+    fn parse_dot_selector(&mut self) -> Result<Selector, QueryError> {
+        match self.peek().cloned() {
+            Some(Token::Star) => {
+                self.index += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some(Token::Ident(name)) => {
+                self.index += 1;
+                Ok(Selector::Name(name))
+            }
+            _ => Err(self.error("Expected a member name or '*'")),
+        }
+    }
+
+    fn parse_bracket_selectors(&mut self) -> Result<Vec<Selector>, QueryError> {
+        let mut selectors = vec![self.parse_bracket_selector()?];
+        while self.eat(&Token::Comma) {
+            selectors.push(self.parse_bracket_selector()?);
+        }
+        self.expect(&Token::RBracket, "']'")?;
+        Ok(selectors)
+    }
+
+    fn parse_bracket_selector(&mut self) -> Result<Selector, QueryError> {
+        match self.peek().cloned() {
+            Some(Token::Str(name)) => {
+                self.index += 1;
+                Ok(Selector::Name(name))
+            }
+            Some(Token::Star) => {
+                self.index += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some(Token::Question) => {
+                self.index += 1;
+                Ok(Selector::Filter(Box::new(self.parse_predicate_or()?)))
+            }
+            Some(Token::Colon) | Some(Token::Minus) | Some(Token::Num(_)) => {
+                let start = match self.peek() {
+                    Some(Token::Colon) => None,
+                    _ => Some(self.parse_integer()?),
+                };
+                if !self.eat(&Token::Colon) {
+                    return match start {
+                        Some(index) => Ok(Selector::Index(index)),
+                        None => Err(self.error("Expected an index")),
+                    };
+                }
+                let end = match self.peek() {
+                    Some(Token::Minus) | Some(Token::Num(_)) => Some(self.parse_integer()?),
+                    _ => None,
+                };
+                let step = if self.eat(&Token::Colon) {
+                    match self.peek() {
+                        Some(Token::Minus) | Some(Token::Num(_)) => Some(self.parse_integer()?),
+                        _ => None,
+                    }
+                } else {
+                    None
+                };
+                Ok(Selector::Slice(start, end, step))
+            }
+            _ => Err(self.error("Expected a selector")),
+        }
+    }
+
+    fn parse_predicate_or(&mut self) -> Result<Predicate, QueryError> {
+        let mut lhs = self.parse_predicate_and()?;
+        while self.eat(&Token::Or) {
+            let rhs = self.parse_predicate_and()?;
+            lhs = Predicate::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_predicate_and(&mut self) -> Result<Predicate, QueryError> {
+        let mut lhs = self.parse_predicate_unary()?;
+        while self.eat(&Token::And) {
+            let rhs = self.parse_predicate_unary()?;
+            lhs = Predicate::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_predicate_unary(&mut self) -> Result<Predicate, QueryError> {
+        if self.eat(&Token::Bang) {
+            return Ok(Predicate::Not(Box::new(self.parse_predicate_unary()?)));
+        }
+        if self.eat(&Token::LParen) {
+            let predicate = self.parse_predicate_or()?;
+            self.expect(&Token::RParen, "')'")?;
+            return Ok(predicate);
+        }
+
+        let position = self.position();
+        let lhs = self.parse_operand()?;
+        if let Some(Token::Cmp(op)) = self.peek().cloned() {
+            self.index += 1;
+            let rhs = self.parse_operand()?;
+            return Ok(Predicate::Compare(lhs, op, rhs));
+        }
+        match lhs {
+            Operand::Path(path) => Ok(Predicate::Exists(path)),
+            Operand::Literal(_) => Err(QueryError::at(
+                "A literal must be compared with something",
+                position,
+            )),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, QueryError> {
+        match self.peek().cloned() {
+            Some(Token::Dollar) | Some(Token::At) => Ok(Operand::Path(self.parse_json_path()?)),
+            Some(Token::Str(value)) => {
+                self.index += 1;
+                Ok(Operand::Literal(Value::String(value)))
+            }
+            Some(Token::Minus) | Some(Token::Num(_)) => {
+                let negative = self.eat(&Token::Minus);
+                match self.advance() {
+                    Some(Token::Num(n)) => Ok(Operand::Literal(number_value(if negative {
+                        -n
+                    } else {
+                        n
+                    }))),
+                    _ => {
+                        self.index -= 1;
+                        Err(self.error("Expected a number"))
+                    }
+                }
+            }
+            Some(Token::Ident(name)) => {
+                let value = match name.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" => Value::Null,
+                    _ => return Err(self.error(format!("Unknown literal '{}'", name))),
+                };
+                self.index += 1;
+                Ok(Operand::Literal(value))
+            }
+            _ => Err(self.error("Expected a path or a literal")),
+        }
+    }
+}
+
+fn select_path<'a>(path: &PathExpr, root: &'a Value, current: &'a Value) -> Vec<&'a Value> {
+    let mut nodes = vec![if path.relative { current } else { root }];
+    for segment in &path.segments {
+        nodes = match segment {
+            Segment::Child(selectors) => nodes
+                .into_iter()
+                .flat_map(|node| apply_selectors(selectors, root, node))
+                .collect(),
+            Segment::Descendant(selectors) => nodes
+                .into_iter()
+                .flat_map(descendants)
+                .flat_map(|node| apply_selectors(selectors, root, node))
+                .collect(),
+        };
+    }
+    nodes
+}
+
+fn apply_selectors<'a>(selectors: &[Selector], root: &'a Value, node: &'a Value) -> Vec<&'a Value> {
+    let mut selected = vec![];
+    for selector in selectors {
+        match selector {
+            Selector::Name(name) => selected.extend(node.as_object().and_then(|o| o.get(name))),
+            Selector::Wildcard => selected.extend(children(node)),
+            Selector::Index(index) => {
+                if let Some(array) = node.as_array() {
+                    selected.extend(normalize_index(*index, array.len()).map(|i| &array[i]));
+                }
+            }
+            Selector::Slice(start, end, step) => {
+                if let Some(array) = node.as_array() {
+                    selected.extend(
+                        slice_indices(*start, *end, *step, array.len())
+                            .into_iter()
+                            .map(|i| &array[i]),
+                    );
+                }
+            }
+            Selector::Filter(predicate) => selected.extend(
+                children(node)
+                    .into_iter()
+                    .filter(|child| test_predicate(predicate, root, child)),
+            ),
+        }
+    }
+    selected
+}
+
+fn children(node: &Value) -> Vec<&Value> {
+    match node {
+        Value::Array(array) => array.iter().collect(),
+        Value::Object(object) => object.values().collect(),
+        _ => vec![],
+    }
+}
+
+fn descendants(node: &Value) -> Vec<&Value> {
+    let mut all = vec![node];
+    for child in children(node) {
+        all.extend(descendants(child));
+    }
+    all
+}
+
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    if index >= 0 && (index as usize) < len {
+        Some(index as usize)
+    } else {
+        None
+    }
+}
+
+fn slice_indices(
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+    len: usize,
+) -> Vec<usize> {
+    let len = len as i64;
+    let step = step.unwrap_or(1);
+    let normalize = |i: i64| if i >= 0 { i } else { len + i };
+    let mut indices = vec![];
+
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+        let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+        let mut i = lower;
+        while i < upper {
+            indices.push(i as usize);
+            // A huge step would overflow, and it means there are no further indices anyway.
+            match i.checked_add(step) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+        let lower = end.map(normalize).unwrap_or(-len - 1).clamp(-1, len - 1);
+        let mut i = upper;
+        while i > lower {
+            indices.push(i as usize);
+            match i.checked_add(step) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+    }
+
+    indices
+}
+
+fn test_predicate(predicate: &Predicate, root: &Value, current: &Value) -> bool {
+    match predicate {
+        Predicate::Or(lhs, rhs) => {
+            test_predicate(lhs, root, current) || test_predicate(rhs, root, current)
+        }
+        Predicate::And(lhs, rhs) => {
+            test_predicate(lhs, root, current) && test_predicate(rhs, root, current)
+        }
+        Predicate::Not(inner) => !test_predicate(inner, root, current),
+        Predicate::Exists(path) => !select_path(path, root, current).is_empty(),
+        Predicate::Compare(lhs, op, rhs) => {
+            let lhs = resolve_operand(lhs, root, current);
+            let rhs = resolve_operand(rhs, root, current);
+            let equal = match (&lhs, &rhs) {
+                (None, None) => true,
+                (Some(a), Some(b)) => json_equal(a, b),
+                _ => false,
+            };
+            let less = match (&lhs, &rhs) {
+                (Some(Value::Number(a)), Some(Value::Number(b))) => {
+                    a.as_f64().unwrap_or(0.0) < b.as_f64().unwrap_or(0.0)
+                }
+                (Some(Value::String(a)), Some(Value::String(b))) => a < b,
+                _ => false,
+            };
+            match op {
+                CmpOp::Eq => equal,
+                CmpOp::Ne => !equal,
+                CmpOp::Lt => less,
+                CmpOp::Le => less || equal,
+                CmpOp::Gt => !less && !equal && comparable(&lhs, &rhs),
+                CmpOp::Ge => !less && comparable(&lhs, &rhs),
+            }
+        }
+    }
+}
+
+fn comparable(lhs: &Option<Value>, rhs: &Option<Value>) -> bool {
+    matches!(
+        (lhs, rhs),
+        (Some(Value::Number(_)), Some(Value::Number(_)))
+            | (Some(Value::String(_)), Some(Value::String(_)))
+    ) || lhs == rhs
+}
+
+fn resolve_operand(operand: &Operand, root: &Value, current: &Value) -> Option<Value> {
+    match operand {
+        Operand::Literal(value) => Some(value.clone()),
+        Operand::Path(path) => match select_path(path, root, current).as_slice() {
+            [single] => Some((*single).clone()),
+            _ => None,
+        },
+    }
+}
+
+// jq subset
+
+#[derive(Debug, Clone)]
+enum Filter {
+    Identity,
+    RecurseAll,
+    Literal(Value),
+    Field(Box<Filter>, String),
+    Index(Box<Filter>, Box<Filter>),
+    Slice(Box<Filter>, Option<Box<Filter>>, Option<Box<Filter>>),
+    Iterate(Box<Filter>),
+    Optional(Box<Filter>),
+    Array(Option<Box<Filter>>),
+    Object(Vec<(String, Filter)>),
+    Pipe(Box<Filter>, Box<Filter>),
+    Comma(Box<Filter>, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Compare(Box<Filter>, CmpOp, Box<Filter>),
+    Arithmetic(Box<Filter>, ArithOp, Box<Filter>),
+    Call(Builtin, Vec<Filter>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Builtin {
+    Select,
+    Map,
+    Has,
+    Keys,
+    Length,
+    Not,
+    Type,
+    Add,
+    Empty,
+    Sort,
+    Reverse,
+    First,
+    Last,
+    ToString,
+}
+
+impl Builtin {
+    fn lookup(name: &str) -> Option<(Builtin, usize)> {
+        Some(match name {
+            "select" => (Builtin::Select, 1),
+            "map" => (Builtin::Map, 1),
+            "has" => (Builtin::Has, 1),
+            "keys" => (Builtin::Keys, 0),
+            "length" => (Builtin::Length, 0),
+            "not" => (Builtin::Not, 0),
+            "type" => (Builtin::Type, 0),
+            "add" => (Builtin::Add, 0),
+            "empty" => (Builtin::Empty, 0),
+            "sort" => (Builtin::Sort, 0),
+            "reverse" => (Builtin::Reverse, 0),
+            "first" => (Builtin::First, 0),
+            "last" => (Builtin::Last, 0),
+            "tostring" => (Builtin::ToString, 0),
+            _ => return None,
+        })
+    }
+}
+
+impl Parser {
+    fn parse_pipe(&mut self) -> Result<Filter, QueryError> {
+        let mut lhs = self.parse_comma()?;
+        while self.eat(&Token::Pipe) {
+            let rhs = self.parse_comma()?;
+            lhs = Filter::Pipe(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_comma(&mut self) -> Result<Filter, QueryError> {
+        let mut lhs = self.parse_or()?;
+        while self.eat(&Token::Comma) {
+            let rhs = self.parse_or()?;
+            lhs = Filter::Comma(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_or(&mut self) -> Result<Filter, QueryError> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::Ident("or".to_string())) {
+            self.index += 1;
+            let rhs = self.parse_and()?;
+            lhs = Filter::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Filter, QueryError> {
+        let mut lhs = self.parse_comparison()?;
+        while self.peek() == Some(&Token::Ident("and".to_string())) {
+            self.index += 1;
+            let rhs = self.parse_comparison()?;
+            lhs = Filter::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Filter, QueryError> {
+        let lhs = self.parse_additive()?;
+        if let Some(Token::Cmp(op)) = self.peek().cloned() {
+            self.index += 1;
+            let rhs = self.parse_additive()?;
+            return Ok(Filter::Compare(Box::new(lhs), op, Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_additive(&mut self) -> Result<Filter, QueryError> {
+        let mut lhs = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithOp::Add,
+                Some(Token::Minus) => ArithOp::Sub,
+                _ => break,
+            };
+            self.index += 1;
+            let rhs = self.parse_multiplicative()?;
+            lhs = Filter::Arithmetic(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Filter, QueryError> {
+        let mut lhs = self.parse_postfix()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => ArithOp::Mul,
+                Some(Token::Slash) => ArithOp::Div,
+                _ => break,
+            };
+            self.index += 1;
+            let rhs = self.parse_postfix()?;
+            lhs = Filter::Arithmetic(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_postfix(&mut self) -> Result<Filter, QueryError> {
+        let mut filter = self.parse_primary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => match self.peek_nth(1).cloned() {
+                    Some(Token::Ident(name)) | Some(Token::Str(name)) => {
+                        self.index += 2;
+                        filter = Filter::Field(Box::new(filter), name);
+                    }
+                    Some(Token::LBracket) => {
+                        self.index += 2;
+                        filter = self.parse_bracket_suffix(filter)?;
+                    }
+                    _ => {
+                        self.index += 1;
+                        return Err(self.error("Expected a field name"));
+                    }
+                },
+                Some(Token::LBracket) => {
+                    self.index += 1;
+                    filter = self.parse_bracket_suffix(filter)?;
+                }
+                Some(Token::Question) => {
+                    self.index += 1;
+                    filter = Filter::Optional(Box::new(filter));
+                }
+                _ => return Ok(filter),
+            }
+        }
+    }
+
+    fn parse_bracket_suffix(&mut self, base: Filter) -> Result<Filter, QueryError> {
+        if self.eat(&Token::RBracket) {
+            return Ok(Filter::Iterate(Box::new(base)));
+        }
+        if self.eat(&Token::Colon) {
+            let end = self.parse_pipe()?;
+            self.expect(&Token::RBracket, "']'")?;
+            return Ok(Filter::Slice(Box::new(base), None, Some(Box::new(end))));
+        }
+
+        let index = self.parse_pipe()?;
+        if self.eat(&Token::Colon) {
+            let end = if self.peek() == Some(&Token::RBracket) {
+                None
+            } else {
+                Some(Box::new(self.parse_pipe()?))
+            };
+            self.expect(&Token::RBracket, "']'")?;
+            return Ok(Filter::Slice(Box::new(base), Some(Box::new(index)), end));
+        }
+        self.expect(&Token::RBracket, "']'")?;
+        Ok(Filter::Index(Box::new(base), Box::new(index)))
+    }
+
+    fn parse_primary(&mut self) -> Result<Filter, QueryError> {
+        let position = self.position();
+        match self.advance() {
+            Some(Token::Dot) => match self.peek().cloned() {
+                Some(Token::Ident(name)) | Some(Token::Str(name)) => {
+                    self.index += 1;
+                    Ok(Filter::Field(Box::new(Filter::Identity), name))
+                }
+                Some(Token::LBracket) => {
+                    self.index += 1;
+                    self.parse_bracket_suffix(Filter::Identity)
+                }
+                _ => Ok(Filter::Identity),
+            },
+            Some(Token::DotDot) => Ok(Filter::RecurseAll),
+            Some(Token::Num(n)) => Ok(Filter::Literal(number_value(n))),
+            Some(Token::Minus) => match self.advance() {
+                Some(Token::Num(n)) => Ok(Filter::Literal(number_value(-n))),
+                _ => {
+                    self.index -= 1;
+                    Err(self.error("Expected a number"))
+                }
+            },
+            Some(Token::Str(value)) => Ok(Filter::Literal(Value::String(value))),
+            Some(Token::LParen) => {
+                let filter = self.parse_pipe()?;
+                self.expect(&Token::RParen, "')'")?;
+                Ok(filter)
+            }
+            Some(Token::LBracket) => {
+                if self.eat(&Token::RBracket) {
+                    return Ok(Filter::Array(None));
+                }
+                let filter = self.parse_pipe()?;
+                self.expect(&Token::RBracket, "']'")?;
+                Ok(Filter::Array(Some(Box::new(filter))))
+            }
+            Some(Token::LBrace) => self.parse_object(),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Filter::Literal(Value::Bool(true))),
+                "false" => Ok(Filter::Literal(Value::Bool(false))),
+                "null" => Ok(Filter::Literal(Value::Null)),
+                _ => self.parse_call(name, position),
+            },
+            Some(_) => {
+                self.index -= 1;
+                Err(self.error("Unexpected token"))
+            }
+            None => Err(self.error("Expected a filter")),
+        }
+    }
+
+    fn parse_call(&mut self, name: String, position: usize) -> Result<Filter, QueryError> {
+        let (builtin, arity) = Builtin::lookup(&name)
+            .ok_or_else(|| QueryError::at(format!("Unknown function '{}'", name), position))?;
+
+        let mut arguments = vec![];
+        if self.eat(&Token::LParen) {
+            arguments.push(self.parse_pipe()?);
+            while self.eat(&Token::Semicolon) {
+                arguments.push(self.parse_pipe()?);
+            }
+            self.expect(&Token::RParen, "')'")?;
+        }
+
+        if arguments.len() != arity {
+            return Err(QueryError::at(
+                format!(
+                    "'{}' takes {} argument(s) but {} were given",
+                    name,
+                    arity,
+                    arguments.len()
+                ),
+                position,
+            ));
+        }
+        Ok(Filter::Call(builtin, arguments))
+    }
+
+    fn parse_object(&mut self) -> Result<Filter, QueryError> {
+        let mut entries = vec![];
+        if self.eat(&Token::RBrace) {
+            return Ok(Filter::Object(entries));
+        }
+
+        loop {
+            let key = match self.advance() {
+                Some(Token::Ident(key)) | Some(Token::Str(key)) => key,
+                _ => {
+                    self.index -= 1;
+                    return Err(self.error("Expected an object key"));
+                }
+            };
+            let value = if self.eat(&Token::Colon) {
+                self.parse_or()?
+            } else {
+                Filter::Field(Box::new(Filter::Identity), key.clone())
+            };
+            entries.push((key, value));
+
+            if self.eat(&Token::RBrace) {
+                return Ok(Filter::Object(entries));
+            }
+            self.expect(&Token::Comma, "',' or '}'")?;
+        }
+    }
+}
+
+fn run(filter: &Filter, input: &Value) -> Result<Vec<Value>, QueryError> {
+    match filter {
+        Filter::Identity => Ok(vec![input.clone()]),
+        Filter::RecurseAll => Ok(descendants(input).into_iter().cloned().collect()),
+        Filter::Literal(value) => Ok(vec![value.clone()]),
+        Filter::Field(base, name) => run(base, input)?
+            .into_iter()
+            .map(|value| match value {
+                Value::Object(mut object) => Ok(object.remove(name).unwrap_or(Value::Null)),
+                Value::Null => Ok(Value::Null),
+                other => Err(QueryError::runtime(format!(
+                    "Cannot index {} with \"{}\"",
+                    type_name(&other),
+                    name
+                ))),
+            })
+            .collect(),
+        Filter::Index(base, index) => {
+            let mut results = vec![];
+            for value in run(base, input)? {
+                for index in run(index, input)? {
+                    results.push(index_value(&value, &index)?);
+                }
+            }
+            Ok(results)
+        }
+        Filter::Slice(base, start, end) => {
+            let starts = match start {
+                Some(start) => run(start, input)?,
+                None => vec![Value::Null],
+            };
+            let ends = match end {
+                Some(end) => run(end, input)?,
+                None => vec![Value::Null],
+            };
+            let mut results = vec![];
+            for value in run(base, input)? {
+                for start in &starts {
+                    for end in &ends {
+                        results.push(slice_value(&value, start, end)?);
+                    }
+                }
+            }
+            Ok(results)
+        }
+        Filter::Iterate(base) => {
+            let mut results = vec![];
+            for value in run(base, input)? {
+                match value {
+                    Value::Array(array) => results.extend(array),
+                    Value::Object(object) => results.extend(object.into_iter().map(|(_, v)| v)),
+                    other => {
+                        return Err(QueryError::runtime(format!(
+                            "Cannot iterate over {}",
+                            type_name(&other)
+                        )))
+                    }
+                }
+            }
+            Ok(results)
+        }
+        Filter::Optional(inner) => Ok(run(inner, input).unwrap_or_default()),
+        Filter::Array(inner) => Ok(vec![Value::Array(match inner {
+            Some(inner) => run(inner, input)?,
+            None => vec![],
+        })]),
+        Filter::Object(entries) => {
+            let mut objects = vec![Map::new()];
+            for (key, value) in entries {
+                let values = run(value, input)?;
+                objects = objects
+                    .into_iter()
+                    .flat_map(|object| {
+                        values.iter().map(move |value| {
+                            let mut object = object.clone();
+                            object.insert(key.clone(), value.clone());
+                            object
+                        })
+                    })
+                    .collect();
+            }
+            Ok(objects.into_iter().map(Value::Object).collect())
+        }
+        Filter::Pipe(lhs, rhs) => {
+            let mut results = vec![];
+            for value in run(lhs, input)? {
+                results.extend(run(rhs, &value)?);
+            }
+            Ok(results)
+        }
+        Filter::Comma(lhs, rhs) => {
+            let mut results = run(lhs, input)?;
+            results.extend(run(rhs, input)?);
+            Ok(results)
+        }
+        Filter::And(lhs, rhs) => {
+            let mut results = vec![];
+            for l in run(lhs, input)? {
+                if !is_truthy(&l) {
+                    results.push(Value::Bool(false));
+                    continue;
+                }
+                for r in run(rhs, input)? {
+                    results.push(Value::Bool(is_truthy(&r)));
+                }
+            }
+            Ok(results)
+        }
+        Filter::Or(lhs, rhs) => {
+            let mut results = vec![];
+            for l in run(lhs, input)? {
+                if is_truthy(&l) {
+                    results.push(Value::Bool(true));
+                    continue;
+                }
+                for r in run(rhs, input)? {
+                    results.push(Value::Bool(is_truthy(&r)));
+                }
+            }
+            Ok(results)
+        }
+        Filter::Compare(lhs, op, rhs) => {
+            let rhs = run(rhs, input)?;
+            let mut results = vec![];
+            for l in run(lhs, input)? {
+                for r in &rhs {
+                    let ordering = compare_values(&l, r);
+                    results.push(Value::Bool(match op {
+                        CmpOp::Eq => ordering == Ordering::Equal,
+                        CmpOp::Ne => ordering != Ordering::Equal,
+                        CmpOp::Lt => ordering == Ordering::Less,
+                        CmpOp::Le => ordering != Ordering::Greater,
+                        CmpOp::Gt => ordering == Ordering::Greater,
+                        CmpOp::Ge => ordering != Ordering::Less,
+                    }));
+                }
+            }
+            Ok(results)
+        }
+        Filter::Arithmetic(lhs, op, rhs) => {
+            let rhs = run(rhs, input)?;
+            let mut results = vec![];
+            for l in run(lhs, input)? {
+                for r in &rhs {
+                    results.push(arithmetic(&l, *op, r)?);
+                }
+            }
+            Ok(results)
+        }
+        Filter::Call(builtin, arguments) => call(*builtin, arguments, input),
+    }
+}
+
+fn call(builtin: Builtin, arguments: &[Filter], input: &Value) -> Result<Vec<Value>, QueryError> {
+    match builtin {
+        Builtin::Select => Ok(run(&arguments[0], input)?
+            .into_iter()
+            .filter(is_truthy)
+            .map(|_| input.clone())
+            .collect()),
+        Builtin::Map => match input {
+            Value::Array(array) => {
+                let mut mapped = vec![];
+                for item in array {
+                    mapped.extend(run(&arguments[0], item)?);
+                }
+                Ok(vec![Value::Array(mapped)])
+            }
+            other => Err(QueryError::runtime(format!(
+                "Cannot map over {}",
+                type_name(other)
+            ))),
+        },
+        Builtin::Has => run(&arguments[0], input)?
+            .into_iter()
+            .map(|key| match (input, &key) {
+                (Value::Object(object), Value::String(key)) => {
+                    Ok(Value::Bool(object.contains_key(key)))
+                }
+                (Value::Array(array), Value::Number(index)) => Ok(Value::Bool(
+                    index
+                        .as_f64()
+                        .is_some_and(|i| i >= 0.0 && (i as usize) < array.len()),
+                )),
+                _ => Err(QueryError::runtime(format!(
+                    "Cannot check whether {} has a {} key",
+                    type_name(input),
+                    type_name(&key)
+                ))),
+            })
+            .collect(),
+        Builtin::Keys => match input {
+            Value::Object(object) => {
+                let mut keys: Vec<&String> = object.keys().collect();
+                keys.sort();
+                Ok(vec![Value::Array(
+                    keys.into_iter().map(|k| Value::String(k.clone())).collect(),
+                )])
+            }
+            Value::Array(array) => Ok(vec![Value::Array(
+                (0..array.len()).map(|i| Value::from(i as u64)).collect(),
+            )]),
+            other => Err(QueryError::runtime(format!(
+                "{} has no keys",
+                type_name(other)
+            ))),
+        },
+        Builtin::Length => Ok(vec![match input {
+            Value::Null => Value::from(0),
+            Value::Bool(_) => {
+                return Err(QueryError::runtime("boolean has no length"));
+            }
+            Value::Number(n) => number_value(n.as_f64().unwrap_or(0.0).abs()),
+            Value::String(s) => Value::from(s.chars().count() as u64),
+            Value::Array(array) => Value::from(array.len() as u64),
+            Value::Object(object) => Value::from(object.len() as u64),
+        }]),
+        Builtin::Not => Ok(vec![Value::Bool(!is_truthy(input))]),
+        Builtin::Type => Ok(vec![Value::String(type_name(input).to_string())]),
+        Builtin::Add => match input {
+            Value::Array(array) => {
+                let mut total = Value::Null;
+                for item in array {
+                    total = arithmetic(&total, ArithOp::Add, item)?;
+                }
+                Ok(vec![total])
+            }
+            other => Err(QueryError::runtime(format!(
+                "Cannot add the elements of {}",
+                type_name(other)
+            ))),
+        },
+        Builtin::Empty => Ok(vec![]),
+        Builtin::Sort => match input {
+            Value::Array(array) => {
+                let mut sorted = array.clone();
+                sorted.sort_by(compare_values);
+                Ok(vec![Value::Array(sorted)])
+            }
+            other => Err(QueryError::runtime(format!(
+                "{} cannot be sorted",
+                type_name(other)
+            ))),
+        },
+        Builtin::Reverse => match input {
+            Value::Array(array) => Ok(vec![Value::Array(array.iter().rev().cloned().collect())]),
+            Value::String(s) => Ok(vec![Value::String(s.chars().rev().collect())]),
+            Value::Null => Ok(vec![Value::Array(vec![])]),
+            other => Err(QueryError::runtime(format!(
+                "{} cannot be reversed",
+                type_name(other)
+            ))),
+        },
+        Builtin::First => Ok(vec![index_value(input, &Value::from(0))?]),
+        Builtin::Last => Ok(vec![index_value(input, &Value::from(-1))?]),
+        Builtin::ToString => Ok(vec![match input {
+            Value::String(_) => input.clone(),
+            other => Value::String(other.to_string()),
+        }]),
+    }
+}
+
+fn index_value(value: &Value, index: &Value) -> Result<Value, QueryError> {
+    match (value, index) {
+        (Value::Object(object), Value::String(key)) => {
+            Ok(object.get(key).cloned().unwrap_or(Value::Null))
+        }
+        (Value::Array(array), Value::Number(n)) => {
+            let index = n.as_f64().unwrap_or(0.0).floor() as i64;
+            Ok(normalize_index(index, array.len())
+                .map(|i| array[i].clone())
+                .unwrap_or(Value::Null))
+        }
+        (Value::Null, Value::String(_) | Value::Number(_)) => Ok(Value::Null),
+        _ => Err(QueryError::runtime(format!(
+            "Cannot index {} with {}",
+            type_name(value),
+            type_name(index)
+        ))),
+    }
+}
+
+fn slice_value(value: &Value, start: &Value, end: &Value) -> Result<Value, QueryError> {
+    let bound = |bound: &Value| match bound {
+        Value::Null => Ok(None),
+        Value::Number(n) => Ok(Some(n.as_f64().unwrap_or(0.0).floor() as i64)),
+        other => Err(QueryError::runtime(format!(
+            "Slice bounds must be numbers, not {}",
+            type_name(other)
+        ))),
+    };
+    let (start, end) = (bound(start)?, bound(end)?);
+
+    match value {
+        Value::Array(array) => Ok(Value::Array(
+            slice_indices(start, end, None, array.len())
+                .into_iter()
+                .map(|i| array[i].clone())
+                .collect(),
+        )),
+        Value::String(s) => {
+            let chars: Vec<char> = s.chars().collect();
+            Ok(Value::String(
+                slice_indices(start, end, None, chars.len())
+                    .into_iter()
+                    .map(|i| chars[i])
+                    .collect(),
+            ))
+        }
+        Value::Null => Ok(Value::Null),
+        other => Err(QueryError::runtime(format!(
+            "Cannot slice {}",
+            type_name(other)
+        ))),
+    }
+}
+
+fn arithmetic(lhs: &Value, op: ArithOp, rhs: &Value) -> Result<Value, QueryError> {
+    let result = match (op, lhs, rhs) {
+        (ArithOp::Add, Value::Null, other) | (ArithOp::Add, other, Value::Null) => {
+            Some(other.clone())
+        }
+        (_, Value::Number(a), Value::Number(b)) => {
+            let (a, b) = (a.as_f64().unwrap_or(0.0), b.as_f64().unwrap_or(0.0));
+            match op {
+                ArithOp::Add => Some(number_value(a + b)),
+                ArithOp::Sub => Some(number_value(a - b)),
+                ArithOp::Mul => Some(number_value(a * b)),
+                ArithOp::Div if b == 0.0 => {
+                    return Err(QueryError::runtime("Division by zero"));
+                }
+                ArithOp::Div => Some(number_value(a / b)),
+            }
+        }
+        (ArithOp::Add, Value::String(a), Value::String(b)) => Some(Value::String(a.clone() + b)),
+        (ArithOp::Add, Value::Array(a), Value::Array(b)) => {
+            Some(Value::Array(a.iter().chain(b).cloned().collect()))
+        }
+        (ArithOp::Add, Value::Object(a), Value::Object(b)) => {
+            let mut merged = a.clone();
+            merged.extend(b.iter().map(|(k, v)| (k.clone(), v.clone())));
+            Some(Value::Object(merged))
+        }
+        (ArithOp::Sub, Value::Array(a), Value::Array(b)) => Some(Value::Array(
+            a.iter()
+                .filter(|item| !b.iter().any(|other| json_equal(item, other)))
+                .cloned()
+                .collect(),
+        )),
+        _ => None,
+    };
+
+    result.ok_or_else(|| {
+        QueryError::runtime(format!(
+            "Cannot apply {:?} to {} and {}",
+            op,
+            type_name(lhs),
+            type_name(rhs)
+        ))
+    })
+}
+
+fn number_value(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        Value::from(n as i64)
+    } else {
+        Number::from_f64(n)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(false) => 1,
+        Value::Bool(true) => 2,
+        Value::Number(_) => 3,
+        Value::String(_) => 4,
+        Value::Array(_) => 5,
+        Value::Object(_) => 6,
+    }
+}
+
+fn json_equal(a: &Value, b: &Value) -> bool {
+    compare_values(a, b) == Ordering::Equal
+}
+
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .unwrap_or(0.0)
+            .total_cmp(&y.as_f64().unwrap_or(0.0)),
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Array(x), Value::Array(y)) => x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| compare_values(x, y))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or_else(|| x.len().cmp(&y.len())),
+        (Value::Object(x), Value::Object(y)) => {
+            let mut x_keys: Vec<&String> = x.keys().collect();
+            let mut y_keys: Vec<&String> = y.keys().collect();
+            x_keys.sort();
+            y_keys.sort();
+            x_keys.cmp(&y_keys).then_with(|| {
+                x_keys
+                    .iter()
+                    .map(|key| compare_values(&x[*key], &y[*key]))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            })
+        }
+        _ => type_rank(a).cmp(&type_rank(b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn query(query: &str, document: &Value) -> Vec<Value> {
+        evaluate(query, document).unwrap_or_else(|e| panic!("{}: {}", query, e))
+    }
+
+    // The example from RFC 9535, section 1.5.
+    fn bookstore() -> Value {
+        json!({
+            "store": {
+                "book": [
+                    {
+                        "category": "reference",
+                        "author": "Nigel Rees",
+                        "title": "Sayings of the Century",
+                        "price": 8.95
+                    },
+                    {
+                        "category": "fiction",
+                        "author": "Evelyn Waugh",
+                        "title": "Sword of Honour",
+                        "price": 12.99
+                    },
+                    {
+                        "category": "fiction",
+                        "author": "Herman Melville",
+                        "title": "Moby Dick",
+                        "isbn": "0-553-21311-3",
+                        "price": 8.99
+                    },
+                    {
+                        "category": "fiction",
+                        "author": "J. R. R. Tolkien",
+                        "title": "The Lord of the Rings",
+                        "isbn": "0-395-19395-8",
+                        "price": 22.99
+                    }
+                ],
+                "bicycle": {
+                    "color": "red",
+                    "price": 399
+                }
+            }
+        })
+    }
+
+    fn titles(books: Vec<Value>) -> Vec<Value> {
+        books.iter().map(|book| book["title"].clone()).collect()
+    }
+
+    #[test]
+    fn json_path_bookstore() {
+        let store = bookstore();
+        let authors = vec![
+            json!("Nigel Rees"),
+            json!("Evelyn Waugh"),
+            json!("Herman Melville"),
+            json!("J. R. R. Tolkien"),
+        ];
+        assert_eq!(query("$.store.book[*].author", &store), authors);
+        assert_eq!(query("$..author", &store), authors);
+        assert_eq!(
+            query("$.store.*", &store),
+            vec![
+                store["store"]["book"].clone(),
+                store["store"]["bicycle"].clone()
+            ]
+        );
+        assert_eq!(
+            query("$.store..price", &store),
+            vec![
+                json!(8.95),
+                json!(12.99),
+                json!(8.99),
+                json!(22.99),
+                json!(399)
+            ]
+        );
+        assert_eq!(
+            titles(query("$..book[2]", &store)),
+            vec![json!("Moby Dick")]
+        );
+        assert_eq!(
+            titles(query("$..book[-1]", &store)),
+            vec![json!("The Lord of the Rings")]
+        );
+        let first_two = vec![json!("Sayings of the Century"), json!("Sword of Honour")];
+        assert_eq!(titles(query("$..book[0,1]", &store)), first_two);
+        assert_eq!(titles(query("$..book[:2]", &store)), first_two);
+        assert_eq!(
+            titles(query("$..book[?@.isbn]", &store)),
+            vec![json!("Moby Dick"), json!("The Lord of the Rings")]
+        );
+        assert_eq!(
+            titles(query("$..book[?@.price<10]", &store)),
+            vec![json!("Sayings of the Century"), json!("Moby Dick")]
+        );
+        assert_eq!(query("$..*", &store).len(), 27);
+    }
+
+    #[test]
+    fn json_path_name_selectors() {
+        let document = json!({"o": {"j j": {"k.k": 3}}, "'": {"@": 2}});
+        assert_eq!(query("$.o['j j']", &document), vec![json!({"k.k": 3})]);
+        assert_eq!(query("$.o['j j']['k.k']", &document), vec![json!(3)]);
+        assert_eq!(query(r#"$.o["j j"]["k.k"]"#, &document), vec![json!(3)]);
+        assert_eq!(query(r#"$["'"]["@"]"#, &document), vec![json!(2)]);
+    }
+
+    #[test]
+    fn json_path_index_and_slice_selectors() {
+        let letters = json!(["a", "b", "c", "d", "e", "f", "g"]);
+        assert_eq!(query("$[1]", &letters), vec![json!("b")]);
+        assert_eq!(query("$[-2]", &letters), vec![json!("f")]);
+        assert_eq!(query("$[1:3]", &letters), vec![json!("b"), json!("c")]);
+        assert_eq!(query("$[5:]", &letters), vec![json!("f"), json!("g")]);
+        assert_eq!(query("$[1:5:2]", &letters), vec![json!("b"), json!("d")]);
+        assert_eq!(query("$[5:1:-2]", &letters), vec![json!("f"), json!("d")]);
+        assert_eq!(
+            query("$[::-1]", &letters),
+            ["g", "f", "e", "d", "c", "b", "a"].map(Value::from)
+        );
+        assert_eq!(query("$[1:5:0]", &letters), Vec::<Value>::new());
+    }
+
+    #[test]
+    fn json_path_slice_with_huge_step() {
+        let letters = json!(["a", "b", "c"]);
+        assert_eq!(
+            query("$[1::9223372036854775807]", &letters),
+            vec![json!("b")]
+        );
+        assert_eq!(
+            query("$[1::-9223372036854775807]", &letters),
+            vec![json!("b")]
+        );
+    }
+
+    #[test]
+    fn json_path_filter_selectors() {
+        let document = json!({
+            "a": [3, 5, 1, 2, 4, 6, {"b": "j"}, {"b": "k"}, {"b": {}}, {"b": "kilo"}],
+            "o": {"p": 1, "q": 2, "r": 3, "s": 5, "t": {"u": 6}},
+            "e": "f"
+        });
+        assert_eq!(
+            query("$.a[?@.b == 'kilo']", &document),
+            vec![json!({"b": "kilo"})]
+        );
+        assert_eq!(
+            query("$.a[?@>3.5]", &document),
+            vec![json!(5), json!(4), json!(6)]
+        );
+        assert_eq!(
+            query("$.a[?@.b]", &document),
+            vec![
+                json!({"b": "j"}),
+                json!({"b": "k"}),
+                json!({"b": {}}),
+                json!({"b": "kilo"})
+            ]
+        );
+        assert_eq!(
+            query("$[?@.*]", &document),
+            vec![document["a"].clone(), document["o"].clone()]
+        );
+        assert_eq!(
+            query("$.o[?@<3, ?@<3]", &document),
+            vec![json!(1), json!(2), json!(1), json!(2)]
+        );
+        assert_eq!(
+            query(r#"$.a[?@<2 || @.b == "k"]"#, &document),
+            vec![json!(1), json!({"b": "k"})]
+        );
+        assert_eq!(
+            query("$.o[?@>1 && @<4]", &document),
+            vec![json!(2), json!(3)]
+        );
+        assert_eq!(
+            query("$.a[?@.b == $.x]", &document),
+            [3, 5, 1, 2, 4, 6].map(Value::from)
+        );
+        assert_eq!(query("$.a[?@ == @]", &document).len(), 10);
+    }
+
+    #[test]
+    fn json_path_descendant_segments() {
+        let document = json!({"o": {"j": 1, "k": 2}, "a": [5, 3, [{"j": 4}, {"k": 6}]]});
+        assert_eq!(query("$..j", &document), vec![json!(1), json!(4)]);
+        assert_eq!(query("$..[0]", &document), vec![json!(5), json!({"j": 4})]);
+        assert_eq!(query("$..o", &document), vec![json!({"j": 1, "k": 2})]);
+        assert_eq!(query("$..*", &document).len(), 11);
+    }
+
+    #[test]
+    fn json_path_errors_have_a_position() {
+        let error = evaluate("$.store[", &bookstore()).unwrap_err();
+        assert!(error.position.is_some(), "{}", error);
+    }
+
+    #[test]
+    fn jq_paths() {
+        let document = json!({"a": {"b": [1, 2, 3]}, "c": null});
+        assert_eq!(query(".", &document), vec![document.clone()]);
+        assert_eq!(query(".a", &document), vec![json!({"b": [1, 2, 3]})]);
+        assert_eq!(query(".a.b", &document), vec![json!([1, 2, 3])]);
+        assert_eq!(query(".missing", &document), vec![Value::Null]);
+        assert_eq!(
+            query(".a.b[]", &document),
+            vec![json!(1), json!(2), json!(3)]
+        );
+        assert_eq!(query(".a.b[-1]", &document), vec![json!(3)]);
+        assert_eq!(
+            query(".[]", &document),
+            vec![json!({"b": [1, 2, 3]}), Value::Null]
+        );
+    }
+
+    #[test]
+    fn jq_pipes_select_and_map() {
+        let store = bookstore();
+        assert_eq!(
+            query(".store.book[] | .author", &store),
+            query("$.store.book[*].author", &store)
+        );
+        assert_eq!(
+            query(".store.book[] | select(.price < 10) | .title", &store),
+            vec![json!("Sayings of the Century"), json!("Moby Dick")]
+        );
+        assert_eq!(
+            query(".store.book | map(.price)", &store),
+            vec![json!([8.95, 12.99, 8.99, 22.99])]
+        );
+        assert_eq!(
+            query("[1, 2, 3] | map(. * 2)", &Value::Null),
+            vec![json!([2, 4, 6])]
+        );
+        assert_eq!(
+            query(".store.book | map(select(has(\"isbn\"))) | length", &store),
+            vec![json!(2)]
+        );
+    }
+
+    #[test]
+    fn jq_runtime_errors() {
+        assert!(evaluate(".[]", &json!(1)).is_err());
+        assert!(evaluate(".a", &json!([1])).is_err());
+    }
+}
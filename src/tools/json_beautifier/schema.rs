@@ -0,0 +1,375 @@
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Draft {
+    Draft7,
+    #[default]
+    Draft202012,
+}
+
+impl Draft {
+    pub const ALL: [Draft; 2] = [Draft::Draft7, Draft::Draft202012];
+}
+
+impl std::fmt::Display for Draft {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Draft::Draft7 => "Draft 7",
+                Draft::Draft202012 => "Draft 2020-12",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub instance_path: String,
+    pub message: String,
+    pub line: Option<usize>,
+}
+
+pub fn validate(
+    schema: &Value,
+    draft: Draft,
+    instance: &Value,
+    instance_text: &str,
+) -> Result<Vec<Violation>, String> {
+    let validator = match draft {
+        Draft::Draft7 => jsonschema::draft7::new(schema),
+        Draft::Draft202012 => jsonschema::draft202012::new(schema),
+    }
+    .map_err(|e| format!("Invalid schema: {}", e))?;
+
+    Ok(validator
+        .iter_errors(instance)
+        .map(|error| {
+            let instance_path = error.instance_path().as_str().to_string();
+            Violation {
+                line: locate(instance_text, &instance_path),
+                message: error.to_string(),
+                instance_path,
+            }
+        })
+        .collect())
+}
+
+pub fn infer(sample: &Value) -> Value {
+    let mut schema = Map::new();
+    schema.insert(
+        "$schema".to_string(),
+        json!("https://json-schema.org/draft/2020-12/schema"),
+    );
+    schema.extend(infer_value(sample));
+    Value::Object(schema)
+}
+
+pub fn infer_value(value: &Value) -> Map<String, Value> {
+    let mut schema = Map::new();
+    match value {
+        Value::Null => {
+            schema.insert("type".to_string(), json!("null"));
+        }
+        Value::Bool(_) => {
+            schema.insert("type".to_string(), json!("boolean"));
+        }
+        Value::Number(n) => {
+            let kind = if n.is_f64() { "number" } else { "integer" };
+            schema.insert("type".to_string(), json!(kind));
+        }
+        Value::String(s) => {
+            schema.insert("type".to_string(), json!("string"));
+            if let Some(format) = detect_format(s) {
+                schema.insert("format".to_string(), json!(format));
+            }
+        }
+        Value::Array(array) => {
+            schema.insert("type".to_string(), json!("array"));
+            if let Some(items) = array.iter().map(infer_value).reduce(|a, b| merge(&a, &b)) {
+                schema.insert("items".to_string(), Value::Object(items));
+            }
+        }
+        Value::Object(object) => {
+            schema.insert("type".to_string(), json!("object"));
+            schema.insert(
+                "properties".to_string(),
+                Value::Object(
+                    object
+                        .iter()
+                        .map(|(key, value)| (key.clone(), Value::Object(infer_value(value))))
+                        .collect(),
+                ),
+            );
+            schema.insert(
+                "required".to_string(),
+                Value::Array(object.keys().map(|key| json!(key)).collect()),
+            );
+        }
+    }
+    schema
+}
+
+pub fn merge(a: &Map<String, Value>, b: &Map<String, Value>) -> Map<String, Value> {
+    let mut types = types_of(a);
+    for kind in types_of(b) {
+        if !types.contains(&kind) {
+            types.push(kind);
+        }
+    }
+    if types.contains(&"number".to_string()) {
+        types.retain(|kind| kind != "integer");
+    }
+
+    let mut schema = Map::new();
+    schema.insert(
+        "type".to_string(),
+        match types.as_slice() {
+            [single] => json!(single),
+            _ => json!(types),
+        },
+    );
+
+    match (a.get("format"), b.get("format")) {
+        (Some(x), Some(y)) if x == y => {
+            schema.insert("format".to_string(), x.clone());
+        }
+        (Some(x), None) if !types_of(b).contains(&"string".to_string()) => {
+            schema.insert("format".to_string(), x.clone());
+        }
+        (None, Some(y)) if !types_of(a).contains(&"string".to_string()) => {
+            schema.insert("format".to_string(), y.clone());
+        }
+        _ => (),
+    }
+
+    match (a.get("items"), b.get("items")) {
+        (Some(Value::Object(x)), Some(Value::Object(y))) => {
+            schema.insert("items".to_string(), Value::Object(merge(x, y)));
+        }
+        (Some(items), None) | (None, Some(items)) => {
+            schema.insert("items".to_string(), items.clone());
+        }
+        _ => (),
+    }
+
+    let properties_of = |schema: &Map<String, Value>| -> Option<Map<String, Value>> {
+        schema.get("properties").and_then(Value::as_object).cloned()
+    };
+    match (properties_of(a), properties_of(b)) {
+        (None, None) => (),
+        (x, y) => {
+            let (x, y) = (x.unwrap_or_default(), y.unwrap_or_default());
+            let mut properties = Map::new();
+            for (key, value) in x.iter().chain(y.iter()) {
+                if properties.contains_key(key) {
+                    continue;
+                }
+                let merged = match (x.get(key), y.get(key)) {
+                    (Some(Value::Object(l)), Some(Value::Object(r))) => Value::Object(merge(l, r)),
+                    _ => value.clone(),
+                };
+                properties.insert(key.clone(), merged);
+            }
+            schema.insert("properties".to_string(), Value::Object(properties));
+        }
+    }
+
+    let required_of = |schema: &Map<String, Value>| -> Option<Vec<Value>> {
+        schema.get("required").and_then(Value::as_array).cloned()
+    };
+    let is_object = |schema: &Map<String, Value>| types_of(schema).contains(&"object".to_string());
+    let required = match (required_of(a), required_of(b)) {
+        (Some(x), Some(y)) => Some(x.into_iter().filter(|key| y.contains(key)).collect()),
+        (Some(x), None) if !is_object(b) => Some(x),
+        (None, Some(y)) if !is_object(a) => Some(y),
+        (Some(_), None) | (None, Some(_)) => Some(vec![]),
+        (None, None) => None,
+    };
+    if let Some(required) = required {
+        schema.insert("required".to_string(), Value::Array(required));
+    }
+
+    schema
+}
+
+fn types_of(schema: &Map<String, Value>) -> Vec<String> {
+    match schema.get("type") {
+        Some(Value::String(kind)) => vec![kind.clone()],
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(|kind| kind.as_str().map(str::to_string))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+pub fn detect_format(value: &str) -> Option<&'static str> {
+    let bytes = value.as_bytes();
+    let digits = |range: std::ops::Range<usize>| {
+        bytes
+            .get(range)
+            .is_some_and(|part| part.iter().all(u8::is_ascii_digit))
+    };
+    let is_date = bytes.len() >= 10
+        && digits(0..4)
+        && bytes[4] == b'-'
+        && digits(5..7)
+        && bytes[7] == b'-'
+        && digits(8..10);
+
+    if is_date && bytes.len() == 10 {
+        Some("date")
+    } else if is_date
+        && matches!(bytes[10], b'T' | b't' | b' ')
+        && digits(11..13)
+        && bytes.get(13) == Some(&b':')
+        && digits(14..16)
+    {
+        Some("date-time")
+    } else if is_uuid(value) {
+        Some("uuid")
+    } else if is_email(value) {
+        Some("email")
+    } else if value.starts_with("http://") || value.starts_with("https://") {
+        Some("uri")
+    } else if is_ipv4(value) {
+        Some("ipv4")
+    } else {
+        None
+    }
+}
+
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+fn is_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !local.contains(char::is_whitespace)
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !domain.contains(|c: char| c.is_whitespace() || c == '@')
+        }
+        None => false,
+    }
+}
+
+fn is_ipv4(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('.').collect();
+    parts.len() == 4
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.len() <= 3 && part.parse::<u8>().is_ok())
+}
+
+pub fn locate(text: &str, pointer: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut position = skip_whitespace(bytes, 0);
+
+    for token in pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+    {
+        match bytes.get(position)? {
+            b'{' => {
+                position += 1;
+                loop {
+                    position = skip_whitespace(bytes, position);
+                    if bytes.get(position)? != &b'"' {
+                        return None;
+                    }
+                    let end = skip_value(bytes, position)?;
+                    let key = serde_json::from_str::<String>(&text[position..end]).ok()?;
+                    position = skip_whitespace(bytes, end);
+                    if bytes.get(position)? != &b':' {
+                        return None;
+                    }
+                    position = skip_whitespace(bytes, position + 1);
+                    if key == token {
+                        break;
+                    }
+                    position = skip_whitespace(bytes, skip_value(bytes, position)?);
+                    if bytes.get(position)? != &b',' {
+                        return None;
+                    }
+                    position += 1;
+                }
+            }
+            b'[' => {
+                let index = token.parse::<usize>().ok()?;
+                position = skip_whitespace(bytes, position + 1);
+                for _ in 0..index {
+                    position = skip_whitespace(bytes, skip_value(bytes, position)?);
+                    if bytes.get(position)? != &b',' {
+                        return None;
+                    }
+                    position = skip_whitespace(bytes, position + 1);
+                }
+                if bytes.get(position)? == &b']' {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(bytes[..position].iter().filter(|b| **b == b'\n').count())
+}
+
+fn skip_whitespace(bytes: &[u8], mut position: usize) -> usize {
+    while bytes.get(position).is_some_and(u8::is_ascii_whitespace) {
+        position += 1;
+    }
+    position
+}
+
+fn skip_value(bytes: &[u8], mut position: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let start = position;
+
+    while let Some(&byte) = bytes.get(position) {
+        position += 1;
+        if in_string {
+            match byte {
+                b'\\' => position += 1,
+                b'"' => {
+                    in_string = false;
+                    if depth == 0 {
+                        return Some(position);
+                    }
+                }
+                _ => (),
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth == 0 => return Some(position - 1),
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(position);
+                }
+            }
+            b',' if depth == 0 => return Some(position - 1),
+            byte if byte.is_ascii_whitespace() && depth == 0 && position - 1 > start => {
+                return Some(position - 1)
+            }
+            _ => (),
+        }
+    }
+
+    (depth == 0 && !in_string).then_some(position)
+}
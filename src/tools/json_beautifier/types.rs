@@ -0,0 +1,594 @@
+use serde_json::{Map, Value};
+
+use super::schema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    Rust,
+    TypeScript,
+    Go,
+    Python,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::Rust,
+        Language::TypeScript,
+        Language::Go,
+        Language::Python,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Language::Rust => "rs",
+            Language::TypeScript => "ts",
+            Language::Go => "go",
+            Language::Python => "py",
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Language::Rust => "Rust (serde)",
+                Language::TypeScript => "TypeScript",
+                Language::Go => "Go",
+                Language::Python => "Python (dataclasses)",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Shape {
+    Any,
+    Bool,
+    Integer,
+    Number,
+    String,
+    Array(Box<Shape>),
+    Struct(String),
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    key: String,
+    shape: Shape,
+    nullable: bool,
+    optional: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Struct {
+    name: String,
+    fields: Vec<Field>,
+}
+
+pub fn generate(document: &Value, language: Language, root_name: &str) -> String {
+    let root_name = match pascal_case(root_name).as_str() {
+        "" => "Root".to_string(),
+        name => name.to_string(),
+    };
+
+    // A top-level array is treated as a list of samples of the same type.
+    let root_schema = match document {
+        Value::Array(samples) => samples
+            .iter()
+            .map(schema::infer_value)
+            .reduce(|a, b| schema::merge(&a, &b))
+            .unwrap_or_default(),
+        other => schema::infer_value(other),
+    };
+
+    let mut structs = vec![];
+    let (shape, nullable) = shape_of(&root_schema, &root_name, &mut structs);
+    if structs.is_empty() {
+        let field = Field {
+            key: "value".to_string(),
+            shape,
+            nullable,
+            optional: false,
+        };
+        structs.push(Struct {
+            name: unique_name(&root_name, &structs),
+            fields: vec![field],
+        });
+    }
+
+    match language {
+        Language::Rust => rust(&structs),
+        Language::TypeScript => typescript(&structs),
+        Language::Go => go(&structs),
+        Language::Python => python(&structs),
+    }
+}
+
+fn shape_of(schema: &Map<String, Value>, name: &str, structs: &mut Vec<Struct>) -> (Shape, bool) {
+    let mut types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(kind)) => vec![kind.as_str()],
+        Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+    let nullable = types.contains(&"null");
+    types.retain(|kind| *kind != "null");
+
+    let shape = match types.as_slice() {
+        ["boolean"] => Shape::Bool,
+        ["integer"] => Shape::Integer,
+        ["number"] => Shape::Number,
+        ["string"] => Shape::String,
+        ["array"] => match schema.get("items") {
+            Some(Value::Object(items)) => {
+                Shape::Array(Box::new(shape_of(items, &singular(name), structs).0))
+            }
+            _ => Shape::Array(Box::new(Shape::Any)),
+        },
+        ["object"] => {
+            let name = unique_name(name, structs);
+            let index = structs.len();
+            structs.push(Struct {
+                name: name.clone(),
+                fields: vec![],
+            });
+
+            let required: Vec<&str> = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|keys| keys.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let properties = schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .cloned()
+                .unwrap_or_default();
+
+            let mut fields = vec![];
+            for (key, property) in &properties {
+                let property = property.as_object().cloned().unwrap_or_default();
+                let (shape, nullable) = shape_of(&property, &pascal_case(key), structs);
+                fields.push(Field {
+                    key: key.clone(),
+                    shape,
+                    nullable,
+                    optional: !required.contains(&key.as_str()),
+                });
+            }
+            structs[index].fields = fields;
+            Shape::Struct(name)
+        }
+        _ => Shape::Any,
+    };
+
+    (shape, nullable)
+}
+
+/// Type names that would clash with a keyword or shadow a built-in type in one of the
+/// generated languages. They get a `Type` suffix, like `StringType`.
+const RESERVED_TYPES: [&str; 32] = [
+    "Any",
+    "Array",
+    "Boolean",
+    "Box",
+    "Clone",
+    "Date",
+    "Debug",
+    "Default",
+    "Deserialize",
+    "Err",
+    "Error",
+    "False",
+    "HashMap",
+    "Map",
+    "None",
+    "Number",
+    "Object",
+    "Ok",
+    "Option",
+    "Optional",
+    "Promise",
+    "Record",
+    "Result",
+    "Self",
+    "Serialize",
+    "Set",
+    "Some",
+    "String",
+    "Symbol",
+    "True",
+    "Value",
+    "Vec",
+];
+
+fn unique_name(name: &str, structs: &[Struct]) -> String {
+    let base = match name {
+        "" => "Item".to_string(),
+        name if name.starts_with(|c: char| c.is_ascii_digit()) => format!("Type{}", name),
+        name if RESERVED_TYPES.contains(&name) => format!("{}Type", name),
+        name => name.to_string(),
+    };
+    let mut candidate = base.clone();
+    let mut counter = 2;
+    while structs.iter().any(|s| s.name == candidate) {
+        candidate = format!("{}{}", base, counter);
+        counter += 1;
+    }
+    candidate
+}
+
+fn singular(name: &str) -> String {
+    match name.strip_suffix("ies") {
+        Some(stem) if !stem.is_empty() => format!("{}y", stem),
+        _ => match name.strip_suffix('s') {
+            Some(stem) if !stem.is_empty() && !stem.ends_with('s') => stem.to_string(),
+            _ => format!("{}Item", name),
+        },
+    }
+}
+
+fn words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = vec![];
+    let mut current = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1);
+        let boundary = c.is_uppercase()
+            && previous.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+            });
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn snake_case(key: &str) -> String {
+    words(key).join("_")
+}
+
+fn pascal_case(key: &str) -> String {
+    words(key)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn go_case(key: &str) -> String {
+    const INITIALISMS: [&str; 8] = ["id", "url", "uri", "http", "api", "uuid", "json", "ip"];
+    words(key)
+        .iter()
+        .map(|word| {
+            if INITIALISMS.contains(&word.as_str()) {
+                word.to_uppercase()
+            } else {
+                pascal_case(word)
+            }
+        })
+        .collect()
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn field_name(name: String, fallback_index: usize) -> String {
+    match name.chars().next() {
+        None => format!("field_{}", fallback_index),
+        Some(c) if c.is_ascii_digit() => format!("field_{}", name),
+        Some(_) => name,
+    }
+}
+
+/// Numbers repeated names in order, like `id`, `id_2`, `id_3`, skipping suffixed names that
+/// are already taken. Different keys can map to the same name, like `userId` and `user_id`.
+fn unique_names(names: Vec<String>, separator: &str) -> Vec<String> {
+    let mut used: Vec<String> = vec![];
+    for name in &names {
+        let mut candidate = name.clone();
+        let mut counter = 2;
+        while used.contains(&candidate) || (candidate != *name && names.contains(&candidate)) {
+            candidate = format!("{}{}{}", name.trim_end_matches('_'), separator, counter);
+            counter += 1;
+        }
+        used.push(candidate);
+    }
+    used
+}
+
+fn rust(structs: &[Struct]) -> String {
+    const KEYWORDS: [&str; 47] = [
+        "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern",
+        "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use",
+        "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+        "priv", "typeof", "unsized", "virtual", "yield",
+    ];
+    // These can't be raw identifiers, so they get a trailing underscore instead.
+    const RESERVED: [&str; 4] = ["self", "Self", "super", "crate"];
+    fn rust_type(shape: &Shape) -> String {
+        match shape {
+            Shape::Any => "serde_json::Value".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Integer => "i64".to_string(),
+            Shape::Number => "f64".to_string(),
+            Shape::String => "String".to_string(),
+            Shape::Array(item) => format!("Vec<{}>", rust_type(item)),
+            Shape::Struct(name) => name.clone(),
+        }
+    }
+
+    let mut output = String::from("use serde::{Deserialize, Serialize};\n");
+    for s in structs {
+        output.push_str("\n#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        output.push_str(&format!("pub struct {} {{\n", s.name));
+        let names = s
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let mut name = field_name(snake_case(&field.key), i);
+                if RESERVED.contains(&name.as_str()) {
+                    name.push('_');
+                }
+                name
+            })
+            .collect();
+        for (field, name) in s.fields.iter().zip(unique_names(names, "_")) {
+            let mut attributes = vec![];
+            if name != field.key {
+                attributes.push(format!("rename = {}", Value::String(field.key.clone())));
+            }
+            if field.optional {
+                attributes.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+            }
+            if !attributes.is_empty() {
+                output.push_str(&format!("    #[serde({})]\n", attributes.join(", ")));
+            }
+            let name = match KEYWORDS.contains(&name.as_str()) {
+                true => format!("r#{}", name),
+                false => name,
+            };
+            let ty = match (field.nullable || field.optional, &field.shape) {
+                (true, _) => format!("Option<{}>", rust_type(&field.shape)),
+                (false, shape) => rust_type(shape),
+            };
+            output.push_str(&format!("    pub {}: {},\n", name, ty));
+        }
+        output.push_str("}\n");
+    }
+    output
+}
+
+fn typescript(structs: &[Struct]) -> String {
+    fn ts_type(shape: &Shape) -> String {
+        match shape {
+            Shape::Any => "unknown".to_string(),
+            Shape::Bool => "boolean".to_string(),
+            Shape::Integer | Shape::Number => "number".to_string(),
+            Shape::String => "string".to_string(),
+            Shape::Array(item) => format!("{}[]", ts_type(item)),
+            Shape::Struct(name) => name.clone(),
+        }
+    }
+
+    structs
+        .iter()
+        .map(|s| {
+            let keys = s.fields.iter().map(|field| field.key.clone()).collect();
+            let fields: String = s
+                .fields
+                .iter()
+                .zip(unique_names(keys, "_"))
+                .map(|(field, key)| {
+                    let key = match is_identifier(&key) {
+                        true => key,
+                        false => Value::String(key).to_string(),
+                    };
+                    let optional = if field.optional { "?" } else { "" };
+                    let null = if field.nullable { " | null" } else { "" };
+                    format!(
+                        "  {}{}: {}{};\n",
+                        key,
+                        optional,
+                        ts_type(&field.shape),
+                        null
+                    )
+                })
+                .collect();
+            format!("export interface {} {{\n{}}}\n", s.name, fields)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn go(structs: &[Struct]) -> String {
+    fn go_type(shape: &Shape) -> String {
+        match shape {
+            Shape::Any => "any".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Integer => "int64".to_string(),
+            Shape::Number => "float64".to_string(),
+            Shape::String => "string".to_string(),
+            Shape::Array(item) => format!("[]{}", go_type(item)),
+            Shape::Struct(name) => name.clone(),
+        }
+    }
+
+    structs
+        .iter()
+        .map(|s| {
+            let names = s
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| match go_case(&field.key) {
+                    name if name.starts_with(|c: char| c.is_ascii_digit()) => {
+                        format!("Field{}", name)
+                    }
+                    name if name.is_empty() => format!("Field{}", i),
+                    name => name,
+                })
+                .collect();
+            let rows: Vec<(String, String, String)> = s
+                .fields
+                .iter()
+                .zip(unique_names(names, ""))
+                .map(|(field, name)| {
+                    let pointer = (field.nullable || field.optional)
+                        && !matches!(field.shape, Shape::Array(_) | Shape::Any);
+                    let ty = format!(
+                        "{}{}",
+                        if pointer { "*" } else { "" },
+                        go_type(&field.shape)
+                    );
+                    let omit = if field.optional { ",omitempty" } else { "" };
+                    let tag = format!("`json:{}`", Value::String(format!("{}{}", field.key, omit)));
+                    (name, ty, tag)
+                })
+                .collect();
+            let name_width = rows.iter().map(|r| r.0.len()).max().unwrap_or(0);
+            let type_width = rows.iter().map(|r| r.1.len()).max().unwrap_or(0);
+            let fields: String = rows
+                .iter()
+                .map(|(name, ty, tag)| {
+                    format!(
+                        "\t{:name_width$} {:type_width$} {}\n",
+                        name,
+                        ty,
+                        tag,
+                        name_width = name_width,
+                        type_width = type_width
+                    )
+                })
+                .collect();
+            format!("type {} struct {{\n{}}}\n", s.name, fields)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn python(structs: &[Struct]) -> String {
+    const KEYWORDS: [&str; 35] = [
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+        "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+        "try", "while", "with", "yield",
+    ];
+    fn py_type(shape: &Shape) -> String {
+        match shape {
+            Shape::Any => "Any".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Integer => "int".to_string(),
+            Shape::Number => "float".to_string(),
+            Shape::String => "str".to_string(),
+            Shape::Array(item) => format!("list[{}]", py_type(item)),
+            Shape::Struct(name) => name.clone(),
+        }
+    }
+
+    let mut output = String::from(
+        "from __future__ import annotations\n\nfrom dataclasses import dataclass\nfrom typing import Any, Optional\n",
+    );
+    for s in structs {
+        output.push_str(&format!("\n\n@dataclass\nclass {}:\n", s.name));
+        if s.fields.is_empty() {
+            output.push_str("    pass\n");
+        }
+        let names = s
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let mut name = field_name(snake_case(&field.key), i);
+                if KEYWORDS.contains(&name.as_str()) {
+                    name.push('_');
+                }
+                name
+            })
+            .collect();
+        // Fields with defaults must come after the ones without.
+        let mut fields: Vec<(&Field, String)> =
+            s.fields.iter().zip(unique_names(names, "_")).collect();
+        fields.sort_by_key(|(field, _)| field.optional);
+        for (field, name) in fields {
+            let comment = match name == field.key {
+                true => String::new(),
+                false => format!("  # {}", Value::String(field.key.clone())),
+            };
+            let line = match (field.nullable, field.optional) {
+                (_, true) => format!("{}: Optional[{}] = None", name, py_type(&field.shape)),
+                (true, false) => format!("{}: Optional[{}]", name, py_type(&field.shape)),
+                (false, false) => format!("{}: {}", name, py_type(&field.shape)),
+            };
+            output.push_str(&format!("    {}{}\n", line, comment));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn struct_names(document: &str) -> Vec<String> {
+        generate(&serde_json::from_str(document).unwrap(), Language::Rust, "")
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub struct "))
+            .map(|line| line.trim_end_matches(" {").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn struct_names_are_valid_and_do_not_shadow_std_types() {
+        let document =
+            r#"{"2fa": {}, "self": {}, "string": {}, "option": {}, "vec": {}, "stringType": {}}"#;
+        assert_eq!(
+            struct_names(document),
+            [
+                "Root",
+                "Type2fa",
+                "SelfType",
+                "StringType",
+                "OptionType",
+                "VecType",
+                "StringType2"
+            ]
+        );
+    }
+
+    #[test]
+    fn root_name_is_sanitized() {
+        let value = serde_json::json!(1);
+        assert!(generate(&value, Language::Rust, "string").contains("pub struct StringType {"));
+        assert!(generate(&value, Language::Go, "404").contains("type Type404 struct {"));
+    }
+}
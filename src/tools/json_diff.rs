@@ -0,0 +1,600 @@
+use std::fs;
+
+use iced::{
+    highlighter,
+    widget::{
+        button, checkbox, column, container, horizontal_space, row, scrollable, text,
+        text_editor::{self, Action},
+    },
+    Color, Element,
+    Length::{Fill, FillPortion},
+    Task,
+};
+use rfd::AsyncFileDialog;
+use serde_json::{json, Value};
+
+pub const NAME: &str = "JSON Diff";
+
+const ADDED_COLOR: Color = Color::from_rgb(0.1, 0.55, 0.1);
+const REMOVED_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+const CHANGED_COLOR: Color = Color::from_rgb(0.8, 0.45, 0.0);
+
+pub struct JsonDiff {
+    left_content: text_editor::Content,
+    right_content: text_editor::Content,
+    left_error: Option<String>,
+    right_error: Option<String>,
+    save_status: Option<String>,
+    ignore_array_order: bool,
+    changes: Vec<Change>,
+    patch_content: text_editor::Content,
+    theme: highlighter::Theme,
+    tool_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    LeftActionPerformed(text_editor::Action),
+    RightActionPerformed(text_editor::Action),
+    PatchActionPerformed(text_editor::Action),
+    IgnoreArrayOrderToggled(bool),
+    SaveToFileClicked,
+    FileSaved(Option<Result<String, String>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone)]
+struct Change {
+    kind: ChangeKind,
+    path: Vec<String>,
+    old: Option<Value>,
+    new: Option<Value>,
+}
+
+impl Default for JsonDiff {
+    fn default() -> Self {
+        JsonDiff::new()
+    }
+}
+
+impl JsonDiff {
+    pub fn new() -> Self {
+        Self {
+            left_content: text_editor::Content::with_text("{}"),
+            right_content: text_editor::Content::with_text("{}"),
+            left_error: None,
+            right_error: None,
+            save_status: None,
+            ignore_array_order: false,
+            changes: vec![],
+            patch_content: text_editor::Content::with_text("[]"),
+            theme: highlighter::Theme::InspiredGitHub,
+            tool_name: NAME.to_string(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let count = |kind: ChangeKind| self.changes.iter().filter(|c| c.kind == kind).count();
+        let controls = row![
+            checkbox("Ignore array order", self.ignore_array_order)
+                .on_toggle(Message::IgnoreArrayOrderToggled),
+            horizontal_space(),
+            text(format!("{} added", count(ChangeKind::Added))).color(ADDED_COLOR),
+            text(format!("{} removed", count(ChangeKind::Removed))).color(REMOVED_COLOR),
+            text(format!("{} changed", count(ChangeKind::Changed))).color(CHANGED_COLOR),
+        ]
+        .spacing(10)
+        .padding(20);
+
+        let documents = row![
+            self.document_panel(
+                "Left",
+                &self.left_content,
+                &self.left_error,
+                Message::LeftActionPerformed
+            ),
+            self.document_panel(
+                "Right",
+                &self.right_content,
+                &self.right_error,
+                Message::RightActionPerformed
+            ),
+        ]
+        .height(FillPortion(1));
+
+        let header = row![
+            text("Path").width(FillPortion(2)),
+            text("Left").width(FillPortion(3)),
+            text("Right").width(FillPortion(3)),
+        ]
+        .spacing(10);
+
+        let rows = self
+            .changes
+            .iter()
+            .fold(column![].spacing(5), |rows, change| {
+                let color = match change.kind {
+                    ChangeKind::Added => ADDED_COLOR,
+                    ChangeKind::Removed => REMOVED_COLOR,
+                    ChangeKind::Changed => CHANGED_COLOR,
+                };
+                let value = |value: &Option<Value>| {
+                    value.as_ref().map(Value::to_string).unwrap_or_default()
+                };
+                rows.push(
+                    row![
+                        text(to_pointer(&change.path))
+                            .color(color)
+                            .width(FillPortion(2)),
+                        text(value(&change.old)).color(color).width(FillPortion(3)),
+                        text(value(&change.new)).color(color).width(FillPortion(3)),
+                    ]
+                    .spacing(10),
+                )
+            });
+
+        let differences = column![
+            row![text("Differences"), horizontal_space()].height(50),
+            header,
+            scrollable(rows).height(Fill),
+        ]
+        .padding(10)
+        .width(FillPortion(3));
+
+        let patch = column![
+            row![
+                text("JSON Patch (RFC 6902)"),
+                horizontal_space(),
+                text(self.save_status.as_deref().unwrap_or_default()),
+                button("Save to file").on_press_maybe(
+                    match self.left_error.is_none() && self.right_error.is_none() {
+                        true => Some(Message::SaveToFileClicked),
+                        false => None,
+                    }
+                )
+            ]
+            .spacing(10)
+            .height(50),
+            container(scrollable(
+                iced::widget::text_editor(&self.patch_content)
+                    .on_action(Message::PatchActionPerformed)
+                    .highlight("js", self.theme),
+            ))
+            .height(Fill),
+        ]
+        .padding(10)
+        .width(FillPortion(2));
+
+        let results = row![differences, patch].height(FillPortion(1));
+
+        column![controls, documents, results].padding(10).into()
+    }
+
+    fn document_panel<'a>(
+        &'a self,
+        label: &'a str,
+        content: &'a text_editor::Content,
+        error: &'a Option<String>,
+        on_action: fn(text_editor::Action) -> Message,
+    ) -> Element<'a, Message> {
+        let mut panel = column![
+            row![text(label), horizontal_space()].height(30),
+            container(scrollable(
+                iced::widget::text_editor(content)
+                    .on_action(on_action)
+                    .highlight("js", self.theme),
+            ))
+            .height(Fill),
+        ]
+        .padding(10);
+        if let Some(v) = error {
+            panel = panel.push(row![text(v.to_owned()), horizontal_space()]);
+        }
+        panel.into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::LeftActionPerformed(action) => {
+                let old_text = self.left_content.text();
+                self.left_content.perform(action);
+                if old_text != self.left_content.text() {
+                    self.compare();
+                }
+                Task::none()
+            }
+            Message::RightActionPerformed(action) => {
+                let old_text = self.right_content.text();
+                self.right_content.perform(action);
+                if old_text != self.right_content.text() {
+                    self.compare();
+                }
+                Task::none()
+            }
+            Message::PatchActionPerformed(action) => {
+                match action {
+                    Action::SelectAll | Action::SelectLine | Action::SelectWord => {
+                        self.patch_content.perform(action);
+                    }
+                    _ => (),
+                };
+                Task::none()
+            }
+            Message::IgnoreArrayOrderToggled(value) => {
+                self.ignore_array_order = value;
+                self.compare();
+                Task::none()
+            }
+            Message::SaveToFileClicked => {
+                Task::perform(save_to_file(self.patch_content.text()), Message::FileSaved)
+            }
+            Message::FileSaved(result) => {
+                self.save_status = result.map(|result| match result {
+                    Ok(path) => format!("Saved to {}", path),
+                    Err(e) => format!("Could not save the patch: {}", e),
+                });
+                Task::none()
+            }
+        }
+    }
+
+    fn compare(&mut self) {
+        let left = serde_json::from_str::<Value>(&self.left_content.text());
+        let right = serde_json::from_str::<Value>(&self.right_content.text());
+        self.left_error = left.as_ref().err().map(|e| e.to_string());
+        self.right_error = right.as_ref().err().map(|e| e.to_string());
+
+        if let (Ok(left), Ok(right)) = (left, right) {
+            self.changes = vec![];
+            diff(
+                &left,
+                &right,
+                &mut vec![],
+                self.ignore_array_order,
+                &mut self.changes,
+            );
+            let patch = to_patch(&self.changes);
+            self.patch_content = text_editor::Content::with_text(
+                &serde_json::to_string_pretty(&patch).unwrap_or_default(),
+            );
+        }
+    }
+}
+
+fn diff(
+    left: &Value,
+    right: &Value,
+    path: &mut Vec<String>,
+    ignore_array_order: bool,
+    changes: &mut Vec<Change>,
+) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            for (key, value) in left {
+                path.push(key.clone());
+                match right.get(key) {
+                    Some(other) => diff(value, other, path, ignore_array_order, changes),
+                    None => changes.push(Change {
+                        kind: ChangeKind::Removed,
+                        path: path.clone(),
+                        old: Some(value.clone()),
+                        new: None,
+                    }),
+                }
+                path.pop();
+            }
+            for (key, value) in right.iter().filter(|(key, _)| !left.contains_key(*key)) {
+                path.push(key.clone());
+                changes.push(Change {
+                    kind: ChangeKind::Added,
+                    path: path.clone(),
+                    old: None,
+                    new: Some(value.clone()),
+                });
+                path.pop();
+            }
+        }
+        (Value::Array(left), Value::Array(right)) if ignore_array_order => {
+            let mut matched = vec![false; right.len()];
+            let mut removed = vec![];
+            for (i, value) in left.iter().enumerate() {
+                match (0..right.len())
+                    .find(|j| !matched[*j] && equivalent(value, &right[*j], ignore_array_order))
+                {
+                    Some(j) => matched[j] = true,
+                    None => removed.push(i),
+                }
+            }
+            for i in removed.into_iter().rev() {
+                path.push(i.to_string());
+                changes.push(Change {
+                    kind: ChangeKind::Removed,
+                    path: path.clone(),
+                    old: Some(left[i].clone()),
+                    new: None,
+                });
+                path.pop();
+            }
+            for (j, _) in matched.iter().enumerate().filter(|(_, matched)| !**matched) {
+                path.push("-".to_string());
+                changes.push(Change {
+                    kind: ChangeKind::Added,
+                    path: path.clone(),
+                    old: None,
+                    new: Some(right[j].clone()),
+                });
+                path.pop();
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for (i, (l, r)) in left.iter().zip(right).enumerate() {
+                path.push(i.to_string());
+                diff(l, r, path, ignore_array_order, changes);
+                path.pop();
+            }
+            for i in (right.len()..left.len()).rev() {
+                path.push(i.to_string());
+                changes.push(Change {
+                    kind: ChangeKind::Removed,
+                    path: path.clone(),
+                    old: Some(left[i].clone()),
+                    new: None,
+                });
+                path.pop();
+            }
+            for (i, value) in right.iter().enumerate().skip(left.len()) {
+                path.push(i.to_string());
+                changes.push(Change {
+                    kind: ChangeKind::Added,
+                    path: path.clone(),
+                    old: None,
+                    new: Some(value.clone()),
+                });
+                path.pop();
+            }
+        }
+        _ => {
+            if !equivalent(left, right, ignore_array_order) {
+                changes.push(Change {
+                    kind: ChangeKind::Changed,
+                    path: path.clone(),
+                    old: Some(left.clone()),
+                    new: Some(right.clone()),
+                });
+            }
+        }
+    }
+}
+
+fn equivalent(left: &Value, right: &Value, ignore_array_order: bool) -> bool {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            left.len() == right.len()
+                && left.iter().all(|(key, value)| {
+                    right
+                        .get(key)
+                        .is_some_and(|other| equivalent(value, other, ignore_array_order))
+                })
+        }
+        (Value::Array(left), Value::Array(right)) if ignore_array_order => {
+            let mut matched = vec![false; right.len()];
+            left.len() == right.len()
+                && left.iter().all(|value| {
+                    match (0..right.len())
+                        .find(|j| !matched[*j] && equivalent(value, &right[*j], true))
+                    {
+                        Some(j) => {
+                            matched[j] = true;
+                            true
+                        }
+                        None => false,
+                    }
+                })
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(l, r)| equivalent(l, r, ignore_array_order))
+        }
+        (Value::Number(left), Value::Number(right)) => left.as_f64() == right.as_f64(),
+        _ => left == right,
+    }
+}
+
+fn to_pointer(path: &[String]) -> String {
+    if path.is_empty() {
+        return String::new();
+    }
+    path.iter()
+        .map(|token| format!("/{}", token.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+fn to_patch(changes: &[Change]) -> Value {
+    Value::Array(
+        changes
+            .iter()
+            .map(|change| {
+                let path = to_pointer(&change.path);
+                match change.kind {
+                    ChangeKind::Added => json!({"op": "add", "path": path, "value": change.new}),
+                    ChangeKind::Removed => json!({"op": "remove", "path": path}),
+                    ChangeKind::Changed => {
+                        json!({"op": "replace", "path": path, "value": change.new})
+                    }
+                }
+            })
+            .collect(),
+    )
+}
+
+async fn save_to_file(patch: String) -> Option<Result<String, String>> {
+    let file = AsyncFileDialog::new()
+        .set_directory("/")
+        .save_file()
+        .await?;
+    Some(
+        fs::write(file.path(), patch)
+            .map(|_| file.path().display().to_string())
+            .map_err(|e| e.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(left: &Value, right: &Value, ignore_array_order: bool) -> Vec<Change> {
+        let mut changes = vec![];
+        diff(left, right, &mut vec![], ignore_array_order, &mut changes);
+        changes
+    }
+
+    fn patch(left: &Value, right: &Value, ignore_array_order: bool) -> Value {
+        to_patch(&changes(left, right, ignore_array_order))
+    }
+
+    /// A minimal RFC 6902 applier for the operations `to_patch` produces.
+    fn apply(document: &Value, patch: &Value) -> Value {
+        let mut document = document.clone();
+        for operation in patch.as_array().unwrap() {
+            let path = operation["path"].as_str().unwrap();
+            let (parent, last) = path.rsplit_once('/').unwrap();
+            let last = last.replace("~1", "/").replace("~0", "~");
+            let target = document.pointer_mut(parent).unwrap();
+            match (operation["op"].as_str().unwrap(), target) {
+                ("add", Value::Array(items)) if last == "-" => {
+                    items.push(operation["value"].clone())
+                }
+                ("add", Value::Array(items)) => {
+                    items.insert(last.parse().unwrap(), operation["value"].clone())
+                }
+                ("add" | "replace", Value::Object(map)) => {
+                    map.insert(last, operation["value"].clone());
+                }
+                ("replace", Value::Array(items)) => {
+                    items[last.parse::<usize>().unwrap()] = operation["value"].clone()
+                }
+                ("remove", Value::Array(items)) => {
+                    items.remove(last.parse().unwrap());
+                }
+                ("remove", Value::Object(map)) => {
+                    map.shift_remove(&last).unwrap();
+                }
+                (op, target) => panic!("cannot apply {} to {}", op, target),
+            }
+        }
+        document
+    }
+
+    #[test]
+    fn pointer_tokens_are_escaped() {
+        let left = json!({"a/b": 1, "m~n": 2, "~/": {"x": 3}});
+        let right = json!({"a/b": 10, "~/": {"x": 30}});
+        assert_eq!(
+            patch(&left, &right, false),
+            json!([
+                {"op": "replace", "path": "/a~1b", "value": 10},
+                {"op": "remove", "path": "/m~0n"},
+                {"op": "replace", "path": "/~0~1/x", "value": 30},
+            ])
+        );
+        assert_eq!(apply(&left, &patch(&left, &right, false)), right);
+    }
+
+    #[test]
+    fn shrinking_arrays_remove_from_the_end() {
+        let left = json!({"items": [1, 2, 3, 4, 5]});
+        let right = json!({"items": [1, 9]});
+        assert_eq!(
+            patch(&left, &right, false),
+            json!([
+                {"op": "replace", "path": "/items/1", "value": 9},
+                {"op": "remove", "path": "/items/4"},
+                {"op": "remove", "path": "/items/3"},
+                {"op": "remove", "path": "/items/2"},
+            ])
+        );
+        assert_eq!(apply(&left, &patch(&left, &right, false)), right);
+    }
+
+    #[test]
+    fn growing_arrays_add_in_order() {
+        let left = json!([{"id": 1}]);
+        let right = json!([{"id": 2}, {"id": 3}, 4]);
+        assert_eq!(
+            patch(&left, &right, false),
+            json!([
+                {"op": "replace", "path": "/0/id", "value": 2},
+                {"op": "add", "path": "/1", "value": {"id": 3}},
+                {"op": "add", "path": "/2", "value": 4},
+            ])
+        );
+        assert_eq!(apply(&left, &patch(&left, &right, false)), right);
+    }
+
+    #[test]
+    fn ignoring_array_order_matches_duplicates_once() {
+        let left = json!({"tags": ["a", "b", "a", "c", "a"]});
+        let right = json!({"tags": ["c", "a", "b", "d"]});
+        assert!(changes(&left, &left.clone(), true).is_empty());
+        assert!(changes(&json!([1, 2, 2]), &json!([2, 1, 2]), true).is_empty());
+        assert_eq!(
+            patch(&left, &right, true),
+            json!([
+                {"op": "remove", "path": "/tags/4"},
+                {"op": "remove", "path": "/tags/2"},
+                {"op": "add", "path": "/tags/-", "value": "d"},
+            ])
+        );
+        let patched = apply(&left, &patch(&left, &right, true));
+        assert!(equivalent(&patched, &right, true));
+        assert!(!equivalent(&json!([1, 2, 2]), &json!([1, 1, 2]), true));
+    }
+
+    #[test]
+    fn patches_turn_the_left_document_into_the_right_one() {
+        let left = json!({
+            "name": "devtools",
+            "version": 1,
+            "tags": ["json", "diff", "tool"],
+            "nested": {"keep": true, "drop": null, "list": [{"a": 1}, {"b": 2}]},
+            "type": "object",
+        });
+        let right = json!({
+            "name": "devtools",
+            "version": 2.5,
+            "tags": ["json"],
+            "nested": {"keep": true, "list": [{"a": 1, "c": 3}], "new": [1, 2]},
+            "type": ["object", "null"],
+            "added": {"x/y": "z"},
+        });
+        for ignore_array_order in [false, true] {
+            let patched = apply(&left, &patch(&left, &right, ignore_array_order));
+            assert!(equivalent(&patched, &right, ignore_array_order));
+            assert_eq!(patched, right);
+        }
+        assert_eq!(patch(&left, &left, false), json!([]));
+        assert_eq!(
+            patch(&json!(1), &json!(1.0), false),
+            json!([]),
+            "numbers compare by value"
+        );
+        assert_eq!(
+            patch(&json!(1), &json!("1"), false),
+            json!([{"op": "replace", "path": "", "value": "1"}])
+        );
+    }
+}
@@ -0,0 +1,611 @@
+use chrono::Utc;
+use iced::widget::text::Wrapping;
+use iced::widget::{
+    button, column, container, horizontal_space, pick_list, row, scrollable, text, text_editor,
+    text_input, Column,
+};
+use iced::{clipboard, Alignment, Color, Element, Font, Length, Task};
+use serde_json::{json, Value};
+
+use crate::tools::hmac_generator::mac::KeyFormat;
+use jws::{Algorithm, PrivateKey, PublicKey};
+use token::{Status, Token};
+
+mod jws;
+mod token;
+
+pub const NAME: &str = "JWT Debugger";
+
+const VALID_COLOR: Color = Color::from_rgb(0.1, 0.55, 0.1);
+const WARNING_COLOR: Color = Color::from_rgb(0.8, 0.45, 0.0);
+const ERROR_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Decode,
+    Encode,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 2] = [Mode::Decode, Mode::Encode];
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Mode::Decode => "Decode and verify",
+                Mode::Encode => "Encode and sign",
+            }
+        )
+    }
+}
+
+/// The algorithm a token has to be signed with. Trusting the header lets whoever made the token
+/// pick how it's checked, so a known algorithm can be pinned instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExpectedAlgorithm {
+    #[default]
+    Any,
+    Pinned(Algorithm),
+}
+
+impl ExpectedAlgorithm {
+    pub const ALL: [ExpectedAlgorithm; 14] = [
+        ExpectedAlgorithm::Any,
+        ExpectedAlgorithm::Pinned(Algorithm::Hs256),
+        ExpectedAlgorithm::Pinned(Algorithm::Hs384),
+        ExpectedAlgorithm::Pinned(Algorithm::Hs512),
+        ExpectedAlgorithm::Pinned(Algorithm::Rs256),
+        ExpectedAlgorithm::Pinned(Algorithm::Rs384),
+        ExpectedAlgorithm::Pinned(Algorithm::Rs512),
+        ExpectedAlgorithm::Pinned(Algorithm::Ps256),
+        ExpectedAlgorithm::Pinned(Algorithm::Ps384),
+        ExpectedAlgorithm::Pinned(Algorithm::Ps512),
+        ExpectedAlgorithm::Pinned(Algorithm::Es256),
+        ExpectedAlgorithm::Pinned(Algorithm::Es384),
+        ExpectedAlgorithm::Pinned(Algorithm::Es512),
+        ExpectedAlgorithm::Pinned(Algorithm::EdDsa),
+    ];
+}
+
+impl std::fmt::Display for ExpectedAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedAlgorithm::Any => write!(f, "Any (from the header)"),
+            ExpectedAlgorithm::Pinned(algorithm) => write!(f, "{}", algorithm),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ModeSelected(Mode),
+    TokenChanged(text_editor::Action),
+    HeaderActionPerformed(text_editor::Action),
+    PayloadActionPerformed(text_editor::Action),
+    SecretChanged(String),
+    SecretFormatSelected(KeyFormat),
+    PublicKeyChanged(text_editor::Action),
+    ExpectedAlgorithmSelected(ExpectedAlgorithm),
+    AlgorithmSelected(Algorithm),
+    HeaderInputChanged(text_editor::Action),
+    PayloadInputChanged(text_editor::Action),
+    PrivateKeyChanged(text_editor::Action),
+    SignClicked,
+    OutputActionPerformed(text_editor::Action),
+    CopyClicked,
+    DecodeSignedClicked,
+}
+
+pub struct JwtDebugger {
+    tool_name: String,
+    mode: Option<Mode>,
+    secret: String,
+    secret_format: Option<KeyFormat>,
+    // Decoding
+    token: text_editor::Content,
+    decoded: Option<Result<Token, String>>,
+    header: text_editor::Content,
+    payload: text_editor::Content,
+    public_key: text_editor::Content,
+    expected_algorithm: Option<ExpectedAlgorithm>,
+    verification: Option<Result<bool, String>>,
+    // Encoding
+    algorithm: Option<Algorithm>,
+    header_input: text_editor::Content,
+    payload_input: text_editor::Content,
+    private_key: text_editor::Content,
+    signed: Option<String>,
+    output: text_editor::Content,
+    sign_error: Option<String>,
+}
+
+impl Default for JwtDebugger {
+    fn default() -> Self {
+        JwtDebugger::new()
+    }
+}
+
+impl JwtDebugger {
+    pub fn new() -> Self {
+        let algorithm = Algorithm::default();
+        let header = json!({ "alg": algorithm.name(), "typ": "JWT" });
+        let payload = json!({
+            "sub": "1234567890",
+            "name": "John Doe",
+            "iat": Utc::now().timestamp(),
+        });
+        Self {
+            tool_name: NAME.to_string(),
+            mode: Some(Mode::default()),
+            secret: String::new(),
+            secret_format: Some(KeyFormat::default()),
+            token: text_editor::Content::new(),
+            decoded: None,
+            header: text_editor::Content::new(),
+            payload: text_editor::Content::new(),
+            public_key: text_editor::Content::new(),
+            expected_algorithm: Some(ExpectedAlgorithm::default()),
+            verification: None,
+            algorithm: Some(algorithm),
+            header_input: text_editor::Content::with_text(&token::pretty(&header)),
+            payload_input: text_editor::Content::with_text(&token::pretty(&payload)),
+            private_key: text_editor::Content::new(),
+            signed: None,
+            output: text_editor::Content::new(),
+            sign_error: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    fn secret(&self) -> Result<Vec<u8>, String> {
+        if self.secret.is_empty() {
+            return Err(String::from("Enter the secret"));
+        }
+        self.secret_format.unwrap_or_default().decode(&self.secret)
+    }
+
+    fn decode(&mut self) {
+        let token = self.token.text();
+        self.decoded = (!token.trim().is_empty()).then(|| Token::decode(&token));
+        let (header, payload) = match &self.decoded {
+            Some(Ok(token)) => (token::pretty(&token.header), token::pretty(&token.payload)),
+            _ => (String::new(), String::new()),
+        };
+        self.header = text_editor::Content::with_text(&header);
+        self.payload = text_editor::Content::with_text(&payload);
+        self.verify();
+    }
+
+    // Why the token can't be verified with the pinned algorithm, if one is pinned.
+    fn algorithm_mismatch(&self, token: &Token) -> Option<String> {
+        let ExpectedAlgorithm::Pinned(expected) = self.expected_algorithm.unwrap_or_default()
+        else {
+            return None;
+        };
+        match token.algorithm() {
+            Ok(Some(algorithm)) if algorithm == expected => None,
+            Ok(Some(algorithm)) => Some(format!(
+                "The header says {}, but {} is expected: the token is rejected",
+                algorithm, expected
+            )),
+            Ok(None) => Some(format!(
+                "The token is unsigned, but {} is expected: the token is rejected",
+                expected
+            )),
+            Err(_) => None,
+        }
+    }
+
+    fn verify(&mut self) {
+        self.verification = match &self.decoded {
+            Some(Ok(token)) if self.algorithm_mismatch(token).is_some() => None,
+            Some(Ok(token)) => match token.algorithm() {
+                Ok(Some(algorithm)) if algorithm.uses_secret() => {
+                    Some(self.secret().and_then(|secret| {
+                        algorithm.verify_with_secret(
+                            &secret,
+                            token.signing_input.as_bytes(),
+                            &token.signature,
+                        )
+                    }))
+                }
+                Ok(Some(algorithm)) => {
+                    let key = self.public_key.text();
+                    (!key.trim().is_empty()).then(|| {
+                        PublicKey::parse(&key, algorithm, token.kid()).and_then(|key| {
+                            algorithm.verify(&key, token.signing_input.as_bytes(), &token.signature)
+                        })
+                    })
+                }
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            },
+            _ => None,
+        };
+    }
+
+    fn sign(&self) -> Result<String, String> {
+        let algorithm = self.algorithm.unwrap_or_default();
+        let mut header = token::parse_object(&self.header_input.text(), "header")?;
+        header["alg"] = Value::from(algorithm.name());
+        let payload = token::parse_object(&self.payload_input.text(), "payload")?;
+
+        let signing_input = token::signing_input(&header, &payload);
+        let signature = match algorithm.uses_secret() {
+            true => algorithm.sign_with_secret(&self.secret()?, signing_input.as_bytes())?,
+            false => {
+                let key = PrivateKey::parse(&self.private_key.text(), algorithm)?;
+                algorithm.sign(&key, signing_input.as_bytes())?
+            }
+        };
+        Ok(token::with_signature(&signing_input, &signature))
+    }
+
+    // Keeps the `alg` in the header being edited in step with the selected algorithm.
+    fn update_header_algorithm(&mut self) {
+        let algorithm = self.algorithm.unwrap_or_default();
+        if let Ok(mut header) = token::parse_object(&self.header_input.text(), "header") {
+            header["alg"] = Value::from(algorithm.name());
+            self.header_input = text_editor::Content::with_text(&token::pretty(&header));
+        }
+    }
+
+    fn secret_view(&self) -> Element<'_, Message> {
+        row![
+            "Secret: ",
+            text_input("Shared secret", &self.secret)
+                .on_input(Message::SecretChanged)
+                .font(Font::MONOSPACE),
+            pick_list(
+                &KeyFormat::ALL[..],
+                self.secret_format,
+                Message::SecretFormatSelected
+            ),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn key_view<'a>(
+        &self,
+        label: &'a str,
+        placeholder: &'a str,
+        content: &'a text_editor::Content,
+        on_action: fn(text_editor::Action) -> Message,
+    ) -> Element<'a, Message> {
+        column![
+            label,
+            text_editor(content)
+                .placeholder(placeholder)
+                .on_action(on_action)
+                .font(Font::MONOSPACE)
+                .height(150),
+        ]
+        .spacing(5)
+        .into()
+    }
+
+    fn claims_view(&self, token: &Token) -> Element<'_, Message> {
+        let now = Utc::now();
+        let mut claims = Column::new().spacing(5);
+        for (claim, label) in token::TIME_CLAIMS {
+            let Some(time) = token::claim_time(&token.payload, claim) else {
+                continue;
+            };
+            let value = match time {
+                Ok(time) => text(token::format_time(time, now)),
+                Err(e) => text(e).color(ERROR_COLOR),
+            };
+            claims = claims
+                .push(row![text(format!("{} ({})", label, claim)).width(150), value].spacing(10));
+        }
+
+        let expiry = token::claim_time(&token.payload, "exp").and_then(Result::ok);
+        let not_before = token::claim_time(&token.payload, "nbf").and_then(Result::ok);
+        let status = match (token::status(&token.payload, now), expiry, not_before) {
+            (Status::Expired, Some(exp), _) => {
+                text(format!("Expired {}", token::format_time(exp, now))).color(ERROR_COLOR)
+            }
+            (Status::NotYetValid, _, Some(nbf)) => text(format!(
+                "Not valid yet: valid from {}",
+                token::format_time(nbf, now)
+            ))
+            .color(WARNING_COLOR),
+            (_, Some(exp), _) => {
+                text(format!("Valid until {}", token::format_time(exp, now))).color(VALID_COLOR)
+            }
+            _ => text("The token never expires: it has no 'exp' claim").color(WARNING_COLOR),
+        };
+        claims.push(status).into()
+    }
+
+    fn signature_view(&self, token: &Token) -> Element<'_, Message> {
+        let expected = row![
+            "Expected algorithm: ",
+            pick_list(
+                &ExpectedAlgorithm::ALL[..],
+                self.expected_algorithm,
+                Message::ExpectedAlgorithmSelected
+            ),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+        if let Some(mismatch) = self.algorithm_mismatch(token) {
+            return column![expected, text(mismatch).color(WARNING_COLOR)]
+                .spacing(10)
+                .into();
+        }
+        let algorithm = match token.algorithm() {
+            Ok(Some(algorithm)) => algorithm,
+            Ok(None) => {
+                return column![
+                    expected,
+                    text("The token is unsigned: its algorithm is 'none'").color(WARNING_COLOR),
+                ]
+                .spacing(10)
+                .into()
+            }
+            Err(e) => {
+                return column![expected, text(e).color(ERROR_COLOR)]
+                    .spacing(10)
+                    .into()
+            }
+        };
+
+        let key = match algorithm.uses_secret() {
+            true => self.secret_view(),
+            false => self.key_view(
+                "Public key",
+                "PEM public key, JWK or JWK Set",
+                &self.public_key,
+                Message::PublicKeyChanged,
+            ),
+        };
+        let result = match &self.verification {
+            Some(Ok(true)) => text("Signature verified").color(VALID_COLOR),
+            Some(Ok(false)) => text("Invalid signature").color(ERROR_COLOR),
+            Some(Err(e)) => text(e.as_str()).color(ERROR_COLOR),
+            None => text("Enter the key to verify the signature"),
+        };
+        column![
+            expected,
+            text(format!("Signature algorithm: {}", algorithm)),
+            key,
+            result,
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn decode_view(&self) -> Element<'_, Message> {
+        let input = column![
+            "Token",
+            text_editor(&self.token)
+                .placeholder("Paste a JWT")
+                .on_action(Message::TokenChanged)
+                .font(Font::MONOSPACE)
+                .wrapping(Wrapping::Glyph)
+                .height(100),
+        ]
+        .spacing(5);
+
+        let token = match &self.decoded {
+            Some(Ok(token)) => token,
+            Some(Err(e)) => {
+                return column![input, text(e.as_str()).color(ERROR_COLOR)]
+                    .spacing(10)
+                    .into()
+            }
+            None => return input.into(),
+        };
+
+        let parts = row![
+            column![
+                "Header",
+                text_editor(&self.header)
+                    .on_action(Message::HeaderActionPerformed)
+                    .font(Font::MONOSPACE)
+                    .height(200),
+            ]
+            .spacing(5),
+            column![
+                "Payload",
+                text_editor(&self.payload)
+                    .on_action(Message::PayloadActionPerformed)
+                    .font(Font::MONOSPACE)
+                    .height(200),
+            ]
+            .spacing(5),
+        ]
+        .spacing(10);
+
+        column![
+            input,
+            parts,
+            self.claims_view(token),
+            self.signature_view(token),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn encode_view(&self) -> Element<'_, Message> {
+        let algorithm = self.algorithm.unwrap_or_default();
+        let parts = row![
+            column![
+                "Header",
+                text_editor(&self.header_input)
+                    .on_action(Message::HeaderInputChanged)
+                    .font(Font::MONOSPACE)
+                    .height(200),
+            ]
+            .spacing(5),
+            column![
+                "Payload",
+                text_editor(&self.payload_input)
+                    .on_action(Message::PayloadInputChanged)
+                    .font(Font::MONOSPACE)
+                    .height(200),
+            ]
+            .spacing(5),
+        ]
+        .spacing(10);
+
+        let key = match algorithm.uses_secret() {
+            true => self.secret_view(),
+            false => self.key_view(
+                "Private key",
+                "PEM private key (PKCS#8, PKCS#1 or SEC1) or private JWK",
+                &self.private_key,
+                Message::PrivateKeyChanged,
+            ),
+        };
+
+        let mut content = column![
+            row![
+                "Algorithm: ",
+                pick_list(
+                    &Algorithm::ALL[..],
+                    self.algorithm,
+                    Message::AlgorithmSelected
+                ),
+                horizontal_space(),
+                button("Sign").on_press(Message::SignClicked),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            parts,
+            key,
+        ]
+        .spacing(10);
+        if let Some(e) = &self.sign_error {
+            content = content.push(text(e.as_str()).color(ERROR_COLOR));
+        }
+        if self.signed.is_some() {
+            content = content.push(
+                column![
+                    row![
+                        "Token",
+                        horizontal_space(),
+                        button("Copy").on_press(Message::CopyClicked),
+                        button("Decode").on_press(Message::DecodeSignedClicked),
+                    ]
+                    .spacing(10)
+                    .align_y(Alignment::Center),
+                    text_editor(&self.output)
+                        .on_action(Message::OutputActionPerformed)
+                        .font(Font::MONOSPACE)
+                        .wrapping(Wrapping::Glyph)
+                        .height(100),
+                ]
+                .spacing(5),
+            );
+        }
+        content.into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let content = match self.mode.unwrap_or_default() {
+            Mode::Decode => self.decode_view(),
+            Mode::Encode => self.encode_view(),
+        };
+        container(scrollable(
+            column![
+                row![pick_list(&Mode::ALL[..], self.mode, Message::ModeSelected)],
+                content,
+            ]
+            .spacing(10)
+            .padding(10),
+        ))
+        .padding(10)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::ModeSelected(mode) => self.mode = Some(mode),
+            Message::TokenChanged(action) => {
+                let is_edit = action.is_edit();
+                self.token.perform(action);
+                if is_edit {
+                    self.decode();
+                }
+            }
+            Message::HeaderActionPerformed(action) => {
+                if !action.is_edit() {
+                    self.header.perform(action);
+                }
+            }
+            Message::PayloadActionPerformed(action) => {
+                if !action.is_edit() {
+                    self.payload.perform(action);
+                }
+            }
+            Message::SecretChanged(secret) => {
+                self.secret = secret;
+                self.verify();
+            }
+            Message::SecretFormatSelected(secret_format) => {
+                self.secret_format = Some(secret_format);
+                self.verify();
+            }
+            Message::PublicKeyChanged(action) => {
+                let is_edit = action.is_edit();
+                self.public_key.perform(action);
+                if is_edit {
+                    self.verify();
+                }
+            }
+            Message::ExpectedAlgorithmSelected(expected_algorithm) => {
+                self.expected_algorithm = Some(expected_algorithm);
+                self.verify();
+            }
+            Message::AlgorithmSelected(algorithm) => {
+                self.algorithm = Some(algorithm);
+                self.update_header_algorithm();
+            }
+            Message::HeaderInputChanged(action) => self.header_input.perform(action),
+            Message::PayloadInputChanged(action) => self.payload_input.perform(action),
+            Message::PrivateKeyChanged(action) => self.private_key.perform(action),
+            Message::SignClicked => match self.sign() {
+                Ok(signed) => {
+                    self.output = text_editor::Content::with_text(&signed);
+                    self.signed = Some(signed);
+                    self.sign_error = None;
+                }
+                Err(e) => {
+                    self.signed = None;
+                    self.sign_error = Some(e);
+                }
+            },
+            Message::OutputActionPerformed(action) => {
+                if !action.is_edit() {
+                    self.output.perform(action);
+                }
+            }
+            Message::CopyClicked => {
+                if let Some(signed) = &self.signed {
+                    return clipboard::write(signed.clone());
+                }
+            }
+            Message::DecodeSignedClicked => {
+                if let Some(signed) = &self.signed {
+                    self.token = text_editor::Content::with_text(signed);
+                    self.mode = Some(Mode::Decode);
+                    self.decode();
+                }
+            }
+        }
+        Task::none()
+    }
+}
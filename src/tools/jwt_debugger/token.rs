@@ -0,0 +1,180 @@
+use chrono::{DateTime, Local, Utc};
+use serde_json::Value;
+
+use super::jws::Algorithm;
+use crate::tools::base64_converter::codec::{self, Alphabet, LineWrap, Padding};
+use crate::tools::json_beautifier::format;
+
+// The registered claims holding times, as seconds since the Unix epoch.
+pub const TIME_CLAIMS: [(&str, &str); 3] = [
+    ("iat", "Issued at"),
+    ("nbf", "Not before"),
+    ("exp", "Expires"),
+];
+
+/// A signed token split into its parts.
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub header: Value,
+    pub payload: Value,
+    /// The encoded header and payload joined by a dot: the bytes the signature covers.
+    pub signing_input: String,
+    pub signature: Vec<u8>,
+}
+
+impl Token {
+    pub fn decode(token: &str) -> Result<Token, String> {
+        let token = token.trim();
+        let token = match token.get(..7) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("bearer ") => token[7..].trim_start(),
+            _ => token,
+        };
+        let parts: Vec<&str> = token.split('.').collect();
+        let [header, payload, signature] = parts[..] else {
+            return Err(match parts.len() {
+                5 => String::from("Encrypted tokens (JWE) aren't supported"),
+                n => format!(
+                    "A JWT has three parts separated by dots, but this one has {}",
+                    n
+                ),
+            });
+        };
+
+        let header_json = decode_json(header, "header")?;
+        if !header_json.is_object() {
+            return Err(String::from("The header is not a JSON object"));
+        }
+        Ok(Token {
+            header: header_json,
+            payload: decode_json(payload, "payload")?,
+            signing_input: format!("{}.{}", header, payload),
+            signature: decode_segment(signature, "signature")?,
+        })
+    }
+
+    /// The algorithm named in the header. Unsigned tokens, with `alg` set to `none`, have
+    /// none.
+    pub fn algorithm(&self) -> Result<Option<Algorithm>, String> {
+        match self.header["alg"].as_str() {
+            Some("none") => Ok(None),
+            Some(name) => Algorithm::from_name(name)
+                .map(Some)
+                .ok_or_else(|| format!("Unsupported algorithm '{}'", name)),
+            None => Err(String::from("The header has no 'alg'")),
+        }
+    }
+
+    pub fn kid(&self) -> Option<&str> {
+        self.header["kid"].as_str()
+    }
+}
+
+fn decode_segment(segment: &str, name: &str) -> Result<Vec<u8>, String> {
+    codec::decode(segment, Alphabet::UrlSafe, Padding::Optional)
+        .map_err(|e| format!("Invalid Base64url in the {}: {}", name, e.message))
+}
+
+fn decode_json(segment: &str, name: &str) -> Result<Value, String> {
+    let bytes = decode_segment(segment, name)?;
+    let text = String::from_utf8(bytes).map_err(|_| format!("The {} is not valid UTF-8", name))?;
+    format::parse(&text).map_err(|e| format!("The {} is not valid JSON: {}", name, e))
+}
+
+fn encode_segment(bytes: &[u8]) -> String {
+    codec::encode(
+        bytes,
+        Alphabet::UrlSafe,
+        Padding::Omitted,
+        LineWrap::Unwrapped,
+    )
+}
+
+/// Encodes the header and payload as compact JSON, returning the signing input.
+pub fn signing_input(header: &Value, payload: &Value) -> String {
+    format!(
+        "{}.{}",
+        encode_segment(header.to_string().as_bytes()),
+        encode_segment(payload.to_string().as_bytes())
+    )
+}
+
+pub fn with_signature(signing_input: &str, signature: &[u8]) -> String {
+    format!("{}.{}", signing_input, encode_segment(signature))
+}
+
+pub fn pretty(value: &Value) -> String {
+    format::pretty(value, 2).unwrap_or_default()
+}
+
+/// Reads a time claim, which may be fractional.
+pub fn claim_time(payload: &Value, claim: &str) -> Option<Result<DateTime<Utc>, String>> {
+    let value = payload.get(claim)?;
+    Some(
+        value
+            .as_f64()
+            .and_then(|seconds| DateTime::from_timestamp(seconds.floor() as i64, 0))
+            .ok_or_else(|| format!("'{}' is not a timestamp: {}", claim, value)),
+    )
+}
+
+pub fn format_time(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    format!(
+        "{} ({})",
+        time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S %:z"),
+        relative_time(time, now)
+    )
+}
+
+fn relative_time(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    const UNITS: [(i64, &str); 5] = [
+        (365 * 24 * 3600, "year"),
+        (24 * 3600, "day"),
+        (3600, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ];
+    let seconds = (time - now).num_seconds();
+    if seconds == 0 {
+        return String::from("now");
+    }
+    let (size, unit) = UNITS
+        .into_iter()
+        .find(|(size, _)| seconds.abs() >= *size)
+        .unwrap_or(UNITS[4]);
+    let amount = seconds.abs() / size;
+    let amount = match amount {
+        1 => format!("1 {}", unit),
+        _ => format!("{} {}s", amount, unit),
+    };
+    match seconds > 0 {
+        true => format!("in {}", amount),
+        false => format!("{} ago", amount),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Valid,
+    NotYetValid,
+    Expired,
+}
+
+/// Whether the token is valid at `now` according to its `nbf` and `exp` claims.
+pub fn status(payload: &Value, now: DateTime<Utc>) -> Status {
+    let time = |claim| claim_time(payload, claim).and_then(Result::ok);
+    match (time("nbf"), time("exp")) {
+        (_, Some(exp)) if exp <= now => Status::Expired,
+        (Some(nbf), _) if nbf > now => Status::NotYetValid,
+        _ => Status::Valid,
+    }
+}
+
+/// Parses the JSON of a header or payload being edited, which has to be an object.
+pub fn parse_object(text: &str, name: &str) -> Result<Value, String> {
+    let value =
+        format::parse(text).map_err(|e| format!("The {} is not valid JSON: {}", name, e))?;
+    match value.is_object() {
+        true => Ok(value),
+        false => Err(format!("The {} is not a JSON object", name)),
+    }
+}
@@ -0,0 +1,170 @@
+use iced::{
+    widget::{
+        checkbox, column, container, horizontal_space, progress_bar, row, scrollable, text,
+        text_input, Column,
+    },
+    Alignment, Color, Element, Font, Length,
+};
+
+use feedback::Attack;
+use scoring::Analysis;
+
+mod dictionary;
+mod feedback;
+mod keyboard;
+mod matching;
+mod scoring;
+
+pub const NAME: &str = "Password Strength Analyzer";
+
+const WARNING_COLOR: Color = Color::from_rgb(0.8, 0.45, 0.0);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PasswordChanged(String),
+    ShowPasswordToggled(bool),
+}
+
+pub struct PasswordAnalyzer {
+    tool_name: String,
+    password: String,
+    is_password_shown: bool,
+    analysis: Option<Analysis>,
+}
+
+impl Default for PasswordAnalyzer {
+    fn default() -> Self {
+        PasswordAnalyzer::new()
+    }
+}
+
+impl PasswordAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            tool_name: NAME.to_string(),
+            password: String::new(),
+            is_password_shown: false,
+            analysis: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    fn result_view(&self) -> Element<'_, Message> {
+        let Some(analysis) = &self.analysis else {
+            return column![].into();
+        };
+
+        let score = row![
+            text(format!(
+                "Score: {} / 4 ({})",
+                analysis.score,
+                match analysis.score {
+                    0 => "too guessable",
+                    1 => "very guessable",
+                    2 => "somewhat guessable",
+                    3 => "safely unguessable",
+                    _ => "very unguessable",
+                }
+            )),
+            progress_bar(0.0..=4.0, analysis.score as f32)
+                .height(10)
+                .width(200),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        let crack_times = Attack::ALL.iter().map(|attack| {
+            row![
+                text(attack.to_string()).width(500),
+                text(attack.crack_time(analysis.guesses)),
+            ]
+            .spacing(10)
+            .into()
+        });
+
+        let patterns = analysis.sequence.iter().map(|m| {
+            row![
+                text(m.token.as_str()).font(Font::MONOSPACE).width(200),
+                text(m.description()),
+                horizontal_space(),
+                text(format!("10^{:.1} guesses", m.guesses.log10())),
+            ]
+            .spacing(10)
+            .into()
+        });
+
+        let feedback = feedback::feedback(analysis.score, &analysis.sequence);
+        let mut result = column![
+            score,
+            text(format!(
+                "Estimated guesses: 10^{:.1}",
+                analysis.guesses.log10()
+            )),
+            "Time to crack",
+            Column::with_children(crack_times).spacing(5),
+            "Patterns",
+            Column::with_children(patterns).spacing(5),
+        ]
+        .spacing(10);
+        if let Some(warning) = feedback.warning {
+            result = result.push(text(warning).color(WARNING_COLOR));
+        }
+        if !feedback.suggestions.is_empty() {
+            result = result.push("Suggestions").push(
+                Column::with_children(
+                    feedback
+                        .suggestions
+                        .iter()
+                        .map(|suggestion| text(format!("• {}", suggestion)).into()),
+                )
+                .spacing(5),
+            );
+        }
+        if analysis.is_truncated {
+            result = result.push(text(format!(
+                "Only the first {} characters were analyzed",
+                scoring::MAX_LENGTH
+            )));
+        }
+        result.into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let input = column![
+            row![
+                "Password: ",
+                text_input("Type or paste a password", self.password.as_str())
+                    .on_input(Message::PasswordChanged)
+                    .secure(!self.is_password_shown)
+                    .font(Font::MONOSPACE),
+                checkbox("Show", self.is_password_shown).on_toggle(Message::ShowPasswordToggled),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            text("The password is analyzed on this machine and never stored or sent anywhere."),
+        ]
+        .spacing(10);
+
+        container(scrollable(
+            column![input, self.result_view()].spacing(20).padding(10),
+        ))
+        .padding(10)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::PasswordChanged(password) => {
+                self.analysis = (!password.is_empty()).then(|| scoring::analyze(&password));
+                self.password = password;
+            }
+            Message::ShowPasswordToggled(value) => {
+                self.is_password_shown = value;
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::tools::password_generator::wordlist;
+
+// Each list has one lowercase entry per line, most common first.
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+const FIRST_NAMES: &str = include_str!("first_names.txt");
+const SURNAMES: &str = include_str!("surnames.txt");
+
+// The most common English words, most common first. They rank ahead of the diceware list.
+const COMMON_WORDS: &[&str] = &[
+    "the", "of", "and", "to", "in", "is", "you", "that", "it", "he", "was", "for", "on", "are",
+    "as", "with", "his", "they", "at", "be", "this", "have", "from", "or", "one", "had", "by",
+    "word", "but", "not", "what", "all", "were", "we", "when", "your", "can", "said", "there",
+    "use", "an", "each", "which", "she", "do", "how", "their", "if", "will", "up", "other",
+    "about", "out", "many", "then", "them", "these", "so", "some", "her", "would", "make", "like",
+    "him", "into", "time", "has", "look", "two", "more", "write", "go", "see", "number", "no",
+    "way", "could", "people", "my", "than", "first", "water", "been", "call", "who", "oil", "its",
+    "now", "find", "long", "down", "day", "did", "get", "come", "made", "may", "part", "love",
+    "god", "life", "home", "man", "woman", "girl", "boy", "baby", "sun", "moon", "star", "king",
+    "queen", "angel", "devil", "fire", "dog", "cat", "horse", "blue", "red", "black", "white",
+    "green", "money", "music", "happy", "sexy", "cool", "hot", "big", "little",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dictionary {
+    Passwords,
+    EnglishWords,
+    FirstNames,
+    Surnames,
+}
+
+impl Dictionary {
+    pub const ALL: [Dictionary; 4] = [
+        Dictionary::Passwords,
+        Dictionary::EnglishWords,
+        Dictionary::FirstNames,
+        Dictionary::Surnames,
+    ];
+
+    /// The rank of `word`, already lowercase, in the list: 1 for the most common entry.
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        self.ranks().get(word).copied()
+    }
+
+    fn ranks(&self) -> &'static HashMap<&'static str, usize> {
+        static RANKS: OnceLock<[HashMap<&'static str, usize>; 4]> = OnceLock::new();
+        &RANKS.get_or_init(|| {
+            let ranked = |words: &mut dyn Iterator<Item = &'static str>| {
+                let mut ranks = HashMap::new();
+                for word in words {
+                    let rank = ranks.len() + 1;
+                    ranks.entry(word).or_insert(rank);
+                }
+                ranks
+            };
+            let mut english = ranked(&mut COMMON_WORDS.iter().copied());
+            // The diceware list is alphabetical rather than ranked, so each of its words counts
+            // as halfway down the list.
+            let words = wordlist::words();
+            let rank = english.len() + words.len() / 2;
+            for word in words {
+                english.entry(word).or_insert(rank);
+            }
+            [
+                ranked(&mut COMMON_PASSWORDS.lines()),
+                english,
+                ranked(&mut FIRST_NAMES.lines()),
+                ranked(&mut SURNAMES.lines()),
+            ]
+        })[*self as usize]
+    }
+}
+
+impl std::fmt::Display for Dictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Dictionary::Passwords => "common passwords",
+                Dictionary::EnglishWords => "English words",
+                Dictionary::FirstNames => "first names",
+                Dictionary::Surnames => "surnames",
+            }
+        )
+    }
+}
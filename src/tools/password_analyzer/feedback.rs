@@ -0,0 +1,192 @@
+use super::dictionary::Dictionary;
+use super::matching::{Match, Pattern};
+
+/// How fast an attacker can guess, from someone trying a rate-limited login form to someone
+/// with a leaked database of unsalted, fast hashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attack {
+    OnlineThrottled,
+    OnlineUnthrottled,
+    OfflineSlowHash,
+    OfflineFastHash,
+}
+
+impl Attack {
+    pub const ALL: [Attack; 4] = [
+        Attack::OnlineThrottled,
+        Attack::OnlineUnthrottled,
+        Attack::OfflineSlowHash,
+        Attack::OfflineFastHash,
+    ];
+
+    fn guesses_per_second(&self) -> f64 {
+        match self {
+            Attack::OnlineThrottled => 100.0 / 3600.0,
+            Attack::OnlineUnthrottled => 10.0,
+            Attack::OfflineSlowHash => 1e4,
+            Attack::OfflineFastHash => 1e10,
+        }
+    }
+
+    /// How long the attack takes to make `guesses` guesses, in words.
+    pub fn crack_time(&self, guesses: f64) -> String {
+        const MINUTE: f64 = 60.0;
+        const HOUR: f64 = MINUTE * 60.0;
+        const DAY: f64 = HOUR * 24.0;
+        const MONTH: f64 = DAY * 31.0;
+        const YEAR: f64 = MONTH * 12.0;
+        const CENTURY: f64 = YEAR * 100.0;
+
+        let seconds = guesses / self.guesses_per_second();
+        let (amount, unit) = match seconds {
+            s if s < 1.0 => return String::from("less than a second"),
+            s if s < MINUTE => (s, "second"),
+            s if s < HOUR => (s / MINUTE, "minute"),
+            s if s < DAY => (s / HOUR, "hour"),
+            s if s < MONTH => (s / DAY, "day"),
+            s if s < YEAR => (s / MONTH, "month"),
+            s if s < CENTURY => (s / YEAR, "year"),
+            _ => return String::from("centuries"),
+        };
+        let amount = amount.round() as u64;
+        match amount {
+            1 => format!("1 {}", unit),
+            _ => format!("{} {}s", amount, unit),
+        }
+    }
+}
+
+impl std::fmt::Display for Attack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Attack::OnlineThrottled => "Online attack, throttled (100 guesses per hour)",
+                Attack::OnlineUnthrottled => "Online attack, unthrottled (10 guesses per second)",
+                Attack::OfflineSlowHash =>
+                    "Offline attack, slow hash like bcrypt (10,000 guesses per second)",
+                Attack::OfflineFastHash =>
+                    "Offline attack, fast hash like MD5 (10 billion guesses per second)",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Feedback {
+    pub warning: Option<&'static str>,
+    pub suggestions: Vec<&'static str>,
+}
+
+/// Explains what makes a weak password weak, based on its longest pattern. Strong passwords
+/// get no feedback.
+pub fn feedback(score: u8, sequence: &[Match]) -> Feedback {
+    if sequence.is_empty() {
+        return Feedback {
+            warning: None,
+            suggestions: vec![
+                "Use a few words, avoid common phrases",
+                "No need for symbols, digits, or uppercase letters",
+            ],
+        };
+    }
+    if score > 2 {
+        return Feedback::default();
+    }
+
+    let Some(longest) = sequence.iter().max_by_key(|m| m.token.chars().count()) else {
+        return Feedback::default();
+    };
+    let mut feedback = match_feedback(longest, sequence.len() == 1);
+    feedback
+        .suggestions
+        .insert(0, "Add another word or two. Uncommon words are better.");
+    feedback
+}
+
+fn match_feedback(m: &Match, is_sole_match: bool) -> Feedback {
+    match &m.pattern {
+        Pattern::Dictionary {
+            dictionary,
+            rank,
+            reversed,
+            l33t,
+            ..
+        } => {
+            let warning = match dictionary {
+                Dictionary::Passwords if is_sole_match && l33t.is_empty() && !reversed => {
+                    Some(match rank {
+                        1..=10 => "This is a top-10 common password",
+                        11..=100 => "This is a top-100 common password",
+                        _ => "This is a very common password",
+                    })
+                }
+                Dictionary::Passwords if m.guesses.log10() <= 4.0 => {
+                    Some("This is similar to a commonly used password")
+                }
+                Dictionary::Passwords => None,
+                Dictionary::EnglishWords => {
+                    is_sole_match.then_some("A word by itself is easy to guess")
+                }
+                Dictionary::FirstNames | Dictionary::Surnames => Some(match is_sole_match {
+                    true => "Names and surnames by themselves are easy to guess",
+                    false => "Common names and surnames are easy to guess",
+                }),
+            };
+
+            let mut suggestions = Vec::new();
+            let chars: Vec<char> = m.token.chars().collect();
+            let is_capitalized = chars.first().is_some_and(|c| c.is_uppercase())
+                && chars[1..].iter().all(|c| !c.is_uppercase());
+            let is_all_upper =
+                chars.iter().all(|c| !c.is_lowercase()) && chars.iter().any(|c| c.is_uppercase());
+            if is_capitalized {
+                suggestions.push("Capitalization doesn't help very much");
+            } else if is_all_upper {
+                suggestions.push("All-uppercase is almost as easy to guess as all-lowercase");
+            }
+            if *reversed && chars.len() >= 4 {
+                suggestions.push("Reversed words aren't much harder to guess");
+            }
+            if !l33t.is_empty() {
+                suggestions
+                    .push("Predictable substitutions like '@' instead of 'a' don't help very much");
+            }
+            Feedback {
+                warning,
+                suggestions,
+            }
+        }
+        Pattern::Spatial { turns, .. } => Feedback {
+            warning: Some(match turns {
+                1 => "Straight rows of keys are easy to guess",
+                _ => "Short keyboard patterns are easy to guess",
+            }),
+            suggestions: vec!["Use a longer keyboard pattern with more turns"],
+        },
+        Pattern::Repeat { base, .. } => Feedback {
+            warning: Some(match base.chars().count() {
+                1 => "Repeats like \"aaa\" are easy to guess",
+                _ => "Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\"",
+            }),
+            suggestions: vec!["Avoid repeated words and characters"],
+        },
+        Pattern::Sequence { .. } => Feedback {
+            warning: Some("Sequences like abc or 6543 are easy to guess"),
+            suggestions: vec!["Avoid sequences"],
+        },
+        Pattern::Year(_) => Feedback {
+            warning: Some("Recent years are easy to guess"),
+            suggestions: vec![
+                "Avoid recent years",
+                "Avoid years that are associated with you",
+            ],
+        },
+        Pattern::Date { .. } => Feedback {
+            warning: Some("Dates are often easy to guess"),
+            suggestions: vec!["Avoid dates and years that are associated with you"],
+        },
+        Pattern::Bruteforce => Feedback::default(),
+    }
+}
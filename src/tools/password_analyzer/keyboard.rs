@@ -0,0 +1,136 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+// Every key is written with its unshifted and shifted character. On a typewriter-style
+// keyboard each row is shifted right by a third of a key, which the leading spaces show.
+const QWERTY: &str = r#"
+`~ 1! 2@ 3# 4$ 5% 6^ 7& 8* 9( 0) -_ =+
+    qQ wW eE rR tT yY uU iI oO pP [{ ]} \|
+     aA sS dD fF gG hH jJ kK lL ;: '"
+      zZ xX cC vV bB nN mM ,< .> /?
+"#;
+
+const KEYPAD: &str = "
+  / * -
+7 8 9 +
+4 5 6
+1 2 3
+  0 .
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyboard {
+    Qwerty,
+    Keypad,
+}
+
+impl Keyboard {
+    pub const ALL: [Keyboard; 2] = [Keyboard::Qwerty, Keyboard::Keypad];
+
+    pub fn graph(&self) -> &'static Graph {
+        static GRAPHS: OnceLock<[Graph; 2]> = OnceLock::new();
+        &GRAPHS.get_or_init(|| [Graph::new(QWERTY, true), Graph::new(KEYPAD, false)])
+            [*self as usize]
+    }
+}
+
+impl std::fmt::Display for Keyboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Keyboard::Qwerty => "QWERTY keyboard",
+                Keyboard::Keypad => "numeric keypad",
+            }
+        )
+    }
+}
+
+/// Which keys neighbor each character. The neighbors are listed in a fixed order of
+/// directions, with `None` where a direction runs off the keyboard, so a walk can tell when it
+/// changes direction.
+pub struct Graph {
+    neighbors: HashMap<char, Vec<Option<&'static str>>>,
+    shifted: HashSet<char>,
+    pub starting_positions: usize,
+    pub average_degree: f64,
+}
+
+impl Graph {
+    fn new(layout: &'static str, is_slanted: bool) -> Self {
+        let mut positions = HashMap::new();
+        for (y, line) in layout.lines().skip(1).enumerate() {
+            let slant = if is_slanted { y as i32 } else { 0 };
+            let mut start = None;
+            for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+                match (c, start) {
+                    (' ', Some(begin)) => {
+                        let key = &line[begin..i];
+                        let width = key.len() as i32 + 1;
+                        positions.insert(((begin as i32 - slant) / width, y as i32), key);
+                        start = None;
+                    }
+                    (' ', None) => (),
+                    (_, None) => start = Some(i),
+                    (_, Some(_)) => (),
+                }
+            }
+        }
+
+        let mut neighbors = HashMap::new();
+        for ((x, y), key) in &positions {
+            let directions: &[(i32, i32)] = match is_slanted {
+                true => &[(-1, 0), (0, -1), (1, -1), (1, 0), (0, 1), (-1, 1)],
+                false => &[
+                    (-1, 0),
+                    (-1, -1),
+                    (0, -1),
+                    (1, -1),
+                    (1, 0),
+                    (1, 1),
+                    (0, 1),
+                    (-1, 1),
+                ],
+            };
+            let adjacent: Vec<Option<&'static str>> = directions
+                .iter()
+                .map(|(dx, dy)| positions.get(&(x + dx, y + dy)).copied())
+                .collect();
+            for c in key.chars() {
+                neighbors.insert(c, adjacent.clone());
+            }
+        }
+        let degrees: usize = neighbors
+            .values()
+            .map(|adjacent| adjacent.iter().flatten().count())
+            .sum();
+        Self {
+            starting_positions: neighbors.len(),
+            average_degree: degrees as f64 / neighbors.len() as f64,
+            neighbors,
+            shifted: positions
+                .values()
+                .filter_map(|key| key.chars().nth(1))
+                .collect(),
+        }
+    }
+
+    /// The direction from `from` to the neighboring key typing `to`, and whether `to` needs
+    /// shift.
+    pub fn step(&self, from: char, to: char) -> Option<(usize, bool)> {
+        self.neighbors
+            .get(&from)?
+            .iter()
+            .enumerate()
+            .find_map(|(direction, key)| {
+                let position = (*key)?.chars().position(|c| c == to)?;
+                Some((direction, position == 1))
+            })
+    }
+
+    /// Whether `c` is the shifted character of its key.
+    pub fn is_shifted(&self, c: char) -> bool {
+        self.shifted.contains(&c)
+    }
+}
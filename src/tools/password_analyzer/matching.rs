@@ -0,0 +1,698 @@
+use std::collections::HashSet;
+
+use super::dictionary::Dictionary;
+use super::keyboard::Keyboard;
+use super::scoring;
+
+// Longer substrings can't be dictionary words, so they aren't looked up.
+const MAX_WORD_LENGTH: usize = 32;
+// How many ways of reading the l33t characters are tried, since characters like `1` can stand
+// for several letters.
+const MAX_SUBSTITUTIONS: usize = 64;
+const DATE_MIN_YEAR: i32 = 1000;
+const DATE_MAX_YEAR: i32 = 2050;
+const DATE_SEPARATORS: &str = " /\\_.-";
+
+const L33T_TABLE: &[(char, &[char])] = &[
+    ('a', &['4', '@']),
+    ('b', &['8']),
+    ('c', &['(', '{', '[', '<']),
+    ('e', &['3']),
+    ('g', &['6', '9']),
+    ('i', &['1', '!', '|']),
+    ('l', &['1', '|', '7']),
+    ('o', &['0']),
+    ('s', &['$', '5']),
+    ('t', &['+', '7']),
+    ('x', &['%']),
+    ('z', &['2']),
+];
+
+/// A part of the password that follows a guessable pattern. `i` and `j` are the positions of
+/// its first and last character.
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub i: usize,
+    pub j: usize,
+    pub token: String,
+    pub pattern: Pattern,
+    pub guesses: f64,
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Dictionary {
+        dictionary: Dictionary,
+        word: String,
+        rank: usize,
+        reversed: bool,
+        // Each substituted character with the letter it stands for.
+        l33t: Vec<(char, char)>,
+    },
+    Spatial {
+        keyboard: Keyboard,
+        turns: usize,
+        shifted: usize,
+    },
+    Repeat {
+        base: String,
+        base_guesses: f64,
+        count: usize,
+    },
+    Sequence {
+        name: &'static str,
+        space: u32,
+        ascending: bool,
+    },
+    Year(i32),
+    Date {
+        year: i32,
+        month: i32,
+        day: i32,
+        separator: Option<char>,
+    },
+    Bruteforce,
+}
+
+impl Match {
+    pub fn new(chars: &[char], i: usize, j: usize, pattern: Pattern) -> Self {
+        Self {
+            i,
+            j,
+            token: chars[i..=j].iter().collect(),
+            pattern,
+            guesses: 0.0,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match &self.pattern {
+            Pattern::Dictionary {
+                dictionary,
+                word,
+                rank,
+                reversed,
+                l33t,
+            } => {
+                let mut description = format!("'{}' from the {}, rank {}", word, dictionary, rank);
+                if *reversed {
+                    description.push_str(", reversed");
+                }
+                if !l33t.is_empty() {
+                    let substitutions: Vec<String> = l33t
+                        .iter()
+                        .map(|(sub, letter)| format!("{} for {}", sub, letter))
+                        .collect();
+                    description.push_str(&format!(", with {}", substitutions.join(", ")));
+                }
+                description
+            }
+            Pattern::Spatial {
+                keyboard,
+                turns,
+                shifted,
+            } => {
+                let mut description = format!(
+                    "Neighboring keys on a {}, {} {}",
+                    keyboard,
+                    turns,
+                    if *turns == 1 {
+                        "direction"
+                    } else {
+                        "directions"
+                    }
+                );
+                if *shifted > 0 {
+                    description.push_str(&format!(", {} shifted", shifted));
+                }
+                description
+            }
+            Pattern::Repeat { base, count, .. } => format!("'{}' repeated {} times", base, count),
+            Pattern::Sequence {
+                name, ascending, ..
+            } => format!(
+                "{} sequence of {}",
+                if *ascending {
+                    "Ascending"
+                } else {
+                    "Descending"
+                },
+                name
+            ),
+            Pattern::Year(year) => format!("The year {}", year),
+            Pattern::Date {
+                year, month, day, ..
+            } => format!("The date {:04}-{:02}-{:02}", year, month, day),
+            Pattern::Bruteforce => String::from("No pattern, guessed character by character"),
+        }
+    }
+}
+
+/// Every pattern found in the password, overlapping ones included.
+pub fn omnimatch(chars: &[char]) -> Vec<Match> {
+    let mut matches = dictionary(chars);
+    matches.extend(reversed_dictionary(chars));
+    matches.extend(l33t(chars));
+    matches.extend(spatial(chars));
+    matches.extend(repeat(chars));
+    matches.extend(sequence(chars));
+    matches.extend(year(chars));
+    matches.extend(date(chars));
+    matches.sort_by_key(|m| (m.i, m.j));
+    matches
+}
+
+fn dictionary(chars: &[char]) -> Vec<Match> {
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let mut matches = Vec::new();
+    for i in 0..chars.len() {
+        let mut word = String::new();
+        for (j, c) in lower.iter().enumerate().skip(i).take(MAX_WORD_LENGTH) {
+            word.push(*c);
+            for dictionary in Dictionary::ALL {
+                if let Some(rank) = dictionary.rank(&word) {
+                    matches.push(Match::new(
+                        chars,
+                        i,
+                        j,
+                        Pattern::Dictionary {
+                            dictionary,
+                            word: word.clone(),
+                            rank,
+                            reversed: false,
+                            l33t: Vec::new(),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+    matches
+}
+
+fn reversed_dictionary(chars: &[char]) -> Vec<Match> {
+    let reversed: Vec<char> = chars.iter().rev().copied().collect();
+    let n = chars.len();
+    dictionary(&reversed)
+        .into_iter()
+        .map(|m| {
+            let (i, j) = (n - 1 - m.j, n - 1 - m.i);
+            let pattern = match m.pattern {
+                Pattern::Dictionary {
+                    dictionary,
+                    word,
+                    rank,
+                    ..
+                } => Pattern::Dictionary {
+                    dictionary,
+                    word,
+                    rank,
+                    reversed: true,
+                    l33t: Vec::new(),
+                },
+                pattern => pattern,
+            };
+            Match::new(chars, i, j, pattern)
+        })
+        .collect()
+}
+
+/// Reads the password with l33t characters replaced by letters, in every combination up to
+/// a limit, and keeps the dictionary words that needed a substitution.
+fn l33t(chars: &[char]) -> Vec<Match> {
+    let mut candidates: Vec<(char, Vec<char>)> = Vec::new();
+    for c in chars {
+        let letters: Vec<char> = L33T_TABLE
+            .iter()
+            .filter(|(_, subs)| subs.contains(c))
+            .map(|(letter, _)| *letter)
+            .collect();
+        if !letters.is_empty() && !candidates.iter().any(|(sub, _)| sub == c) {
+            candidates.push((*c, letters));
+        }
+    }
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    let mut substitutions: Vec<Vec<(char, char)>> = vec![Vec::new()];
+    for (sub, letters) in &candidates {
+        substitutions = substitutions
+            .iter()
+            .flat_map(|map| {
+                letters.iter().map(move |letter| {
+                    let mut map = map.clone();
+                    map.push((*sub, *letter));
+                    map
+                })
+            })
+            .take(MAX_SUBSTITUTIONS)
+            .collect();
+    }
+
+    let mut seen = HashSet::new();
+    let mut matches = Vec::new();
+    for map in substitutions {
+        let translated: Vec<char> = chars
+            .iter()
+            .map(|c| match map.iter().find(|(sub, _)| sub == c) {
+                Some((_, letter)) => *letter,
+                None => *c,
+            })
+            .collect();
+        for m in dictionary(&translated) {
+            let Pattern::Dictionary {
+                dictionary,
+                word,
+                rank,
+                ..
+            } = m.pattern
+            else {
+                continue;
+            };
+            let token = &chars[m.i..=m.j];
+            if token.len() <= 1 || !seen.insert((m.i, m.j, dictionary as usize, word.clone())) {
+                continue;
+            }
+            let used: Vec<(char, char)> = map
+                .iter()
+                .filter(|(sub, _)| token.contains(sub))
+                .copied()
+                .collect();
+            if used.is_empty() {
+                continue;
+            }
+            matches.push(Match::new(
+                chars,
+                m.i,
+                m.j,
+                Pattern::Dictionary {
+                    dictionary,
+                    word,
+                    rank,
+                    reversed: false,
+                    l33t: used,
+                },
+            ));
+        }
+    }
+    matches
+}
+
+/// Runs of at least three keys where each is next to the one before, like `qwerty` or `zaq1`.
+fn spatial(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for keyboard in Keyboard::ALL {
+        let graph = keyboard.graph();
+        let mut i = 0;
+        while i + 1 < chars.len() {
+            let mut j = i + 1;
+            let mut last_direction = None;
+            let mut turns = 0;
+            let mut shifted = usize::from(graph.is_shifted(chars[i]));
+            loop {
+                match chars.get(j).and_then(|c| graph.step(chars[j - 1], *c)) {
+                    Some((direction, is_shifted)) => {
+                        shifted += usize::from(is_shifted);
+                        if last_direction != Some(direction) {
+                            turns += 1;
+                            last_direction = Some(direction);
+                        }
+                        j += 1;
+                    }
+                    None => {
+                        if j - i > 2 {
+                            matches.push(Match::new(
+                                chars,
+                                i,
+                                j - 1,
+                                Pattern::Spatial {
+                                    keyboard,
+                                    turns,
+                                    shifted,
+                                },
+                            ));
+                        }
+                        i = j;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    matches
+}
+
+/// The longest run of a repeated substring at each position, like `aaaa` or `abcabc`.
+fn repeat(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let mut best: Option<(usize, usize)> = None;
+        for length in 1..=(chars.len() - i) / 2 {
+            let base = &chars[i..i + length];
+            let mut count = 1;
+            while chars
+                .get(i + count * length..i + (count + 1) * length)
+                .is_some_and(|next| next == base)
+            {
+                count += 1;
+            }
+            if count >= 2 && best.is_none_or(|(l, c)| count * length > l * c) {
+                best = Some((length, count));
+            }
+        }
+        match best {
+            Some((length, count)) => {
+                let base = &chars[i..i + length];
+                let base_guesses = scoring::most_guessable(base, omnimatch(base)).0;
+                matches.push(Match::new(
+                    chars,
+                    i,
+                    i + length * count - 1,
+                    Pattern::Repeat {
+                        base: base.iter().collect(),
+                        base_guesses,
+                        count,
+                    },
+                ));
+                i += length * count;
+            }
+            None => i += 1,
+        }
+    }
+    matches
+}
+
+/// Runs of characters with a constant step of up to five code points, like `abc`, `9753` or
+/// `zyx`.
+fn sequence(chars: &[char]) -> Vec<Match> {
+    const MAX_DELTA: i64 = 5;
+    let mut matches = Vec::new();
+    if chars.len() < 2 {
+        return matches;
+    }
+    let mut push = |i: usize, j: usize, delta: i64| {
+        if (j - i > 1 || delta.abs() == 1) && delta != 0 && delta.abs() <= MAX_DELTA {
+            let token = &chars[i..=j];
+            let (name, space) = if token.iter().all(|c| c.is_ascii_lowercase()) {
+                ("lowercase letters", 26)
+            } else if token.iter().all(|c| c.is_ascii_uppercase()) {
+                ("uppercase letters", 26)
+            } else if token.iter().all(|c| c.is_ascii_digit()) {
+                ("digits", 10)
+            } else {
+                ("characters", 26)
+            };
+            matches.push(Match::new(
+                chars,
+                i,
+                j,
+                Pattern::Sequence {
+                    name,
+                    space,
+                    ascending: delta > 0,
+                },
+            ));
+        }
+    };
+    let mut i = 0;
+    let mut last_delta = None;
+    for k in 1..chars.len() {
+        let delta = chars[k] as i64 - chars[k - 1] as i64;
+        let last = *last_delta.get_or_insert(delta);
+        if delta == last {
+            continue;
+        }
+        push(i, k - 1, last);
+        i = k - 1;
+        last_delta = Some(delta);
+    }
+    push(i, chars.len() - 1, last_delta.unwrap_or(0));
+    matches
+}
+
+fn year(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i + 4 <= chars.len() {
+        let token: String = chars[i..i + 4].iter().collect();
+        match token.parse::<i32>() {
+            Ok(year)
+                if token.chars().all(|c| c.is_ascii_digit()) && (1900..2100).contains(&year) =>
+            {
+                matches.push(Match::new(chars, i, i + 3, Pattern::Year(year)));
+                i += 4;
+            }
+            _ => i += 1,
+        }
+    }
+    matches
+}
+
+/// Dates of four to eight digits like `13111991` or `0623`, and dates with separators like
+/// `1991-11-13` or `3/7/08`.
+fn date(chars: &[char]) -> Vec<Match> {
+    let reference = scoring::reference_year();
+    let mut matches = Vec::new();
+    for i in 0..chars.len() {
+        for j in i + 3..chars.len().min(i + 8) {
+            let token = &chars[i..=j];
+            if !token.iter().all(|c| c.is_ascii_digit()) {
+                break;
+            }
+            let splits: &[(usize, usize)] = match token.len() {
+                4 => &[(1, 2), (2, 3)],
+                5 => &[(1, 3), (2, 3)],
+                6 => &[(1, 2), (2, 4), (4, 5)],
+                7 => &[(1, 3), (2, 3), (4, 5), (4, 6)],
+                _ => &[(2, 4), (4, 6)],
+            };
+            let number = |from: usize, to: usize| -> i32 {
+                token[from..to]
+                    .iter()
+                    .fold(0, |n, c| n * 10 + c.to_digit(10).unwrap_or(0) as i32)
+            };
+            let best = splits
+                .iter()
+                .filter_map(|(k, l)| {
+                    day_month_year([number(0, *k), number(*k, *l), number(*l, token.len())])
+                })
+                .min_by_key(|(year, _, _)| (year - reference).abs());
+            if let Some((year, month, day)) = best {
+                matches.push(Match::new(
+                    chars,
+                    i,
+                    j,
+                    Pattern::Date {
+                        year,
+                        month,
+                        day,
+                        separator: None,
+                    },
+                ));
+            }
+        }
+
+        for j in i + 5..chars.len().min(i + 10) {
+            if let Some((separator, numbers)) = separated_date(&chars[i..=j]) {
+                if let Some((year, month, day)) = day_month_year(numbers) {
+                    matches.push(Match::new(
+                        chars,
+                        i,
+                        j,
+                        Pattern::Date {
+                            year,
+                            month,
+                            day,
+                            separator: Some(separator),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    // A date inside a longer date, like `1991` in `13111991`, adds nothing.
+    let spans: Vec<(usize, usize)> = matches.iter().map(|m| (m.i, m.j)).collect();
+    matches.retain(|m| {
+        !spans
+            .iter()
+            .any(|(i, j)| (*i, *j) != (m.i, m.j) && *i <= m.i && *j >= m.j)
+    });
+    matches
+}
+
+// One to four digits, a separator, one or two digits, the same separator, one to four digits.
+fn separated_date(token: &[char]) -> Option<(char, [i32; 3])> {
+    let mut parts: Vec<(usize, i32)> = Vec::new();
+    let mut separator = None;
+    let mut digits = 0;
+    let mut value = 0;
+    for c in token {
+        match c.to_digit(10) {
+            Some(_) if digits == 4 => return None,
+            Some(digit) => {
+                digits += 1;
+                value = value * 10 + digit as i32;
+            }
+            None if DATE_SEPARATORS.contains(*c)
+                && digits > 0
+                && *separator.get_or_insert(*c) == *c =>
+            {
+                parts.push((digits, value));
+                digits = 0;
+                value = 0;
+            }
+            None => return None,
+        }
+    }
+    parts.push((digits, value));
+    match parts[..] {
+        [(a, first), (b, second), (c, third)]
+            if (1..=4).contains(&a) && (1..=2).contains(&b) && (1..=4).contains(&c) =>
+        {
+            Some((separator?, [first, second, third]))
+        }
+        _ => None,
+    }
+}
+
+/// Reads three numbers as a date in any day, month and year order, returning the year, month
+/// and day. Two-digit years are taken to be the closest to now.
+fn day_month_year(numbers: [i32; 3]) -> Option<(i32, i32, i32)> {
+    if numbers[1] > 31 || numbers[1] <= 0 {
+        return None;
+    }
+    let (mut over_12, mut over_31, mut under_1) = (0, 0, 0);
+    for number in numbers {
+        if (99 < number && number < DATE_MIN_YEAR) || number > DATE_MAX_YEAR {
+            return None;
+        }
+        over_31 += usize::from(number > 31);
+        over_12 += usize::from(number > 12);
+        under_1 += usize::from(number <= 0);
+    }
+    if over_31 >= 2 || over_12 == 3 || under_1 >= 2 {
+        return None;
+    }
+
+    let splits = [
+        (numbers[2], [numbers[0], numbers[1]]),
+        (numbers[0], [numbers[1], numbers[2]]),
+    ];
+    for (year, rest) in splits {
+        if (DATE_MIN_YEAR..=DATE_MAX_YEAR).contains(&year) {
+            // A four-digit year has to go with a valid day and month.
+            return month_day(rest).map(|(month, day)| (year, month, day));
+        }
+    }
+    for (year, rest) in splits {
+        if let Some((month, day)) = month_day(rest) {
+            let year = match year {
+                year if year > 99 => year,
+                year if year > 50 => 1900 + year,
+                year => 2000 + year,
+            };
+            return Some((year, month, day));
+        }
+    }
+    None
+}
+
+fn month_day([a, b]: [i32; 2]) -> Option<(i32, i32)> {
+    [(a, b), (b, a)]
+        .into_iter()
+        .find(|(day, month)| (1..=31).contains(day) && (1..=12).contains(month))
+        .map(|(day, month)| (month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(password: &str) -> Vec<char> {
+        password.chars().collect()
+    }
+
+    #[test]
+    fn keyboard_walks_are_matched_per_run() {
+        let walks = spatial(&chars("zaq1xsw2"));
+        let tokens: Vec<&str> = walks.iter().map(|m| m.token.as_str()).collect();
+        assert_eq!(tokens, ["zaq1", "xsw2"]);
+        assert!(walks.iter().all(|m| matches!(
+            m.pattern,
+            Pattern::Spatial {
+                keyboard: Keyboard::Qwerty,
+                turns: 1,
+                shifted: 0
+            }
+        )));
+
+        let shifted = spatial(&chars("QWErty"));
+        assert!(matches!(
+            shifted[..],
+            [Match {
+                i: 0,
+                j: 5,
+                pattern: Pattern::Spatial {
+                    turns: 1,
+                    shifted: 3,
+                    ..
+                },
+                ..
+            }]
+        ));
+        assert!(spatial(&chars("qp")).is_empty());
+    }
+
+    #[test]
+    fn dates_are_read_in_any_order() {
+        let date_of = |password: &str| {
+            date(&chars(password))
+                .into_iter()
+                .find(|m| m.i == 0 && m.j == password.chars().count() - 1)
+                .map(|m| match m.pattern {
+                    Pattern::Date {
+                        year,
+                        month,
+                        day,
+                        separator,
+                    } => (year, month, day, separator),
+                    _ => unreachable!(),
+                })
+        };
+        assert_eq!(date_of("13/05/1997"), Some((1997, 5, 13, Some('/'))));
+        assert_eq!(date_of("1997-05-13"), Some((1997, 5, 13, Some('-'))));
+        assert_eq!(date_of("13051997"), Some((1997, 5, 13, None)));
+        assert_eq!(date_of("9/1/85"), Some((1985, 1, 9, Some('/'))));
+        assert_eq!(date_of("13/05-1997"), None);
+        assert_eq!(date_of("32/13/1997"), None);
+    }
+
+    #[test]
+    fn l33t_substitutions_are_undone() {
+        let matches = l33t(&chars("p@ssw0rd"));
+        let password = matches
+            .iter()
+            .find(|m| m.i == 0 && m.j == 7)
+            .map(|m| &m.pattern);
+        let Some(Pattern::Dictionary {
+            dictionary: Dictionary::Passwords,
+            word,
+            rank: 2,
+            l33t: substitutions,
+            ..
+        }) = password
+        else {
+            panic!("p@ssw0rd should match 'password', got {:?}", matches);
+        };
+        assert_eq!(word, "password");
+        assert_eq!(substitutions, &[('@', 'a'), ('0', 'o')]);
+        // Words without substitutions are left to the plain dictionary matcher.
+        assert!(l33t(&chars("password")).is_empty());
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::BTreeMap;
+
+use chrono::Datelike;
+
+use super::matching::{self, Match, Pattern};
+
+// Only the start of very long passwords is analyzed; the estimate is past any realistic
+// attack long before that.
+pub const MAX_LENGTH: usize = 100;
+
+// An attacker guesses the number of patterns before guessing each one, so every extra
+// pattern in a sequence costs at least this much more.
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10_000.0;
+const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_SUBMATCH_GUESSES_MULTI_CHAR: f64 = 50.0;
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_YEAR_SPACE: i32 = 20;
+
+/// How guessable a password is, and the patterns that make it so.
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub guesses: f64,
+    pub score: u8,
+    pub sequence: Vec<Match>,
+    pub is_truncated: bool,
+}
+
+pub fn analyze(password: &str) -> Analysis {
+    let chars: Vec<char> = password.chars().take(MAX_LENGTH).collect();
+    let (guesses, sequence) = most_guessable(&chars, matching::omnimatch(&chars));
+    Analysis {
+        guesses,
+        score: score(guesses),
+        sequence,
+        is_truncated: password.chars().count() > MAX_LENGTH,
+    }
+}
+
+pub fn reference_year() -> i32 {
+    chrono::Local::now().year()
+}
+
+/// Scores from 0, too guessable, to 4, very unguessable.
+fn score(guesses: f64) -> u8 {
+    const DELTA: f64 = 5.0;
+    match guesses {
+        g if g < 1e3 + DELTA => 0,
+        g if g < 1e6 + DELTA => 1,
+        g if g < 1e8 + DELTA => 2,
+        g if g < 1e10 + DELTA => 3,
+        _ => 4,
+    }
+}
+
+/// Finds the sequence of non-overlapping matches, with brute force filling the gaps, that
+/// needs the fewest guesses to cover the whole password. A sequence of `l` matches costs
+/// `l!` times the product of their guesses, since the attacker doesn't know their order,
+/// plus the cost of trying shorter sequences first.
+pub fn most_guessable(chars: &[char], matches: Vec<Match>) -> (f64, Vec<Match>) {
+    let n = chars.len();
+    if n == 0 {
+        return (1.0, Vec::new());
+    }
+
+    let mut arena: Vec<Match> = matches
+        .into_iter()
+        .map(|mut m| {
+            m.guesses = estimate(&m, n);
+            m
+        })
+        .collect();
+    let mut by_end: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (index, m) in arena.iter().enumerate() {
+        by_end[m.j].push(index);
+    }
+
+    let mut optimal = Optimal {
+        m: vec![BTreeMap::new(); n],
+        pi: vec![BTreeMap::new(); n],
+        g: vec![BTreeMap::new(); n],
+    };
+    for (k, ending) in by_end.iter().enumerate() {
+        for index in ending {
+            let i = arena[*index].i;
+            match i {
+                0 => optimal.update(&arena, *index, 1),
+                _ => {
+                    let lengths: Vec<usize> = optimal.m[i - 1].keys().copied().collect();
+                    for l in lengths {
+                        optimal.update(&arena, *index, l + 1);
+                    }
+                }
+            }
+        }
+
+        // Brute force from the start, or after any sequence that doesn't already end in
+        // brute force.
+        arena.push(bruteforce(chars, 0, k, n));
+        optimal.update(&arena, arena.len() - 1, 1);
+        for i in 1..=k {
+            arena.push(bruteforce(chars, i, k, n));
+            let previous: Vec<(usize, usize)> =
+                optimal.m[i - 1].iter().map(|(l, m)| (*l, *m)).collect();
+            for (l, last) in previous {
+                if !matches!(arena[last].pattern, Pattern::Bruteforce) {
+                    optimal.update(&arena, arena.len() - 1, l + 1);
+                }
+            }
+        }
+    }
+
+    let Some((mut l, guesses)) = optimal.g[n - 1]
+        .iter()
+        .map(|(l, g)| (*l, *g))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+        return (1.0, Vec::new());
+    };
+    let mut sequence = Vec::new();
+    let mut k = n - 1;
+    loop {
+        let m = &arena[optimal.m[k][&l]];
+        sequence.push(m.clone());
+        if m.i == 0 {
+            break;
+        }
+        k = m.i - 1;
+        l -= 1;
+    }
+    sequence.reverse();
+    (guesses, sequence)
+}
+
+// For each end position and number of matches, the best last match, the product of the
+// guesses in its sequence, and the sequence's total guesses.
+struct Optimal {
+    m: Vec<BTreeMap<usize, usize>>,
+    pi: Vec<BTreeMap<usize, f64>>,
+    g: Vec<BTreeMap<usize, f64>>,
+}
+
+impl Optimal {
+    fn update(&mut self, arena: &[Match], index: usize, l: usize) {
+        let m = &arena[index];
+        let k = m.j;
+        let mut pi = m.guesses;
+        if l > 1 {
+            pi *= self.pi[m.i - 1][&(l - 1)];
+        }
+        let g = factorial(l) * pi + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(l as i32 - 1);
+        // A sequence with fewer matches and no more guesses is always better.
+        if self.g[k]
+            .iter()
+            .any(|(competing_l, competing_g)| *competing_l <= l && *competing_g <= g)
+        {
+            return;
+        }
+        self.g[k].insert(l, g);
+        self.m[k].insert(l, index);
+        self.pi[k].insert(l, pi);
+    }
+}
+
+fn bruteforce(chars: &[char], i: usize, j: usize, n: usize) -> Match {
+    let mut m = Match::new(chars, i, j, Pattern::Bruteforce);
+    m.guesses = estimate(&m, n);
+    m
+}
+
+/// The number of guesses an attacker who knows the pattern needs to find the match.
+fn estimate(m: &Match, password_length: usize) -> f64 {
+    let length = m.j - m.i + 1;
+    let guesses = match &m.pattern {
+        Pattern::Bruteforce => {
+            let guesses = BRUTEFORCE_CARDINALITY.powi(length as i32).min(f64::MAX);
+            let min = match length {
+                1 => MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1.0,
+                _ => MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1.0,
+            };
+            guesses.max(min)
+        }
+        Pattern::Dictionary {
+            rank,
+            reversed,
+            l33t,
+            ..
+        } => {
+            *rank as f64
+                * uppercase_variations(&m.token)
+                * l33t_variations(&m.token, l33t)
+                * if *reversed { 2.0 } else { 1.0 }
+        }
+        Pattern::Spatial {
+            keyboard,
+            turns,
+            shifted,
+        } => {
+            let graph = keyboard.graph();
+            let (starts, degree) = (graph.starting_positions as f64, graph.average_degree);
+            let mut guesses = 0.0;
+            for i in 2..=length {
+                for j in 1..=(*turns).min(i - 1) {
+                    guesses += choose(i - 1, j - 1) * starts * degree.powi(j as i32);
+                }
+            }
+            let unshifted = length - shifted;
+            match (*shifted, unshifted) {
+                (0, _) => guesses,
+                (_, 0) => guesses * 2.0,
+                (shifted, unshifted) => {
+                    guesses
+                        * (1..=shifted.min(unshifted))
+                            .map(|i| choose(shifted + unshifted, i))
+                            .sum::<f64>()
+                }
+            }
+        }
+        Pattern::Repeat {
+            base_guesses,
+            count,
+            ..
+        } => base_guesses * *count as f64,
+        Pattern::Sequence {
+            space, ascending, ..
+        } => {
+            let first = m.token.chars().next().unwrap_or_default();
+            let base = match first {
+                'a' | 'A' | 'z' | 'Z' | '0' | '1' | '9' => 4.0,
+                c if c.is_ascii_digit() => 10.0,
+                _ => *space as f64,
+            };
+            base * if *ascending { 1.0 } else { 2.0 } * length as f64
+        }
+        Pattern::Year(year) => year_space(*year),
+        Pattern::Date {
+            year, separator, ..
+        } => year_space(*year) * 365.0 * if separator.is_some() { 4.0 } else { 1.0 },
+    };
+
+    // A match covering only part of the password needs at least a few guesses, so that
+    // splitting it into tiny matches doesn't look cheaper than brute force.
+    let min = match (length < password_length, length) {
+        (false, _) => 1.0,
+        (true, 1) => MIN_SUBMATCH_GUESSES_SINGLE_CHAR,
+        (true, _) => MIN_SUBMATCH_GUESSES_MULTI_CHAR,
+    };
+    guesses.max(min)
+}
+
+fn year_space(year: i32) -> f64 {
+    (year - reference_year()).abs().max(MIN_YEAR_SPACE) as f64
+}
+
+/// How many ways of capitalizing the word an attacker tries before this one: capitalizing
+/// the first or last letter, or all of them, is tried early.
+fn uppercase_variations(word: &str) -> f64 {
+    let upper = word.chars().filter(|c| c.is_uppercase()).count();
+    let lower = word.chars().filter(|c| c.is_lowercase()).count();
+    let chars: Vec<char> = word.chars().collect();
+    let starts_upper = chars.first().is_some_and(|c| c.is_uppercase())
+        && chars[1..].iter().all(|c| !c.is_uppercase());
+    let ends_upper = chars.last().is_some_and(|c| c.is_uppercase())
+        && chars[..chars.len() - 1].iter().all(|c| !c.is_uppercase());
+    if upper == 0 {
+        1.0
+    } else if starts_upper || ends_upper || lower == 0 {
+        2.0
+    } else {
+        (1..=upper.min(lower))
+            .map(|i| choose(upper + lower, i))
+            .sum()
+    }
+}
+
+fn l33t_variations(token: &str, substitutions: &[(char, char)]) -> f64 {
+    let token = token.to_lowercase();
+    substitutions
+        .iter()
+        .map(|(sub, letter)| {
+            let subbed = token.chars().filter(|c| c == sub).count();
+            let unsubbed = token.chars().filter(|c| c == letter).count();
+            match (subbed, unsubbed) {
+                (0, _) | (_, 0) => 2.0,
+                (subbed, unsubbed) => (1..=subbed.min(unsubbed))
+                    .map(|i| choose(subbed + unsubbed, i))
+                    .sum(),
+            }
+        })
+        .product()
+}
+
+fn choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (1..=k).fold(1.0, |result, i| result * (n - k + i) as f64 / i as f64)
+}
+
+fn factorial(n: usize) -> f64 {
+    (2..=n).fold(1.0, |result, i| result * i as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(analysis: &Analysis) -> Vec<&Pattern> {
+        analysis.sequence.iter().map(|m| &m.pattern).collect()
+    }
+
+    #[test]
+    fn common_passwords_score_zero() {
+        let password = analyze("password");
+        assert_eq!((password.score, password.guesses), (0, 3.0));
+        let qwerty = analyze("qwerty");
+        assert_eq!((qwerty.score, qwerty.guesses), (0, 7.0));
+        // Capitalizing the first letter doubles the guesses.
+        assert_eq!(analyze("Password").guesses, 5.0);
+    }
+
+    #[test]
+    fn keyboard_walks_are_cheap() {
+        let walk = analyze("hjkl;'");
+        assert_eq!(walk.score, 1);
+        assert!(matches!(
+            patterns(&walk)[..],
+            [Pattern::Spatial {
+                turns: 1,
+                shifted: 0,
+                ..
+            }]
+        ));
+        assert!((2000.0..3000.0).contains(&walk.guesses));
+    }
+
+    #[test]
+    fn dates_are_guessed_by_year() {
+        let years = (reference_year() - 1997).max(MIN_YEAR_SPACE) as f64;
+        let separated = analyze("13/05/1997");
+        assert_eq!(separated.score, 1);
+        assert_eq!(separated.guesses, years * 365.0 * 4.0 + 1.0);
+        assert_eq!(analyze("13051997").guesses, years * 365.0 + 1.0);
+    }
+
+    #[test]
+    fn l33t_substitutions_barely_help() {
+        let l33t = analyze("p@ssw0rd");
+        assert_eq!(l33t.score, 0);
+        // Rank 2, times two ways of reading each substituted character.
+        assert_eq!(l33t.guesses, 9.0);
+        assert!(matches!(
+            patterns(&l33t)[..],
+            [Pattern::Dictionary { rank: 2, .. }]
+        ));
+    }
+
+    #[test]
+    fn long_random_strings_score_four() {
+        let random = analyze("Xk9#mQ2$vL7!pR4z");
+        assert_eq!(random.score, 4);
+        assert_eq!(random.guesses, 1e16);
+        assert!(matches!(patterns(&random)[..], [Pattern::Bruteforce]));
+        assert!(!random.is_truncated);
+        assert!(analyze(&"x".repeat(MAX_LENGTH + 1)).is_truncated);
+    }
+
+    #[test]
+    fn scores_follow_the_guess_thresholds() {
+        assert_eq!(score(1e3), 0);
+        assert_eq!(score(1e3 + 5.0), 1);
+        assert_eq!(score(1e6 + 5.0), 2);
+        assert_eq!(score(1e8 + 5.0), 3);
+        assert_eq!(score(1e10 + 5.0), 4);
+    }
+}
@@ -0,0 +1,389 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use iced::task::Handle;
+use iced::widget::{
+    button, checkbox, column, container, horizontal_space, pick_list, progress_bar, row,
+    scrollable, text, text_editor, text_input, Space,
+};
+use iced::{Element, Length, Task};
+
+use crate::save::{self, SaveEvent};
+use generate::{CharacterClass, ClassOptions, Mode, Settings};
+
+mod generate;
+pub mod wordlist;
+
+pub const NAME: &str = "Password Generator";
+
+// Larger batches make the result editor sluggish, so they can only be saved to a file.
+const DISPLAY_LIMIT: u32 = 1000;
+
+impl Default for PasswordGenerator {
+    fn default() -> Self {
+        PasswordGenerator::new()
+    }
+}
+
+pub struct PasswordGenerator {
+    mode: Option<Mode>,
+    raw_length: String,
+    enabled_classes: [bool; 4],
+    raw_minimums: [String; 4],
+    exclude_ambiguous: bool,
+    raw_words: String,
+    separator: String,
+    capitalize: bool,
+    output: text_editor::Content,
+    tool_name: String,
+    raw_amount: String,
+    parsed_amount: u32,
+    parsing_error: String,
+    saving: Option<(Handle, Arc<AtomicBool>)>,
+    save_total: u64,
+    save_written: u64,
+    save_status: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Generated,
+    ModeSelected(Mode),
+    LengthChanged(String),
+    ClassToggled(CharacterClass, bool),
+    MinimumChanged(CharacterClass, String),
+    ExcludeAmbiguousToggled(bool),
+    WordsChanged(String),
+    SeparatorChanged(String),
+    CapitalizeToggled(bool),
+    AmountChanged(String),
+    PasswordList(String),
+    OutputActionPerformed(text_editor::Action),
+    SaveToFileClicked,
+    FilePicked(Option<PathBuf>),
+    SaveProgress(SaveEvent),
+    CancelSaveClicked,
+}
+
+impl PasswordGenerator {
+    pub fn new() -> Self {
+        Self {
+            mode: Some(Mode::default()),
+            raw_length: String::from("20"),
+            enabled_classes: [true; 4],
+            raw_minimums: [(); 4].map(|_| String::from("1")),
+            exclude_ambiguous: false,
+            raw_words: String::from("6"),
+            separator: String::from("-"),
+            capitalize: false,
+            output: text_editor::Content::with_text(""),
+            tool_name: NAME.to_string(),
+            raw_amount: String::from("1"),
+            parsed_amount: 1,
+            parsing_error: String::new(),
+            saving: None,
+            save_total: 0,
+            save_written: 0,
+            save_status: String::new(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    fn settings(&self) -> Result<Settings, String> {
+        let mode = self.mode.unwrap_or_default();
+        let parse = |name: &str, value: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Cannot parse {} '{}'", name, value))
+        };
+        let (length, words) = match mode {
+            Mode::Random | Mode::Pronounceable => (parse("length", &self.raw_length)?, 0),
+            Mode::Passphrase => (0, parse("word count", &self.raw_words)?),
+        };
+        let mut classes = [ClassOptions::default(); 4];
+        for class in CharacterClass::ALL {
+            let i = class as usize;
+            classes[i] = ClassOptions {
+                is_enabled: self.enabled_classes[i],
+                minimum: match self.enabled_classes[i] && mode != Mode::Passphrase {
+                    true => parse("minimum", &self.raw_minimums[i])?,
+                    false => 0,
+                },
+            };
+        }
+        let settings = Settings {
+            mode,
+            length,
+            classes,
+            exclude_ambiguous: self.exclude_ambiguous,
+            words,
+            separator: self.separator.clone(),
+            capitalize: self.capitalize,
+            amount: self.parsed_amount as u64,
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    fn can_display(&self) -> bool {
+        self.can_export() && self.parsed_amount <= DISPLAY_LIMIT
+    }
+
+    fn can_export(&self) -> bool {
+        self.saving.is_none() && self.parsing_error.is_empty() && self.settings().is_ok()
+    }
+
+    fn save_to_file(&mut self, settings: Settings, path: PathBuf) -> Task<Message> {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.save_total = settings.amount;
+        self.save_written = 0;
+        self.save_status = String::new();
+        let (task, handle) = Task::run(
+            save::save_in_background(path, cancelled.clone(), move |file, cancelled, progress| {
+                generate::write(&settings, file, cancelled, progress)
+            }),
+            Message::SaveProgress,
+        )
+        .abortable();
+        self.saving = Some((handle, cancelled));
+        task
+    }
+
+    fn save_view(&self) -> Element<'_, Message> {
+        match self.saving {
+            Some(_) => row![
+                progress_bar(0.0..=self.save_total as f32, self.save_written as f32)
+                    .height(20)
+                    .width(300),
+                text(format!("{} / {}", self.save_written, self.save_total)),
+                button("Cancel").on_press(Message::CancelSaveClicked),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into(),
+            None => text(self.save_status.as_str()).into(),
+        }
+    }
+
+    fn mode_options(&self) -> Element<'_, Message> {
+        let mode = self.mode.unwrap_or_default();
+        if mode == Mode::Passphrase {
+            return column![
+                row![
+                    "Words: ",
+                    text_input("Words", self.raw_words.as_str())
+                        .on_input(Message::WordsChanged)
+                        .width(80),
+                    "Separator: ",
+                    text_input("None", self.separator.as_str())
+                        .on_input(Message::SeparatorChanged)
+                        .width(80),
+                    checkbox("Capitalize words", self.capitalize)
+                        .on_toggle(Message::CapitalizeToggled),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+                text(format!(
+                    "Words are drawn from the EFF large wordlist of {} words",
+                    wordlist::words().len()
+                )),
+            ]
+            .spacing(10)
+            .into();
+        }
+
+        let classes = CharacterClass::ALL.iter().map(|class| {
+            let i = *class as usize;
+            row![
+                checkbox(class.to_string(), self.enabled_classes[i])
+                    .on_toggle(move |value| Message::ClassToggled(*class, value))
+                    .width(200),
+                "At least: ",
+                text_input("0", self.raw_minimums[i].as_str())
+                    .on_input_maybe(
+                        self.enabled_classes[i]
+                            .then_some(move |value| Message::MinimumChanged(*class, value))
+                    )
+                    .width(60),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into()
+        });
+        let mut options = column![
+            row![
+                "Length: ",
+                text_input("Length", self.raw_length.as_str())
+                    .on_input(Message::LengthChanged)
+                    .width(80),
+                checkbox(
+                    format!("Exclude ambiguous characters ({})", generate::AMBIGUOUS),
+                    self.exclude_ambiguous
+                )
+                .on_toggle(Message::ExcludeAmbiguousToggled),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+            column(classes).spacing(5),
+        ]
+        .spacing(10);
+        if mode == Mode::Pronounceable {
+            options = options.push(text(
+                "Letters alternate consonants and vowels. The required capitals are placed at random and the required digits and symbols go at the end.",
+            ));
+        }
+        options.into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let entropy = match self.settings() {
+            Ok(settings) => {
+                let entropy = settings.entropy();
+                format!(
+                    "Entropy: {:.1} bits per password ({})",
+                    entropy,
+                    generate::strength(entropy)
+                )
+            }
+            Err(e) => e,
+        };
+
+        let configuration = column![
+            "Configuration",
+            row![
+                "Mode: ",
+                pick_list(&Mode::ALL[..], self.mode, Message::ModeSelected)
+            ],
+            self.mode_options(),
+            row![
+                "Amount: ",
+                text_input("Amount", self.raw_amount.as_str()).on_input(Message::AmountChanged),
+                text(self.parsing_error.as_str()),
+                horizontal_space()
+            ],
+            text(entropy),
+            row![
+                button("Generate").on_press_maybe(match self.can_display() {
+                    true => Some(Message::Generated),
+                    false => None,
+                }),
+                Space::with_width(10),
+                button("Save to file").on_press_maybe(match self.can_export() {
+                    true => Some(Message::SaveToFileClicked),
+                    false => None,
+                })
+            ],
+            self.save_view(),
+        ]
+        .padding(10)
+        .spacing(10);
+
+        let result = column![
+            "Result",
+            scrollable(text_editor(&self.output).on_action(Message::OutputActionPerformed))
+        ]
+        .padding(10)
+        .spacing(10);
+
+        container(column![configuration, Space::with_height(20), result])
+            .padding(10)
+            .height(Length::Fill)
+            .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Generated => match self.settings() {
+                Ok(settings) => Task::perform(
+                    async move { generate::render(&settings) },
+                    Message::PasswordList,
+                ),
+                Err(_) => Task::none(),
+            },
+            Message::ModeSelected(mode) => {
+                self.mode = Some(mode);
+                Task::none()
+            }
+            Message::LengthChanged(value) => {
+                self.raw_length = value;
+                Task::none()
+            }
+            Message::ClassToggled(class, value) => {
+                self.enabled_classes[class as usize] = value;
+                Task::none()
+            }
+            Message::MinimumChanged(class, value) => {
+                self.raw_minimums[class as usize] = value;
+                Task::none()
+            }
+            Message::ExcludeAmbiguousToggled(value) => {
+                self.exclude_ambiguous = value;
+                Task::none()
+            }
+            Message::WordsChanged(value) => {
+                self.raw_words = value;
+                Task::none()
+            }
+            Message::SeparatorChanged(value) => {
+                self.separator = value;
+                Task::none()
+            }
+            Message::CapitalizeToggled(value) => {
+                self.capitalize = value;
+                Task::none()
+            }
+            Message::AmountChanged(value) => {
+                self.raw_amount = value.clone();
+                match value.parse::<u32>() {
+                    Ok(v) if v > 0 => {
+                        self.parsed_amount = v;
+                        self.parsing_error = String::new();
+                    }
+                    Ok(_) => self.parsing_error = format!("Amount must be at least 1 '{}'", value),
+                    Err(_) => self.parsing_error = format!("Cannot parse '{}'", value),
+                };
+                Task::none()
+            }
+            Message::PasswordList(result) => {
+                self.output = text_editor::Content::with_text(result.as_str());
+                Task::none()
+            }
+            Message::OutputActionPerformed(action) => {
+                if !action.is_edit() {
+                    self.output.perform(action);
+                }
+                Task::none()
+            }
+            Message::SaveToFileClicked => Task::perform(save::pick_file(), Message::FilePicked),
+            Message::FilePicked(path) => match (path, self.settings()) {
+                (Some(path), Ok(settings)) => self.save_to_file(settings, path),
+                _ => Task::none(),
+            },
+            Message::SaveProgress(SaveEvent::Written(written)) => {
+                self.save_written = written;
+                Task::none()
+            }
+            Message::SaveProgress(SaveEvent::Finished(result)) => {
+                self.saving = None;
+                self.save_status = match result {
+                    Ok(written) => format!("Saved {} passwords", written),
+                    Err(e) => format!("Could not save the file: {}", e),
+                };
+                Task::none()
+            }
+            Message::CancelSaveClicked => {
+                if let Some((handle, cancelled)) = self.saving.take() {
+                    cancelled.store(true, Ordering::Relaxed);
+                    handle.abort();
+                    self.save_status = String::from("Saving cancelled");
+                }
+                Task::none()
+            }
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::wordlist;
+use crate::random::SecureRandom;
+
+pub const MAX_LENGTH: usize = 1024;
+pub const MAX_WORDS: usize = 64;
+
+// How many passwords are written between progress reports and cancellation checks.
+const PROGRESS_STEP: u64 = 10_000;
+
+// Characters that are easily mistaken for one another in many fonts.
+pub const AMBIGUOUS: &str = "0Oo1lI|`'\"";
+const CONSONANTS: &str = "bcdfghjklmnpqrstvwxz";
+const VOWELS: &str = "aeiou";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Random,
+    Pronounceable,
+    Passphrase,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 3] = [Mode::Random, Mode::Pronounceable, Mode::Passphrase];
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Mode::Random => "Random characters",
+                Mode::Pronounceable => "Pronounceable",
+                Mode::Passphrase => "Diceware passphrase",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterClass {
+    Lowercase,
+    Uppercase,
+    Digits,
+    Symbols,
+}
+
+impl CharacterClass {
+    pub const ALL: [CharacterClass; 4] = [
+        CharacterClass::Lowercase,
+        CharacterClass::Uppercase,
+        CharacterClass::Digits,
+        CharacterClass::Symbols,
+    ];
+
+    fn characters(&self) -> &'static str {
+        match self {
+            CharacterClass::Lowercase => "abcdefghijklmnopqrstuvwxyz",
+            CharacterClass::Uppercase => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            CharacterClass::Digits => "0123456789",
+            CharacterClass::Symbols => "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~",
+        }
+    }
+}
+
+impl std::fmt::Display for CharacterClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                CharacterClass::Lowercase => "Lowercase (a-z)",
+                CharacterClass::Uppercase => "Uppercase (A-Z)",
+                CharacterClass::Digits => "Digits (0-9)",
+                CharacterClass::Symbols => "Symbols (!@#...)",
+            }
+        )
+    }
+}
+
+/// Whether a character class is used and how many of its characters every password needs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClassOptions {
+    pub is_enabled: bool,
+    pub minimum: usize,
+}
+
+/// Everything needed to generate a batch of passwords. Random and pronounceable passwords use
+/// the length and character classes, passphrases the word options.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub mode: Mode,
+    pub length: usize,
+    pub classes: [ClassOptions; 4],
+    pub exclude_ambiguous: bool,
+    pub words: usize,
+    pub separator: String,
+    pub capitalize: bool,
+    pub amount: u64,
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.mode == Mode::Passphrase {
+            if self.words == 0 || self.words > MAX_WORDS {
+                return Err(format!("Words must be between 1 and {}", MAX_WORDS));
+            }
+            return Ok(());
+        }
+        if self.length == 0 || self.length > MAX_LENGTH {
+            return Err(format!("Length must be between 1 and {}", MAX_LENGTH));
+        }
+        if self.mode == Mode::Random && self.classes.iter().all(|class| !class.is_enabled) {
+            return Err(String::from("Choose at least one character class"));
+        }
+        let required: usize = CharacterClass::ALL
+            .iter()
+            .map(|class| self.minimum(*class))
+            .sum();
+        if required > self.length {
+            return Err(format!(
+                "{} required characters don't fit in a length of {}",
+                required, self.length
+            ));
+        }
+        Ok(())
+    }
+
+    /// The entropy of a single password in bits, assuming an attacker knows these settings.
+    /// For random passwords the extra entropy from shuffling the required characters into
+    /// place is left out, so the estimate errs on the low side.
+    pub fn entropy(&self) -> f64 {
+        let alphabets = self.alphabets();
+        let required = |classes: &[CharacterClass]| -> f64 {
+            classes
+                .iter()
+                .map(|class| {
+                    self.minimum(*class) as f64 * bits(alphabets.classes[*class as usize].len())
+                })
+                .sum()
+        };
+        match self.mode {
+            Mode::Random => {
+                let rest = self.length
+                    - CharacterClass::ALL
+                        .iter()
+                        .map(|class| self.minimum(*class))
+                        .sum::<usize>();
+                required(&CharacterClass::ALL) + rest as f64 * bits(alphabets.pool.len())
+            }
+            Mode::Pronounceable => {
+                let letters = self.pronounceable_letters();
+                let syllables = letters.div_ceil(2) as f64 * bits(alphabets.consonants.len())
+                    + (letters / 2) as f64 * bits(alphabets.vowels.len());
+                // Which of the letters are capitalized.
+                let capitals: f64 = (0..self.minimum(CharacterClass::Uppercase))
+                    .map(|i| ((letters - i) as f64 / (i + 1) as f64).log2())
+                    .sum();
+                syllables + capitals + required(&[CharacterClass::Digits, CharacterClass::Symbols])
+            }
+            Mode::Passphrase => self.words as f64 * bits(wordlist::words().len()),
+        }
+    }
+
+    fn minimum(&self, class: CharacterClass) -> usize {
+        match self.classes[class as usize] {
+            ClassOptions {
+                is_enabled: true,
+                minimum,
+            } => minimum,
+            _ => 0,
+        }
+    }
+
+    fn alphabets(&self) -> Alphabets {
+        let is_allowed = |c: char| !self.exclude_ambiguous || !AMBIGUOUS.contains(c);
+        let classes = CharacterClass::ALL.map(|class| {
+            class
+                .characters()
+                .chars()
+                .filter(|c| is_allowed(*c))
+                .collect::<Vec<_>>()
+        });
+        let pool = CharacterClass::ALL
+            .iter()
+            .filter(|class| self.classes[**class as usize].is_enabled)
+            .flat_map(|class| classes[*class as usize].iter().copied())
+            .collect();
+        // Letters of pronounceable passwords may be capitalized, so a letter is only kept when
+        // both of its cases are unambiguous.
+        let syllable = |letters: &str| {
+            letters
+                .chars()
+                .filter(|c| is_allowed(*c) && is_allowed(c.to_ascii_uppercase()))
+                .collect()
+        };
+        Alphabets {
+            classes,
+            pool,
+            consonants: syllable(CONSONANTS),
+            vowels: syllable(VOWELS),
+        }
+    }
+
+    fn pronounceable_letters(&self) -> usize {
+        self.length
+            .saturating_sub(self.minimum(CharacterClass::Digits))
+            .saturating_sub(self.minimum(CharacterClass::Symbols))
+    }
+
+    fn push_required(
+        &self,
+        password: &mut Vec<char>,
+        alphabets: &Alphabets,
+        class: CharacterClass,
+        random: &mut SecureRandom,
+    ) {
+        let alphabet = &alphabets.classes[class as usize];
+        password.extend((0..self.minimum(class)).filter_map(|_| alphabet.choose(random)));
+    }
+
+    fn generate(&self, alphabets: &Alphabets, random: &mut SecureRandom) -> String {
+        match self.mode {
+            Mode::Random => {
+                let mut password: Vec<char> = Vec::with_capacity(self.length);
+                for class in CharacterClass::ALL {
+                    self.push_required(&mut password, alphabets, class, random);
+                }
+                while password.len() < self.length {
+                    password.extend(alphabets.pool.choose(random));
+                }
+                password.shuffle(random);
+                password.into_iter().collect()
+            }
+            // Alternating consonants and vowels, with the required capitals placed at random
+            // and the required digits and symbols at the end.
+            Mode::Pronounceable => {
+                let letters = self.pronounceable_letters();
+                let mut password: Vec<char> = (0..letters)
+                    .filter_map(|i| match i % 2 {
+                        0 => alphabets.consonants.choose(random).copied(),
+                        _ => alphabets.vowels.choose(random).copied(),
+                    })
+                    .collect();
+                let capitals = self.minimum(CharacterClass::Uppercase);
+                for i in rand::seq::index::sample(random, letters, capitals) {
+                    password[i] = password[i].to_ascii_uppercase();
+                }
+                self.push_required(&mut password, alphabets, CharacterClass::Digits, random);
+                self.push_required(&mut password, alphabets, CharacterClass::Symbols, random);
+                password.into_iter().collect()
+            }
+            Mode::Passphrase => {
+                let words = wordlist::words();
+                (0..self.words)
+                    .map(|_| {
+                        let word = words[random.gen_range(0..words.len())];
+                        match self.capitalize {
+                            true => capitalize(word),
+                            false => word.to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(&self.separator)
+            }
+        }
+    }
+}
+
+/// The characters each class, and the password as a whole, draws from once ambiguous ones
+/// are excluded.
+struct Alphabets {
+    classes: [Vec<char>; 4],
+    pool: Vec<char>,
+    consonants: Vec<char>,
+    vowels: Vec<char>,
+}
+
+/// A rough rating of an entropy in bits.
+pub fn strength(entropy: f64) -> &'static str {
+    match entropy {
+        e if e < 40.0 => "weak",
+        e if e < 60.0 => "fair",
+        e if e < 80.0 => "strong",
+        _ => "very strong",
+    }
+}
+
+fn bits(choices: usize) -> f64 {
+    (choices.max(1) as f64).log2()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub fn render(settings: &Settings) -> String {
+    let mut output = Vec::new();
+    match write(settings, &mut output, &AtomicBool::new(false), |_| ()) {
+        Ok(_) => String::from_utf8_lossy(&output).into_owned(),
+        Err(e) => e,
+    }
+}
+
+/// Streams the passwords into `writer`, one per line, calling `progress` with the number
+/// written so far. Checks `cancelled` every few thousand passwords and stops once it's set.
+pub fn write(
+    settings: &Settings,
+    writer: impl Write,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(u64),
+) -> Result<u64, String> {
+    let mut writer = BufWriter::new(writer);
+    let written = write_passwords(settings, &mut writer, cancelled, &mut progress)
+        .and_then(|written| writer.flush().map(|_| written))
+        .map_err(|e| e.to_string())?;
+    progress(written);
+    Ok(written)
+}
+
+fn write_passwords(
+    settings: &Settings,
+    writer: &mut impl Write,
+    cancelled: &AtomicBool,
+    progress: &mut impl FnMut(u64),
+) -> io::Result<u64> {
+    let alphabets = settings.alphabets();
+    let mut random = SecureRandom::new();
+    for written in 0..settings.amount {
+        if written > 0 {
+            writer.write_all(b"\n")?;
+        }
+        writer.write_all(settings.generate(&alphabets, &mut random).as_bytes())?;
+        if (written + 1) % PROGRESS_STEP == 0 {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "Saving cancelled",
+                ));
+            }
+            progress(written + 1);
+        }
+    }
+    Ok(settings.amount)
+}
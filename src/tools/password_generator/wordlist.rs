@@ -0,0 +1,17 @@
+use std::sync::OnceLock;
+
+// The EFF large wordlist for five-dice diceware: 7776 words, each on a line after its dice
+// roll and a tab.
+const WORDLIST: &str = include_str!("eff_large_wordlist.txt");
+
+static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+pub fn words() -> &'static [&'static str] {
+    WORDS.get_or_init(|| {
+        WORDLIST
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(_, word)| word)
+            .collect()
+    })
+}
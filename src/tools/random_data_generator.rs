@@ -1,26 +1,519 @@
-use iced::{widget::text, Element};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use iced::task::Handle;
+use iced::{
+    widget::{
+        button, column, container, horizontal_space, pick_list, progress_bar, row, scrollable,
+        text, text_editor, text_input, Column,
+    },
+    Color, Element, Length, Task,
+};
+
+use crate::save::{self, SaveEvent};
+use export::{Export, ExportFormat, SqlDialect};
+use field::{Field, FieldKind};
+use generate::{Schema, Settings};
+use json_schema::JsonSchema;
+use locale::Locale;
+
+mod export;
+mod field;
+mod generate;
+mod json_schema;
+mod locale;
 
 pub const NAME: &str = "Random Data Generator";
 
+const ERROR_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+const WARNING_COLOR: Color = Color::from_rgb(0.8, 0.45, 0.0);
+const PREVIEW_ROWS: u64 = 100;
+
+/// A field as entered in the UI, validated into a `Field` on every change.
+#[derive(Debug, Clone)]
+struct FieldRow {
+    name: String,
+    kind: Option<FieldKind>,
+    options: Vec<String>,
+}
+
+impl FieldRow {
+    fn new(name: &str, kind: FieldKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: Some(kind),
+            options: kind
+                .options()
+                .iter()
+                .map(|(_, default)| default.to_string())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SourceSelected(Source),
+    JsonInputActionPerformed(text_editor::Action),
+    FieldNameChanged(usize, String),
+    FieldKindSelected(usize, FieldKind),
+    FieldOptionChanged(usize, usize, String),
+    AddFieldClicked,
+    RemoveFieldClicked(usize),
+    RowsChanged(String),
+    LocaleSelected(Locale),
+    SeedChanged(String),
+    RegenerateClicked,
+    PreviewActionPerformed(text_editor::Action),
+    ExportFormatSelected(ExportFormat),
+    SqlDialectSelected(SqlDialect),
+    TableChanged(String),
+    SaveToFileClicked,
+    FilePicked(Option<PathBuf>),
+    SaveProgress(SaveEvent),
+    CancelSaveClicked,
+}
+
 pub struct RandomDataGenerator {
     tool_name: String,
+    selected_source: Option<Source>,
+    fields: Vec<FieldRow>,
+    json_input: text_editor::Content,
+    raw_rows: String,
+    locale: Option<Locale>,
+    raw_seed: String,
+    selected_format: Option<ExportFormat>,
+    selected_dialect: Option<SqlDialect>,
+    table: String,
+    preview: text_editor::Content,
+    error_text: Option<String>,
+    saving: Option<(Handle, Arc<AtomicBool>)>,
+    save_total: u64,
+    save_written: u64,
+    save_status: String,
 }
 
-#[derive(Debug, Clone)]
-pub enum Message {}
+impl Default for RandomDataGenerator {
+    fn default() -> Self {
+        RandomDataGenerator::new()
+    }
+}
 
 impl RandomDataGenerator {
     pub fn new() -> Self {
-        Self {
+        let mut generator = Self {
             tool_name: NAME.to_string(),
-        }
+            selected_source: Some(Source::default()),
+            fields: vec![
+                FieldRow::new("id", FieldKind::Uuid),
+                FieldRow::new("name", FieldKind::FullName),
+                FieldRow::new("email", FieldKind::Email),
+                FieldRow::new("age", FieldKind::Integer),
+                FieldRow::new("signed_up", FieldKind::Date),
+                FieldRow::new("active", FieldKind::Boolean),
+            ],
+            json_input: text_editor::Content::new(),
+            raw_rows: String::from("10"),
+            locale: Some(Locale::default()),
+            raw_seed: String::new(),
+            selected_format: Some(ExportFormat::default()),
+            selected_dialect: Some(SqlDialect::default()),
+            table: String::from("users"),
+            preview: text_editor::Content::new(),
+            error_text: None,
+            saving: None,
+            save_total: 0,
+            save_written: 0,
+            save_status: String::new(),
+        };
+        generator.generate_preview();
+        generator
     }
 
     pub fn title(&self) -> String {
         self.tool_name.clone()
     }
 
-    pub fn view(&self) -> Element<Message> {
-        text("Test").into()
+    fn fields(&self) -> Result<Vec<Field>, String> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|row| {
+                Field::new(
+                    &row.name,
+                    row.kind.unwrap_or(FieldKind::FullName),
+                    &row.options,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if fields.is_empty() {
+            return Err(String::from("Add at least one field"));
+        }
+        if let Some(field) = fields
+            .iter()
+            .enumerate()
+            .find_map(|(i, field)| fields[..i].iter().find(|f| f.name == field.name))
+        {
+            return Err(format!(
+                "There is more than one field named '{}'",
+                field.name
+            ));
+        }
+        Ok(fields)
+    }
+
+    fn schema(&self) -> Result<Schema, String> {
+        let source = self.selected_source.unwrap_or_default();
+        if source == Source::Fields {
+            return Ok(Schema::Fields(self.fields()?));
+        }
+        let input = self.json_input.text();
+        if input.trim().is_empty() {
+            return Err(match source {
+                Source::JsonSample => String::from("Paste an example JSON document"),
+                _ => String::from("Paste a JSON Schema"),
+            });
+        }
+        let document = serde_json::from_str::<serde_json::Value>(&input)
+            .map_err(|e| format!("Invalid JSON: {}", e))?;
+        Ok(Schema::Json(match source {
+            Source::JsonSample => JsonSchema::from_sample(&document),
+            _ => JsonSchema::from_schema(document)?,
+        }))
+    }
+
+    fn rows(&self) -> Result<u64, String> {
+        match self.raw_rows.trim().parse::<u64>() {
+            Ok(rows) if rows > 0 => Ok(rows),
+            _ => Err(format!("Cannot parse row count '{}'", self.raw_rows)),
+        }
+    }
+
+    fn seed(&self) -> Option<&str> {
+        Some(self.raw_seed.as_str()).filter(|seed| !seed.is_empty())
+    }
+
+    fn settings(&self) -> Result<Settings, String> {
+        let export = Export {
+            format: self.selected_format.unwrap_or_default(),
+            dialect: self.selected_dialect.unwrap_or_default(),
+            table: self.table.clone(),
+        };
+        let settings = Settings {
+            schema: self.schema()?,
+            rows: self.rows()?,
+            locale: self.locale.unwrap_or_default(),
+            seed: self.seed().map(String::from),
+            export,
+        };
+        settings.export.validate(settings.columns().as_deref())?;
+        Ok(settings)
+    }
+
+    fn generate_preview(&mut self) {
+        match self.settings() {
+            Ok(settings) => {
+                self.preview =
+                    text_editor::Content::with_text(&generate::render(&settings, PREVIEW_ROWS));
+                self.error_text = None;
+            }
+            Err(e) => self.error_text = Some(e),
+        }
+    }
+
+    fn can_export(&self) -> bool {
+        self.saving.is_none() && self.error_text.is_none()
+    }
+
+    fn save_to_file(&mut self, settings: Settings, path: PathBuf) -> Task<Message> {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.save_total = settings.rows;
+        self.save_written = 0;
+        self.save_status = String::new();
+        let (task, handle) = Task::run(
+            save::save_in_background(path, cancelled.clone(), move |file, cancelled, progress| {
+                generate::write(&settings, file, cancelled, progress)
+            }),
+            Message::SaveProgress,
+        )
+        .abortable();
+        self.saving = Some((handle, cancelled));
+        task
+    }
+
+    fn export_options(&self) -> Element<'_, Message> {
+        let mut options = row![
+            "Format: ",
+            pick_list(
+                &ExportFormat::ALL[..],
+                self.selected_format,
+                Message::ExportFormatSelected
+            ),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+        if self.selected_format == Some(ExportFormat::Sql) {
+            options = options
+                .push("Dialect: ")
+                .push(pick_list(
+                    &SqlDialect::ALL[..],
+                    self.selected_dialect,
+                    Message::SqlDialectSelected,
+                ))
+                .push("Table: ")
+                .push(
+                    text_input("Table name", &self.table)
+                        .on_input(Message::TableChanged)
+                        .width(200),
+                );
+        }
+        options
+            .push(horizontal_space())
+            .push(
+                button("Save to file").on_press_maybe(match self.can_export() {
+                    true => Some(Message::SaveToFileClicked),
+                    false => None,
+                }),
+            )
+            .into()
+    }
+
+    fn save_view(&self) -> Element<'_, Message> {
+        match self.saving {
+            Some(_) => row![
+                progress_bar(0.0..=self.save_total as f32, self.save_written as f32)
+                    .height(20)
+                    .width(300),
+                text(format!("{} / {}", self.save_written, self.save_total)),
+                button("Cancel").on_press(Message::CancelSaveClicked),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into(),
+            None => text(self.save_status.as_str()).into(),
+        }
+    }
+
+    fn field_view(&self, index: usize, field: &FieldRow) -> Element<'_, Message> {
+        let kind = field.kind.unwrap_or(FieldKind::FullName);
+        let mut row = row![
+            text_input("Field name", &field.name)
+                .on_input(move |name| Message::FieldNameChanged(index, name))
+                .width(160),
+            pick_list(&FieldKind::ALL[..], field.kind, move |kind| {
+                Message::FieldKindSelected(index, kind)
+            })
+            .width(160),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+        for (option, (label, _)) in kind.options().iter().enumerate() {
+            let value = field.options.get(option).map(String::as_str).unwrap_or("");
+            row = row.push(text(*label)).push(
+                text_input(label, value)
+                    .on_input(move |value| Message::FieldOptionChanged(index, option, value))
+                    .width(Length::FillPortion(1)),
+            );
+        }
+        row.push(horizontal_space())
+            .push(button("Remove").on_press(Message::RemoveFieldClicked(index)))
+            .into()
+    }
+
+    fn schema_view(&self) -> Element<'_, Message> {
+        let placeholder = match self.selected_source.unwrap_or_default() {
+            Source::Fields => {
+                let fields = self
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .fold(Column::new().spacing(5), |column, (index, field)| {
+                        column.push(self.field_view(index, field))
+                    });
+                return column![
+                    container(scrollable(fields)).max_height(300),
+                    button("Add field").on_press(Message::AddFieldClicked),
+                ]
+                .spacing(10)
+                .into();
+            }
+            Source::JsonSchema => "Paste a JSON Schema",
+            Source::JsonSample => "Paste an example document, or an array of them",
+        };
+        text_editor(&self.json_input)
+            .placeholder(placeholder)
+            .on_action(Message::JsonInputActionPerformed)
+            .height(250)
+            .into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let configuration = row![
+            "Source: ",
+            pick_list(
+                &Source::ALL[..],
+                self.selected_source,
+                Message::SourceSelected
+            ),
+            "Rows: ",
+            text_input("Rows", &self.raw_rows)
+                .on_input(Message::RowsChanged)
+                .width(100),
+            "Locale: ",
+            pick_list(&Locale::ALL[..], self.locale, Message::LocaleSelected),
+            "Seed: ",
+            text_input("Optional, makes the output reproducible", &self.raw_seed)
+                .on_input(Message::SeedChanged)
+                .width(300),
+            button("Regenerate").on_press(Message::RegenerateClicked),
+            horizontal_space(),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let seed_warning = match self.seed() {
+            Some(_) => text(
+                "Not secure: anyone who knows the seed can reproduce this data, so never use it for secrets.",
+            )
+            .color(WARNING_COLOR),
+            None => text(""),
+        };
+
+        let preview_title = match self.rows() {
+            Ok(rows) if rows > PREVIEW_ROWS => {
+                format!("Preview (first {} of {} records)", PREVIEW_ROWS, rows)
+            }
+            _ => String::from("Preview"),
+        };
+
+        let content = column![
+            configuration,
+            seed_warning,
+            self.schema_view(),
+            text(self.error_text.clone().unwrap_or_default()).color(ERROR_COLOR),
+            self.export_options(),
+            self.save_view(),
+            text(preview_title),
+            container(scrollable(
+                text_editor(&self.preview).on_action(Message::PreviewActionPerformed)
+            ))
+            .height(Length::Fill),
+        ]
+        .spacing(10);
+
+        container(content).padding(10).into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::SourceSelected(source) => self.selected_source = Some(source),
+            Message::JsonInputActionPerformed(action) => {
+                let is_edit = action.is_edit();
+                self.json_input.perform(action);
+                if !is_edit {
+                    return Task::none();
+                }
+            }
+            Message::FieldNameChanged(index, name) => {
+                if let Some(field) = self.fields.get_mut(index) {
+                    field.name = name;
+                }
+            }
+            Message::FieldKindSelected(index, kind) => {
+                if let Some(field) = self.fields.get_mut(index) {
+                    *field = FieldRow::new(&field.name, kind);
+                }
+            }
+            Message::FieldOptionChanged(index, option, value) => {
+                if let Some(slot) = self
+                    .fields
+                    .get_mut(index)
+                    .and_then(|field| field.options.get_mut(option))
+                {
+                    *slot = value;
+                }
+            }
+            Message::AddFieldClicked => {
+                let name = format!("field_{}", self.fields.len() + 1);
+                self.fields.push(FieldRow::new(&name, FieldKind::Lorem));
+            }
+            Message::RemoveFieldClicked(index) => {
+                if index < self.fields.len() {
+                    self.fields.remove(index);
+                }
+            }
+            Message::RowsChanged(value) => self.raw_rows = value,
+            Message::LocaleSelected(locale) => self.locale = Some(locale),
+            Message::SeedChanged(value) => self.raw_seed = value,
+            Message::RegenerateClicked => (),
+            Message::PreviewActionPerformed(action) => {
+                // The preview is generated, so only allow selecting and copying it.
+                if !action.is_edit() {
+                    self.preview.perform(action);
+                }
+                return Task::none();
+            }
+            Message::ExportFormatSelected(format) => self.selected_format = Some(format),
+            Message::SqlDialectSelected(dialect) => self.selected_dialect = Some(dialect),
+            Message::TableChanged(value) => self.table = value,
+            Message::SaveToFileClicked => {
+                return Task::perform(save::pick_file(), Message::FilePicked);
+            }
+            Message::FilePicked(path) => {
+                return match (path, self.settings()) {
+                    (Some(path), Ok(settings)) => self.save_to_file(settings, path),
+                    _ => Task::none(),
+                };
+            }
+            Message::SaveProgress(SaveEvent::Written(written)) => {
+                self.save_written = written;
+                return Task::none();
+            }
+            Message::SaveProgress(SaveEvent::Finished(result)) => {
+                self.saving = None;
+                self.save_status = match result {
+                    Ok(written) => format!("Saved {} records", written),
+                    Err(e) => format!("Could not save the file: {}", e),
+                };
+                return Task::none();
+            }
+            Message::CancelSaveClicked => {
+                if let Some((handle, cancelled)) = self.saving.take() {
+                    cancelled.store(true, Ordering::Relaxed);
+                    handle.abort();
+                    self.save_status = String::from("Saving cancelled");
+                }
+                return Task::none();
+            }
+        }
+        self.generate_preview();
+        Task::none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Source {
+    #[default]
+    Fields,
+    JsonSchema,
+    JsonSample,
+}
+
+impl Source {
+    const ALL: [Source; 3] = [Source::Fields, Source::JsonSchema, Source::JsonSample];
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Source::Fields => "Fields",
+                Source::JsonSchema => "JSON Schema",
+                Source::JsonSample => "JSON sample",
+            }
+        )
     }
 }
@@ -0,0 +1,308 @@
+use std::io::{self, Write};
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    JsonArray,
+    Ndjson,
+    Csv,
+    Sql,
+    Yaml,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 5] = [
+        ExportFormat::JsonArray,
+        ExportFormat::Ndjson,
+        ExportFormat::Csv,
+        ExportFormat::Sql,
+        ExportFormat::Yaml,
+    ];
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ExportFormat::JsonArray => "JSON array",
+                ExportFormat::Ndjson => "NDJSON",
+                ExportFormat::Csv => "CSV",
+                ExportFormat::Sql => "SQL INSERT",
+                ExportFormat::Yaml => "YAML",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqlDialect {
+    #[default]
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    pub const ALL: [SqlDialect; 3] = [SqlDialect::Postgres, SqlDialect::MySql, SqlDialect::Sqlite];
+
+    fn identifier(&self, name: &str) -> String {
+        match self {
+            SqlDialect::MySql => format!("`{}`", name.replace('`', "``")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => {
+                format!("\"{}\"", name.replace('"', "\"\""))
+            }
+        }
+    }
+
+    fn literal(&self, value: &Value) -> String {
+        match value {
+            Value::Null => String::from("NULL"),
+            Value::Bool(value) => match self {
+                // SQLite only learned TRUE and FALSE in 3.23, so stick to the integers.
+                SqlDialect::Sqlite => String::from(if *value { "1" } else { "0" }),
+                SqlDialect::Postgres | SqlDialect::MySql => {
+                    String::from(if *value { "TRUE" } else { "FALSE" })
+                }
+            },
+            Value::Number(number) => number.to_string(),
+            Value::String(text) => self.string(text),
+            value => self.string(&value.to_string()),
+        }
+    }
+
+    fn string(&self, text: &str) -> String {
+        let escaped = text.replace('\'', "''");
+        match self {
+            // MySQL treats backslashes in string literals as escapes by default.
+            SqlDialect::MySql => format!("'{}'", escaped.replace('\\', "\\\\")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => format!("'{}'", escaped),
+        }
+    }
+}
+
+impl std::fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SqlDialect::Postgres => "PostgreSQL",
+                SqlDialect::MySql => "MySQL",
+                SqlDialect::Sqlite => "SQLite",
+            }
+        )
+    }
+}
+
+/// How generated records are written out. `columns` are the top-level keys of the records, in
+/// order; CSV and SQL write one column per key.
+#[derive(Debug, Clone, Default)]
+pub struct Export {
+    pub format: ExportFormat,
+    pub dialect: SqlDialect,
+    pub table: String,
+}
+
+impl Export {
+    /// Checks the export can write records with the given top-level keys, `None` meaning the
+    /// records aren't objects.
+    pub fn validate(&self, columns: Option<&[String]>) -> Result<(), String> {
+        if matches!(self.format, ExportFormat::Csv | ExportFormat::Sql) && columns.is_none() {
+            return Err(format!(
+                "{} needs records that are objects, so give the schema an object type",
+                self.format
+            ));
+        }
+        if self.format == ExportFormat::Sql && self.table().is_empty() {
+            return Err(String::from("Enter a table name for the INSERT statements"));
+        }
+        Ok(())
+    }
+
+    pub fn header(&self, columns: &[String]) -> Option<String> {
+        match self.format {
+            ExportFormat::JsonArray => Some(String::from("[")),
+            ExportFormat::Csv => Some(
+                columns
+                    .iter()
+                    .map(|column| csv_field(column))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            ExportFormat::Ndjson | ExportFormat::Sql | ExportFormat::Yaml => None,
+        }
+    }
+
+    pub fn write_record(
+        &self,
+        writer: &mut impl Write,
+        columns: &[String],
+        record: &Value,
+    ) -> io::Result<()> {
+        match self.format {
+            ExportFormat::JsonArray => {
+                writer.write_all(b"  ")?;
+                serde_json::to_writer(&mut *writer, record)?;
+            }
+            ExportFormat::Ndjson => serde_json::to_writer(&mut *writer, record)?,
+            ExportFormat::Csv => {
+                for (i, column) in columns.iter().enumerate() {
+                    if i > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    writer.write_all(csv_field(&csv_text(cell(record, column))).as_bytes())?;
+                }
+            }
+            ExportFormat::Sql => {
+                let table = self
+                    .table()
+                    .split('.')
+                    .map(|part| self.dialect.identifier(part.trim()))
+                    .collect::<Vec<_>>()
+                    .join(".");
+                let names = columns
+                    .iter()
+                    .map(|column| self.dialect.identifier(column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let values = columns
+                    .iter()
+                    .map(|column| self.dialect.literal(cell(record, column)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    writer,
+                    "INSERT INTO {} ({}) VALUES ({});",
+                    table, names, values
+                )?;
+            }
+            ExportFormat::Yaml => {
+                writer.write_all(b"-")?;
+                yaml_entry(writer, record, 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn separator(&self) -> &'static str {
+        match self.format {
+            ExportFormat::JsonArray => ",\n",
+            _ => "\n",
+        }
+    }
+
+    pub fn footer(&self) -> Option<&'static str> {
+        match self.format {
+            ExportFormat::JsonArray => Some("]"),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> &str {
+        self.table.trim()
+    }
+}
+
+fn cell<'a>(record: &'a Value, column: &str) -> &'a Value {
+    record.get(column).unwrap_or(&Value::Null)
+}
+
+fn csv_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// YAML is written in block style, one entry per line. Strings are double-quoted the way JSON
+// quotes them, which YAML reads back unchanged.
+
+/// Writes the value of an entry whose `key:` or `-` is already on the line.
+fn yaml_entry(writer: &mut impl Write, value: &Value, indent: usize) -> io::Result<()> {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            writer.write_all(b" ")?;
+            yaml_mapping(writer, map, indent + 2, true)
+        }
+        Value::Array(items) if !items.is_empty() => {
+            writer.write_all(b" ")?;
+            yaml_sequence(writer, items, indent + 2, true)
+        }
+        value => write!(writer, " {}", yaml_scalar(value)),
+    }
+}
+
+fn yaml_mapping(
+    writer: &mut impl Write,
+    map: &serde_json::Map<String, Value>,
+    indent: usize,
+    continues_line: bool,
+) -> io::Result<()> {
+    for (i, (key, value)) in map.iter().enumerate() {
+        if i > 0 || !continues_line {
+            write!(writer, "\n{:indent$}", "")?;
+        }
+        write!(writer, "{}:", yaml_key(key))?;
+        match value {
+            Value::Object(map) if !map.is_empty() => yaml_mapping(writer, map, indent + 2, false)?,
+            Value::Array(items) if !items.is_empty() => {
+                yaml_sequence(writer, items, indent + 2, false)?
+            }
+            value => write!(writer, " {}", yaml_scalar(value))?,
+        }
+    }
+    Ok(())
+}
+
+fn yaml_sequence(
+    writer: &mut impl Write,
+    items: &[Value],
+    indent: usize,
+    continues_line: bool,
+) -> io::Result<()> {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 || !continues_line {
+            write!(writer, "\n{:indent$}", "")?;
+        }
+        writer.write_all(b"-")?;
+        yaml_entry(writer, item, indent)?;
+    }
+    Ok(())
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Object(_) => String::from("{}"),
+        Value::Array(_) => String::from("[]"),
+        value => value.to_string(),
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    let is_plain = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(
+            key.to_ascii_lowercase().as_str(),
+            "true" | "false" | "null" | "yes" | "no" | "on" | "off" | "y" | "n"
+        );
+    match is_plain {
+        true => key.to_string(),
+        false => Value::from(key).to_string(),
+    }
+}
@@ -0,0 +1,314 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::NaiveDate;
+use rand::{seq::SliceRandom, Rng};
+use serde_json::{Map, Number, Value};
+use uuid::Builder;
+
+use super::locale::Locale;
+
+const LOREM: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    FullName,
+    FirstName,
+    LastName,
+    Email,
+    Phone,
+    Address,
+    City,
+    Date,
+    Integer,
+    Float,
+    Choice,
+    Lorem,
+    Uuid,
+    Ipv4,
+    Ipv6,
+    Boolean,
+}
+
+impl FieldKind {
+    pub const ALL: [FieldKind; 16] = [
+        FieldKind::FullName,
+        FieldKind::FirstName,
+        FieldKind::LastName,
+        FieldKind::Email,
+        FieldKind::Phone,
+        FieldKind::Address,
+        FieldKind::City,
+        FieldKind::Date,
+        FieldKind::Integer,
+        FieldKind::Float,
+        FieldKind::Choice,
+        FieldKind::Lorem,
+        FieldKind::Uuid,
+        FieldKind::Ipv4,
+        FieldKind::Ipv6,
+        FieldKind::Boolean,
+    ];
+
+    /// Labels and default values of the options the kind takes, if any.
+    pub fn options(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            FieldKind::Date => &[("From", "2000-01-01"), ("To", "2030-12-31")],
+            FieldKind::Integer => &[("Min", "0"), ("Max", "100")],
+            FieldKind::Float => &[("Min", "0"), ("Max", "1"), ("Decimals", "2")],
+            FieldKind::Choice => &[("Choices (comma separated)", "red, green, blue")],
+            FieldKind::Lorem => &[("Words", "8")],
+            FieldKind::Boolean => &[("Probability of true", "0.5")],
+            _ => &[],
+        }
+    }
+}
+
+impl std::fmt::Display for FieldKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FieldKind::FullName => "Full name",
+                FieldKind::FirstName => "First name",
+                FieldKind::LastName => "Last name",
+                FieldKind::Email => "Email",
+                FieldKind::Phone => "Phone",
+                FieldKind::Address => "Address",
+                FieldKind::City => "City",
+                FieldKind::Date => "Date",
+                FieldKind::Integer => "Integer",
+                FieldKind::Float => "Float",
+                FieldKind::Choice => "Choice",
+                FieldKind::Lorem => "Lorem ipsum",
+                FieldKind::Uuid => "UUID",
+                FieldKind::Ipv4 => "IPv4 address",
+                FieldKind::Ipv6 => "IPv6 address",
+                FieldKind::Boolean => "Boolean",
+            }
+        )
+    }
+}
+
+/// A validated field of the record schema.
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub name: String,
+    generator: Generator,
+}
+
+#[derive(Debug, Clone)]
+enum Generator {
+    Simple(FieldKind),
+    Date(NaiveDate, NaiveDate),
+    Integer(i64, i64),
+    Float(f64, f64, usize),
+    Choice(Vec<String>),
+    Lorem(usize),
+    Boolean(f64),
+}
+
+impl Field {
+    /// Validates the raw `options` entered for a field of `kind`, in the order of
+    /// `FieldKind::options`.
+    pub fn new(name: &str, kind: FieldKind, options: &[String]) -> Result<Self, String> {
+        if name.trim().is_empty() {
+            return Err(String::from("Every field needs a name"));
+        }
+        let option = |index: usize| options.get(index).map(|o| o.trim()).unwrap_or_default();
+        let generator = match kind {
+            FieldKind::Date => {
+                let from = parse_date(option(0))?;
+                let to = parse_date(option(1))?;
+                if from > to {
+                    return Err(format!("{}: the start date is after the end date", name));
+                }
+                Generator::Date(from, to)
+            }
+            FieldKind::Integer => {
+                let (min, max) = (parse(name, option(0))?, parse(name, option(1))?);
+                if min > max {
+                    return Err(format!("{}: the minimum is above the maximum", name));
+                }
+                Generator::Integer(min, max)
+            }
+            FieldKind::Float => {
+                let (min, max): (f64, f64) = (parse(name, option(0))?, parse(name, option(1))?);
+                if !(min.is_finite() && max.is_finite()) {
+                    return Err(format!("{}: the minimum and maximum must be finite", name));
+                }
+                if min > max {
+                    return Err(format!("{}: the minimum is above the maximum", name));
+                }
+                // Sampling needs the width of the range, which overflows for the widest ones.
+                if !(max - min).is_finite() {
+                    return Err(format!(
+                        "{}: the range is too wide, the maximum minus the minimum must be a finite number",
+                        name
+                    ));
+                }
+                Generator::Float(min, max, parse::<usize>(name, option(2))?.min(15))
+            }
+            FieldKind::Choice => {
+                let choices: Vec<String> = option(0)
+                    .split(',')
+                    .map(|choice| choice.trim().to_string())
+                    .filter(|choice| !choice.is_empty())
+                    .collect();
+                if choices.is_empty() {
+                    return Err(format!("{}: enter at least one choice", name));
+                }
+                Generator::Choice(choices)
+            }
+            FieldKind::Lorem => Generator::Lorem(parse::<usize>(name, option(0))?.min(1000)),
+            FieldKind::Boolean => {
+                let probability: f64 = parse(name, option(0))?;
+                if !(0.0..=1.0).contains(&probability) {
+                    return Err(format!("{}: the probability must be between 0 and 1", name));
+                }
+                Generator::Boolean(probability)
+            }
+            kind => Generator::Simple(kind),
+        };
+        Ok(Self {
+            name: name.trim().to_string(),
+            generator,
+        })
+    }
+
+    pub fn generate(&self, locale: Locale, rng: &mut impl Rng) -> Value {
+        match &self.generator {
+            Generator::Simple(kind) => Value::String(match kind {
+                FieldKind::FullName => {
+                    format!("{} {}", locale.first_name(rng), locale.last_name(rng))
+                }
+                FieldKind::FirstName => locale.first_name(rng).to_string(),
+                FieldKind::LastName => locale.last_name(rng).to_string(),
+                FieldKind::Email => locale.email(rng),
+                FieldKind::Phone => locale.phone(rng),
+                FieldKind::Address => locale.address(rng),
+                FieldKind::City => locale.city(rng).to_string(),
+                FieldKind::Uuid => Builder::from_random_bytes(rng.gen())
+                    .into_uuid()
+                    .to_string(),
+                FieldKind::Ipv4 => Ipv4Addr::from(rng.gen::<u32>()).to_string(),
+                FieldKind::Ipv6 => Ipv6Addr::from(rng.gen::<u128>()).to_string(),
+                _ => unreachable!("{} takes options", kind),
+            }),
+            Generator::Date(from, to) => {
+                let days = (*to - *from).num_days();
+                let date = *from + chrono::Duration::days(rng.gen_range(0..=days));
+                Value::String(date.format("%Y-%m-%d").to_string())
+            }
+            Generator::Integer(min, max) => Value::from(rng.gen_range(*min..=*max)),
+            Generator::Float(min, max, decimals) => {
+                let value = rng.gen_range(*min..=*max);
+                let factor = 10f64.powi(*decimals as i32);
+                // Values this large have no decimals left to round away.
+                let rounded = match (value * factor).is_finite() {
+                    true => (value * factor).round() / factor,
+                    false => value,
+                };
+                Number::from_f64(rounded)
+                    .map(Value::Number)
+                    .unwrap_or(Value::Null)
+            }
+            Generator::Choice(choices) => Value::String(
+                choices
+                    .choose(rng)
+                    .cloned()
+                    .expect("choices are never empty"),
+            ),
+            Generator::Lorem(words) => Value::String(
+                (0..*words)
+                    .map(|_| *LOREM.choose(rng).expect("the word list isn't empty"))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            Generator::Boolean(probability) => Value::Bool(rng.gen_bool(*probability)),
+        }
+    }
+}
+
+/// Generates one record with a value for every field, in schema order.
+pub fn record(fields: &[Field], locale: Locale, rng: &mut impl Rng) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|field| (field.name.clone(), field.generate(locale, rng)))
+        .collect()
+}
+
+fn parse<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{}: cannot parse '{}'", name, value))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("'{}' isn't a date like 2024-12-31", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: &[&str]) -> Vec<String> {
+        options.iter().map(|option| option.to_string()).collect()
+    }
+
+    #[test]
+    fn float_ranges_are_validated() {
+        let float = |min, max| Field::new("price", FieldKind::Float, &options(&[min, max, "2"]));
+        assert!(float("-1e308", "1e308")
+            .unwrap_err()
+            .contains("the range is too wide"));
+        assert!(float("inf", "1").unwrap_err().contains("must be finite"));
+        assert!(float("NaN", "1").unwrap_err().contains("must be finite"));
+        assert!(float("2", "1")
+            .unwrap_err()
+            .contains("the minimum is above the maximum"));
+
+        let field = float("-1e307", "1e307").unwrap();
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let value = field.generate(Locale::default(), &mut rng);
+            assert!(value.as_f64().is_some_and(|value| value.abs() <= 1e307));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::Value;
+
+use super::export::Export;
+use super::field::{self, Field};
+use super::json_schema::JsonSchema;
+use super::locale::Locale;
+use crate::random::Random;
+
+// How many records are written between progress reports and cancellation checks.
+const PROGRESS_STEP: u64 = 10_000;
+
+/// What the records look like: flat records with the fields defined in the UI, or documents
+/// matching a JSON Schema.
+#[derive(Debug, Clone)]
+pub enum Schema {
+    Fields(Vec<Field>),
+    Json(JsonSchema),
+}
+
+/// Everything needed to generate and export a batch of records.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub schema: Schema,
+    pub rows: u64,
+    pub locale: Locale,
+    pub seed: Option<String>,
+    pub export: Export,
+}
+
+impl Settings {
+    /// The top-level keys of the records, or `None` if they aren't objects.
+    pub fn columns(&self) -> Option<Vec<String>> {
+        match &self.schema {
+            Schema::Fields(fields) => Some(fields.iter().map(|field| field.name.clone()).collect()),
+            Schema::Json(schema) => schema.columns(),
+        }
+    }
+
+    fn record(&self, random: &mut Random) -> Value {
+        match &self.schema {
+            Schema::Fields(fields) => Value::Object(field::record(fields, self.locale, random)),
+            Schema::Json(schema) => schema.generate(self.locale, random),
+        }
+    }
+}
+
+/// Renders at most `limit` records, for previewing the export.
+pub fn render(settings: &Settings, limit: u64) -> String {
+    let mut output = Vec::new();
+    let _ = write_records(
+        settings,
+        &mut output,
+        settings.rows.min(limit),
+        &AtomicBool::new(false),
+        &mut |_| (),
+    );
+    String::from_utf8(output).unwrap_or_default()
+}
+
+/// Streams every record to `writer`, calling `progress` with the number of records written so
+/// far. Checks `cancelled` between records and stops with an error once it's set.
+pub fn write(
+    settings: &Settings,
+    writer: impl Write,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(u64),
+) -> Result<u64, String> {
+    let mut writer = BufWriter::new(writer);
+    let written = write_records(
+        settings,
+        &mut writer,
+        settings.rows,
+        cancelled,
+        &mut progress,
+    )
+    .and_then(|written| writer.flush().map(|_| written))
+    .map_err(|e| e.to_string())?;
+    progress(written);
+    Ok(written)
+}
+
+// The header, the records and the footer each go on their own line.
+fn write_records(
+    settings: &Settings,
+    writer: &mut impl Write,
+    rows: u64,
+    cancelled: &AtomicBool,
+    progress: &mut impl FnMut(u64),
+) -> io::Result<u64> {
+    let export = &settings.export;
+    let columns = settings.columns().unwrap_or_default();
+    let header = export.header(&columns);
+    if let Some(header) = &header {
+        writer.write_all(header.as_bytes())?;
+    }
+
+    let mut random = Random::new(settings.seed.as_deref());
+    for written in 0..rows {
+        match written {
+            0 if header.is_some() => writer.write_all(b"\n")?,
+            0 => (),
+            _ => writer.write_all(export.separator().as_bytes())?,
+        }
+        export.write_record(writer, &columns, &settings.record(&mut random))?;
+        if (written + 1) % PROGRESS_STEP == 0 {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "Saving cancelled",
+                ));
+            }
+            progress(written + 1);
+        }
+    }
+
+    if let Some(footer) = export.footer() {
+        if header.is_some() || rows > 0 {
+            writer.write_all(b"\n")?;
+        }
+        writer.write_all(footer.as_bytes())?;
+    }
+    Ok(rows)
+}
@@ -0,0 +1,519 @@
+use std::borrow::Cow;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{DateTime, NaiveDate};
+use rand::{seq::SliceRandom, Rng};
+use serde_json::{Map, Number, Value};
+use uuid::Builder;
+
+use super::locale::Locale;
+use crate::tools::json_beautifier::schema::{infer_value, merge};
+
+// Past this depth only required properties and the fewest allowed items are generated, so
+// recursive schemas like trees end.
+const NESTING_DEPTH: usize = 4;
+// Deeper schemas, usually recursive `$ref`s, generate `null` instead of recursing forever.
+const MAX_DEPTH: usize = 16;
+// Arrays without a `maxItems` get at most this many items more than their `minItems`.
+const EXTRA_ITEMS: u64 = 3;
+// `date` and `date-time` values fall between 2000-01-01 and 2030-12-31.
+const EARLIEST_SECOND: i64 = 946_684_800;
+const LATEST_SECOND: i64 = 1_924_991_999;
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "tempor",
+    "magna",
+    "aliqua",
+    "veniam",
+    "nostrud",
+    "commodo",
+];
+
+/// A JSON Schema that random documents are generated from. Supports `type`, `enum`, `const`,
+/// `format`, the numeric, length and item count limits, `required`, local `$ref`s and the
+/// `allOf`, `anyOf` and `oneOf` combinators; `pattern` and other keywords are ignored.
+#[derive(Debug, Clone)]
+pub struct JsonSchema {
+    root: Value,
+}
+
+impl JsonSchema {
+    pub fn from_schema(schema: Value) -> Result<Self, String> {
+        match schema {
+            Value::Object(_) | Value::Bool(_) => Ok(Self { root: schema }),
+            _ => Err(String::from("A JSON Schema must be an object or a boolean")),
+        }
+    }
+
+    /// Infers the schema of an example document. An array is taken as a list of examples, so
+    /// the documents look like its items.
+    pub fn from_sample(sample: &Value) -> Self {
+        let schema = match sample {
+            Value::Array(examples) if !examples.is_empty() => examples
+                .iter()
+                .map(infer_value)
+                .reduce(|a, b| merge(&a, &b))
+                .unwrap_or_default(),
+            sample => infer_value(sample),
+        };
+        Self {
+            root: Value::Object(schema),
+        }
+    }
+
+    /// The top-level property names, if the documents are objects.
+    pub fn columns(&self) -> Option<Vec<String>> {
+        let schema = flatten(&self.root, resolve(&self.root, &self.root)?.as_object()?);
+        let is_object = match schema.get("type") {
+            Some(Value::String(kind)) => kind == "object",
+            Some(Value::Array(kinds)) => kinds.contains(&Value::from("object")),
+            _ => schema.contains_key("properties"),
+        };
+        if !is_object {
+            return None;
+        }
+        let mut columns: Vec<String> = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| properties.keys().cloned().collect())
+            .unwrap_or_default();
+        for key in required(&schema) {
+            if !columns.iter().any(|column| column == key) {
+                columns.push(key.to_string());
+            }
+        }
+        Some(columns)
+    }
+
+    pub fn generate(&self, locale: Locale, rng: &mut impl Rng) -> Value {
+        Generator {
+            root: &self.root,
+            locale,
+            rng,
+        }
+        .value(&self.root, None, 0)
+    }
+}
+
+struct Generator<'a, R> {
+    root: &'a Value,
+    locale: Locale,
+    rng: &'a mut R,
+}
+
+impl<R: Rng> Generator<'_, R> {
+    fn value(&mut self, schema: &Value, name: Option<&str>, depth: usize) -> Value {
+        let schema = match resolve(self.root, schema) {
+            Some(Value::Object(schema)) if depth <= MAX_DEPTH => schema,
+            Some(Value::Bool(true)) if depth <= MAX_DEPTH => return Value::from(self.word()),
+            _ => return Value::Null,
+        };
+        let mut schema = flatten(self.root, schema);
+        for combinator in ["oneOf", "anyOf"] {
+            let option = schema
+                .get(combinator)
+                .and_then(Value::as_array)
+                .and_then(|options| options.choose(self.rng))
+                .cloned()
+                .unwrap_or_default();
+            if let Some(Value::Object(option)) = resolve(self.root, &option) {
+                let mut merged = schema.into_owned();
+                merged.remove(combinator);
+                schema = Cow::Owned(combine(&merged, &flatten(self.root, option)));
+            }
+        }
+
+        if let Some(value) = schema.get("const") {
+            return value.clone();
+        }
+        if let Some(value) = schema
+            .get("enum")
+            .and_then(Value::as_array)
+            .and_then(|choices| choices.choose(self.rng))
+        {
+            return value.clone();
+        }
+        match self.kind(&schema) {
+            "null" => Value::Null,
+            "boolean" => Value::Bool(self.rng.gen_bool(0.5)),
+            "integer" => self.integer(&schema),
+            "number" => self.number(&schema),
+            "array" => self.array(&schema, name, depth),
+            "object" => self.object(&schema, depth),
+            _ => Value::String(self.string(&schema, name)),
+        }
+    }
+
+    fn kind(&mut self, schema: &Map<String, Value>) -> &'static str {
+        let types: Vec<&str> = match schema.get("type") {
+            Some(Value::String(kind)) => vec![kind.as_str()],
+            Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        match types.choose(self.rng) {
+            Some(&"null") => "null",
+            Some(&"boolean") => "boolean",
+            Some(&"integer") => "integer",
+            Some(&"number") => "number",
+            Some(&"array") => "array",
+            Some(&"object") => "object",
+            Some(_) => "string",
+            // Without a type, guess it from the keywords that only apply to one.
+            None if ["properties", "required", "additionalProperties"]
+                .iter()
+                .any(|key| schema.contains_key(*key)) =>
+            {
+                "object"
+            }
+            None if ["items", "prefixItems", "minItems", "maxItems"]
+                .iter()
+                .any(|key| schema.contains_key(*key)) =>
+            {
+                "array"
+            }
+            None if ["minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum"]
+                .iter()
+                .any(|key| schema.contains_key(*key)) =>
+            {
+                "number"
+            }
+            None => "string",
+        }
+    }
+
+    fn string(&mut self, schema: &Map<String, Value>, name: Option<&str>) -> String {
+        let locale = self.locale;
+        match schema.get("format").and_then(Value::as_str) {
+            Some("email" | "idn-email") => return locale.email(self.rng),
+            Some("date-time") => return self.timestamp().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            Some("date") => return self.timestamp().format("%Y-%m-%d").to_string(),
+            Some("time") => return self.timestamp().format("%H:%M:%SZ").to_string(),
+            Some("uuid") => {
+                return Builder::from_random_bytes(self.rng.gen())
+                    .into_uuid()
+                    .to_string()
+            }
+            Some("uri" | "url" | "iri") => return format!("https://example.com/{}", self.word()),
+            Some("hostname" | "idn-hostname") => return format!("{}.example.com", self.word()),
+            Some("ipv4") => return Ipv4Addr::from(self.rng.gen::<u32>()).to_string(),
+            Some("ipv6") => return Ipv6Addr::from(self.rng.gen::<u128>()).to_string(),
+            _ => (),
+        }
+
+        let min_length = limit(schema, "minLength");
+        let max_length = limit(schema, "maxLength");
+        if min_length.is_none() && max_length.is_none() {
+            if let Some(text) = name.and_then(|name| self.by_name(name)) {
+                return text;
+            }
+        }
+        let min_length = min_length.unwrap_or(0) as usize;
+        let max_length = max_length
+            .map_or(min_length.max(8) + 24, |max| max as usize)
+            .min(min_length + 32);
+        let length = self.rng.gen_range(min_length..=max_length.max(min_length));
+        // The words are ASCII, so bytes and characters are the same length.
+        let mut text = String::with_capacity(length + 16);
+        while text.len() < length {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(self.word());
+        }
+        text.truncate(length);
+        if text.ends_with(' ') {
+            text.pop();
+            text.push('a');
+        }
+        text
+    }
+
+    /// Realistic values for strings whose property name says what they hold.
+    fn by_name(&mut self, name: &str) -> Option<String> {
+        let name = name.to_lowercase();
+        let locale = self.locale;
+        Some(if name.contains("email") {
+            locale.email(self.rng)
+        } else if name.contains("first") && name.contains("name") {
+            locale.first_name(self.rng).to_string()
+        } else if name.contains("last") && name.contains("name") || name.contains("surname") {
+            locale.last_name(self.rng).to_string()
+        } else if name == "name" || name.contains("full") && name.contains("name") {
+            format!(
+                "{} {}",
+                locale.first_name(self.rng),
+                locale.last_name(self.rng)
+            )
+        } else if name.contains("phone") {
+            locale.phone(self.rng)
+        } else if name.contains("city") {
+            locale.city(self.rng).to_string()
+        } else if name.contains("address") || name.contains("street") {
+            locale.address(self.rng)
+        } else {
+            return None;
+        })
+    }
+
+    fn integer(&mut self, schema: &Map<String, Value>) -> Value {
+        let (low, high) = bounds(schema);
+        let mut low = low.map(|(bound, exclusive)| match exclusive {
+            true => bound.floor() + 1.0,
+            false => bound.ceil(),
+        });
+        let mut high = high.map(|(bound, exclusive)| match exclusive {
+            true => bound.ceil() - 1.0,
+            false => bound.floor(),
+        });
+        let is_whole = |step: &f64| *step >= 1.0 && step.fract() == 0.0;
+        if let Some(step) = number(schema, "multipleOf").filter(is_whole) {
+            low = low.map(|low| (low / step).ceil());
+            high = high.map(|high| (high / step).floor());
+            let (low, high) = defaults(low, high, 100.0);
+            // Only multiples whose product still fits in an `i64` can be generated.
+            let limit = match step < i64::MAX as f64 {
+                true => i64::MAX / step as i64,
+                false => 0,
+            };
+            let low = (low as i64).clamp(-limit, limit);
+            let high = (high as i64).clamp(-limit, limit);
+            return Value::from(self.rng.gen_range(low..=high) * step as i64);
+        }
+        let (low, high) = defaults(low, high, 1000.0);
+        Value::from(self.rng.gen_range(low as i64..=high as i64))
+    }
+
+    fn number(&mut self, schema: &Map<String, Value>) -> Value {
+        if let Some(step) = number(schema, "multipleOf").filter(|step| *step > 0.0) {
+            let (low, high) = bounds(schema);
+            let low = low.map(|(bound, exclusive)| {
+                let multiple = (bound / step).ceil();
+                match exclusive && multiple * step <= bound {
+                    true => multiple + 1.0,
+                    false => multiple,
+                }
+            });
+            let high = high.map(|(bound, exclusive)| {
+                let multiple = (bound / step).floor();
+                match exclusive && multiple * step >= bound {
+                    true => multiple - 1.0,
+                    false => multiple,
+                }
+            });
+            let (low, high) = defaults(low, high, 100.0);
+            // Round away the floating point noise, to as many decimals as the step has.
+            let factor =
+                10f64.powi(step.to_string().split_once('.').map_or(0, |(_, d)| d.len()) as i32);
+            let multiple = self.rng.gen_range(low as i64..=high as i64) as f64 * step;
+            return Number::from_f64((multiple * factor).round() / factor)
+                .map_or(Value::Null, Value::Number);
+        }
+        let (low, high) = bounds(schema);
+        let (low, high) = defaults(low.map(|(low, _)| low), high.map(|(high, _)| high), 1000.0);
+        let value = self.rng.gen_range(low..=high);
+        let rounded = (value * 100.0).round() / 100.0;
+        // Rounding may land on an excluded bound or outside the range, so keep the exact value.
+        let in_range = |candidate: f64| match bounds(schema) {
+            (Some((low, true)), _) if candidate <= low => false,
+            (_, Some((high, true))) if candidate >= high => false,
+            (Some((low, _)), _) if candidate < low => false,
+            (_, Some((high, _))) if candidate > high => false,
+            _ => true,
+        };
+        let value = if in_range(rounded) { rounded } else { value };
+        Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+
+    fn array(&mut self, schema: &Map<String, Value>, name: Option<&str>, depth: usize) -> Value {
+        // Draft 2020-12 lists tuple items in `prefixItems`, earlier drafts in `items`.
+        let (prefix, rest) = match (schema.get("prefixItems"), schema.get("items")) {
+            (Some(Value::Array(prefix)), rest) => (prefix.as_slice(), rest),
+            (_, Some(Value::Array(prefix))) => (prefix.as_slice(), schema.get("additionalItems")),
+            (_, rest) => (&[][..], rest),
+        };
+        let min_items = limit(schema, "minItems").unwrap_or(0);
+        let max_items = limit(schema, "maxItems")
+            .unwrap_or(u64::MAX)
+            .min(min_items.max(1) + EXTRA_ITEMS)
+            .max(min_items);
+        let mut count = match depth < NESTING_DEPTH {
+            true => self.rng.gen_range(min_items..=max_items) as usize,
+            false => min_items as usize,
+        };
+        if rest == Some(&Value::Bool(false)) {
+            count = count.min(prefix.len());
+        }
+        let unique = schema.get("uniqueItems") == Some(&Value::Bool(true));
+        let any = Value::Bool(true);
+
+        let mut items: Vec<Value> = Vec::with_capacity(count);
+        for index in 0..count {
+            let item_schema = prefix.get(index).or(rest).unwrap_or(&any);
+            let mut item = self.value(item_schema, name, depth + 1);
+            // Retry a few times, as the schema may not allow enough distinct values.
+            for _ in 0..10 {
+                if !unique || !items.contains(&item) {
+                    break;
+                }
+                item = self.value(item_schema, name, depth + 1);
+            }
+            if unique && items.contains(&item) {
+                break;
+            }
+            items.push(item);
+        }
+        Value::Array(items)
+    }
+
+    fn object(&mut self, schema: &Map<String, Value>, depth: usize) -> Value {
+        let required = required(schema);
+        let mut object = Map::new();
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, property) in properties {
+                let is_optional = !required.contains(&key.as_str());
+                if !is_optional || depth < NESTING_DEPTH && self.rng.gen_bool(0.5) {
+                    object.insert(key.clone(), self.value(property, Some(key), depth + 1));
+                }
+            }
+        }
+        let additional = match schema.get("additionalProperties") {
+            Some(additional @ Value::Object(_)) => additional.clone(),
+            _ => Value::Bool(true),
+        };
+        for key in required {
+            if !object.contains_key(key) {
+                let value = self.value(&additional, Some(key), depth + 1);
+                object.insert(key.to_string(), value);
+            }
+        }
+        Value::Object(object)
+    }
+
+    fn timestamp(&mut self) -> DateTime<chrono::Utc> {
+        DateTime::from_timestamp(self.rng.gen_range(EARLIEST_SECOND..=LATEST_SECOND), 0)
+            .unwrap_or_else(|| NaiveDate::default().and_time(Default::default()).and_utc())
+    }
+
+    fn word(&mut self) -> &'static str {
+        WORDS.choose(self.rng).expect("the word list isn't empty")
+    }
+}
+
+/// Follows local `$ref`s like `#/$defs/address` to the schema they point to.
+fn resolve<'s>(root: &'s Value, mut schema: &'s Value) -> Option<&'s Value> {
+    for _ in 0..MAX_DEPTH {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => schema = root.pointer(reference.strip_prefix('#')?)?,
+            None => return Some(schema),
+        }
+    }
+    None
+}
+
+/// Merges the `allOf` subschemas into the schema itself.
+fn flatten<'s>(root: &Value, schema: &'s Map<String, Value>) -> Cow<'s, Map<String, Value>> {
+    match schema.get("allOf").and_then(Value::as_array) {
+        Some(subschemas) => {
+            let mut flattened = schema.clone();
+            flattened.remove("allOf");
+            for subschema in subschemas {
+                if let Some(Value::Object(subschema)) = resolve(root, subschema) {
+                    flattened = combine(&flattened, &flatten(root, subschema));
+                }
+            }
+            Cow::Owned(flattened)
+        }
+        None => Cow::Borrowed(schema),
+    }
+}
+
+/// Merges two schemas that must both hold, keeping the properties and required names of both.
+fn combine(a: &Map<String, Value>, b: &Map<String, Value>) -> Map<String, Value> {
+    let mut combined = a.clone();
+    for (key, value) in b {
+        let merged = match (key.as_str(), combined.get(key), value) {
+            ("properties", Some(Value::Object(x)), Value::Object(y)) => {
+                let mut properties = x.clone();
+                properties.extend(y.iter().map(|(key, value)| (key.clone(), value.clone())));
+                Value::Object(properties)
+            }
+            ("required", Some(Value::Array(x)), Value::Array(y)) => {
+                let mut names = x.clone();
+                names.extend(y.iter().filter(|name| !x.contains(name)).cloned());
+                Value::Array(names)
+            }
+            _ => value.clone(),
+        };
+        combined.insert(key.clone(), merged);
+    }
+    combined
+}
+
+fn required(schema: &Map<String, Value>) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn number(schema: &Map<String, Value>, key: &str) -> Option<f64> {
+    schema
+        .get(key)
+        .and_then(Value::as_f64)
+        .filter(|n| n.is_finite())
+}
+
+fn limit(schema: &Map<String, Value>, key: &str) -> Option<u64> {
+    schema.get(key).and_then(Value::as_u64)
+}
+
+/// A numeric limit and whether it's exclusive.
+type Bound = Option<(f64, bool)>;
+
+/// The lower and upper bound and whether each is exclusive. Draft 4 marks `minimum` and
+/// `maximum` exclusive with booleans, later drafts give `exclusiveMinimum` a number.
+fn bounds(schema: &Map<String, Value>) -> (Bound, Bound) {
+    let flag = |key: &str| schema.get(key) == Some(&Value::Bool(true));
+    let low = match (
+        number(schema, "minimum"),
+        number(schema, "exclusiveMinimum"),
+    ) {
+        (Some(minimum), Some(exclusive)) if exclusive >= minimum => Some((exclusive, true)),
+        (Some(minimum), _) => Some((minimum, flag("exclusiveMinimum"))),
+        (None, exclusive) => exclusive.map(|exclusive| (exclusive, true)),
+    };
+    let high = match (
+        number(schema, "maximum"),
+        number(schema, "exclusiveMaximum"),
+    ) {
+        (Some(maximum), Some(exclusive)) if exclusive <= maximum => Some((exclusive, true)),
+        (Some(maximum), _) => Some((maximum, flag("exclusiveMaximum"))),
+        (None, exclusive) => exclusive.map(|exclusive| (exclusive, true)),
+    };
+    (low, high)
+}
+
+/// Fills in a missing bound `span` away from the other one, or from zero. An impossible range
+/// collapses to its lower bound.
+fn defaults(low: Option<f64>, high: Option<f64>, span: f64) -> (f64, f64) {
+    // Larger values lose precision as `f64` and overflow `i64`.
+    const SAFE: f64 = 9_007_199_254_740_991.0;
+    let (low, high) = match (low, high) {
+        (Some(low), Some(high)) => (low, high),
+        (Some(low), None) => (low, low + span),
+        (None, Some(high)) if high >= 0.0 => (0.0, high),
+        (None, Some(high)) => (high - span, high),
+        (None, None) => (0.0, span),
+    };
+    let low = low.clamp(-SAFE, SAFE);
+    (low, high.clamp(low, SAFE))
+}
@@ -0,0 +1,453 @@
+use rand::{seq::SliceRandom, Rng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    EnglishUs,
+    EnglishUk,
+    German,
+    French,
+    Spanish,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 5] = [
+        Locale::EnglishUs,
+        Locale::EnglishUk,
+        Locale::German,
+        Locale::French,
+        Locale::Spanish,
+    ];
+
+    fn data(&self) -> &'static LocaleData {
+        match self {
+            Locale::EnglishUs => &ENGLISH_US,
+            Locale::EnglishUk => &ENGLISH_UK,
+            Locale::German => &GERMAN,
+            Locale::French => &FRENCH,
+            Locale::Spanish => &SPANISH,
+        }
+    }
+
+    pub fn first_name(&self, rng: &mut impl Rng) -> &'static str {
+        pick(rng, self.data().first_names)
+    }
+
+    pub fn last_name(&self, rng: &mut impl Rng) -> &'static str {
+        pick(rng, self.data().last_names)
+    }
+
+    pub fn city(&self, rng: &mut impl Rng) -> &'static str {
+        pick(rng, self.data().cities)
+    }
+
+    pub fn phone(&self, rng: &mut impl Rng) -> String {
+        fill_pattern(rng, self.data().phone)
+    }
+
+    pub fn email(&self, rng: &mut impl Rng) -> String {
+        format!(
+            "{}.{}{}@{}",
+            ascii_fold(self.first_name(rng)),
+            ascii_fold(self.last_name(rng)),
+            rng.gen_range(1..100),
+            pick(rng, self.data().email_domains)
+        )
+    }
+
+    /// A street address with house number, postcode and city, laid out the way the locale
+    /// writes them.
+    pub fn address(&self, rng: &mut impl Rng) -> String {
+        let data = self.data();
+        let street = pick(rng, data.streets);
+        let number = rng.gen_range(1..200);
+        let city = self.city(rng);
+        let postcode = fill_pattern(rng, data.postcode);
+        match self {
+            Locale::EnglishUs => format!(
+                "{} {}, {}, {} {}",
+                number,
+                street,
+                city,
+                pick(rng, US_STATES),
+                postcode
+            ),
+            Locale::EnglishUk | Locale::French => {
+                format!("{} {}, {} {}", number, street, postcode, city)
+            }
+            Locale::German | Locale::Spanish => {
+                format!("{} {}, {} {}", street, number, postcode, city)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Locale::EnglishUs => "English (US)",
+                Locale::EnglishUk => "English (UK)",
+                Locale::German => "German",
+                Locale::French => "French",
+                Locale::Spanish => "Spanish",
+            }
+        )
+    }
+}
+
+struct LocaleData {
+    first_names: &'static [&'static str],
+    last_names: &'static [&'static str],
+    streets: &'static [&'static str],
+    cities: &'static [&'static str],
+    email_domains: &'static [&'static str],
+    // `#` stands for a digit and `?` for an uppercase letter.
+    phone: &'static str,
+    postcode: &'static str,
+}
+
+const US_STATES: &[&str] = &[
+    "AL", "AZ", "CA", "CO", "FL", "GA", "IL", "MA", "MI", "NC", "NY", "OH", "OR", "PA", "TX", "VA",
+    "WA",
+];
+
+const ENGLISH_US: LocaleData = LocaleData {
+    first_names: &[
+        "James",
+        "Mary",
+        "Robert",
+        "Patricia",
+        "John",
+        "Jennifer",
+        "Michael",
+        "Linda",
+        "David",
+        "Elizabeth",
+        "William",
+        "Barbara",
+        "Richard",
+        "Susan",
+        "Joseph",
+        "Jessica",
+        "Thomas",
+        "Sarah",
+        "Daniel",
+        "Karen",
+    ],
+    last_names: &[
+        "Smith",
+        "Johnson",
+        "Williams",
+        "Brown",
+        "Jones",
+        "Garcia",
+        "Miller",
+        "Davis",
+        "Rodriguez",
+        "Martinez",
+        "Hernandez",
+        "Lopez",
+        "Wilson",
+        "Anderson",
+        "Thomas",
+        "Taylor",
+        "Moore",
+        "Jackson",
+        "Martin",
+        "Lee",
+    ],
+    streets: &[
+        "Main Street",
+        "Oak Avenue",
+        "Maple Drive",
+        "Cedar Lane",
+        "Pine Street",
+        "Elm Street",
+        "Washington Avenue",
+        "Lake Road",
+        "Hill Street",
+        "Park Avenue",
+    ],
+    cities: &[
+        "Springfield",
+        "Portland",
+        "Austin",
+        "Denver",
+        "Columbus",
+        "Seattle",
+        "Boston",
+        "Phoenix",
+        "Atlanta",
+        "Madison",
+    ],
+    email_domains: &["example.com", "example.org", "mail.example.net"],
+    phone: "(###) ###-####",
+    postcode: "#####",
+};
+
+const ENGLISH_UK: LocaleData = LocaleData {
+    first_names: &[
+        "Oliver", "Olivia", "George", "Amelia", "Harry", "Isla", "Jack", "Ava", "Charlie", "Emily",
+        "Thomas", "Sophie", "Oscar", "Grace", "William", "Lily", "James", "Freya", "Henry", "Ella",
+    ],
+    last_names: &[
+        "Smith", "Jones", "Taylor", "Brown", "Williams", "Wilson", "Johnson", "Davies", "Robinson",
+        "Wright", "Thompson", "Evans", "Walker", "White", "Roberts", "Green", "Hall", "Wood",
+        "Jackson", "Clarke",
+    ],
+    streets: &[
+        "High Street",
+        "Station Road",
+        "Church Lane",
+        "Victoria Road",
+        "Green Lane",
+        "Manor Road",
+        "Park Road",
+        "Queens Road",
+        "Mill Lane",
+        "Kings Road",
+    ],
+    cities: &[
+        "London",
+        "Manchester",
+        "Bristol",
+        "Leeds",
+        "Sheffield",
+        "Liverpool",
+        "Oxford",
+        "Cambridge",
+        "York",
+        "Brighton",
+    ],
+    email_domains: &["example.co.uk", "example.com", "mail.example.org"],
+    phone: "07### ######",
+    postcode: "?# #??",
+};
+
+const GERMAN: LocaleData = LocaleData {
+    first_names: &[
+        "Lukas",
+        "Anna",
+        "Leon",
+        "Lea",
+        "Finn",
+        "Hannah",
+        "Jonas",
+        "Mia",
+        "Paul",
+        "Lena",
+        "Felix",
+        "Sophie",
+        "Maximilian",
+        "Marie",
+        "Jürgen",
+        "Laura",
+        "Moritz",
+        "Emilia",
+        "Tobias",
+        "Katharina",
+    ],
+    last_names: &[
+        "Müller",
+        "Schmidt",
+        "Schneider",
+        "Fischer",
+        "Weber",
+        "Meyer",
+        "Wagner",
+        "Becker",
+        "Schulz",
+        "Hoffmann",
+        "Schäfer",
+        "Koch",
+        "Bauer",
+        "Richter",
+        "Klein",
+        "Wolf",
+        "Schröder",
+        "Neumann",
+        "Schwarz",
+        "Zimmermann",
+    ],
+    streets: &[
+        "Hauptstraße",
+        "Schulstraße",
+        "Gartenstraße",
+        "Bahnhofstraße",
+        "Dorfstraße",
+        "Bergstraße",
+        "Birkenweg",
+        "Lindenstraße",
+        "Kirchstraße",
+        "Waldstraße",
+    ],
+    cities: &[
+        "Berlin",
+        "Hamburg",
+        "München",
+        "Köln",
+        "Frankfurt am Main",
+        "Stuttgart",
+        "Düsseldorf",
+        "Leipzig",
+        "Dresden",
+        "Nürnberg",
+    ],
+    email_domains: &["example.de", "example.com", "mail.example.org"],
+    phone: "+49 1## #######",
+    postcode: "#####",
+};
+
+const FRENCH: LocaleData = LocaleData {
+    first_names: &[
+        "Gabriel", "Louise", "Léo", "Jade", "Raphaël", "Ambre", "Arthur", "Alice", "Louis",
+        "Chloé", "Jules", "Léa", "Hugo", "Manon", "Noé", "Camille", "Lucas", "Inès", "Théo", "Zoé",
+    ],
+    last_names: &[
+        "Martin", "Bernard", "Thomas", "Petit", "Robert", "Richard", "Durand", "Dubois", "Moreau",
+        "Laurent", "Simon", "Michel", "Lefèvre", "Leroy", "Roux", "David", "Bertrand", "Morel",
+        "Fournier", "Girard",
+    ],
+    streets: &[
+        "rue de la Paix",
+        "avenue Victor Hugo",
+        "rue de la République",
+        "boulevard Voltaire",
+        "rue du Moulin",
+        "place de l'Église",
+        "rue des Écoles",
+        "avenue Jean Jaurès",
+        "rue Pasteur",
+        "chemin des Vignes",
+    ],
+    cities: &[
+        "Paris",
+        "Marseille",
+        "Lyon",
+        "Toulouse",
+        "Nice",
+        "Nantes",
+        "Strasbourg",
+        "Montpellier",
+        "Bordeaux",
+        "Lille",
+    ],
+    email_domains: &["example.fr", "example.com", "mail.example.org"],
+    phone: "0# ## ## ## ##",
+    postcode: "#####",
+};
+
+const SPANISH: LocaleData = LocaleData {
+    first_names: &[
+        "Hugo",
+        "Lucía",
+        "Martín",
+        "Sofía",
+        "Pablo",
+        "María",
+        "Alejandro",
+        "Martina",
+        "Daniel",
+        "Paula",
+        "Álvaro",
+        "Julia",
+        "Adrián",
+        "Valeria",
+        "Mateo",
+        "Carmen",
+        "Diego",
+        "Elena",
+        "Javier",
+        "Noelia",
+    ],
+    last_names: &[
+        "García",
+        "Rodríguez",
+        "González",
+        "Fernández",
+        "López",
+        "Martínez",
+        "Sánchez",
+        "Pérez",
+        "Gómez",
+        "Martín",
+        "Jiménez",
+        "Ruiz",
+        "Hernández",
+        "Díaz",
+        "Moreno",
+        "Muñoz",
+        "Álvarez",
+        "Romero",
+        "Alonso",
+        "Navarro",
+    ],
+    streets: &[
+        "Calle Mayor",
+        "Calle Real",
+        "Avenida de la Constitución",
+        "Calle del Sol",
+        "Plaza de España",
+        "Calle Nueva",
+        "Paseo del Prado",
+        "Calle de Alcalá",
+        "Calle Luna",
+        "Avenida de Andalucía",
+    ],
+    cities: &[
+        "Madrid",
+        "Barcelona",
+        "Valencia",
+        "Sevilla",
+        "Zaragoza",
+        "Málaga",
+        "Bilbao",
+        "Murcia",
+        "Granada",
+        "Salamanca",
+    ],
+    email_domains: &["example.es", "example.com", "mail.example.org"],
+    phone: "6## ### ###",
+    postcode: "#####",
+};
+
+fn pick<T: Copy>(rng: &mut impl Rng, items: &[T]) -> T {
+    *items.choose(rng).expect("locale lists are never empty")
+}
+
+fn fill_pattern(rng: &mut impl Rng, pattern: &str) -> String {
+    pattern
+        .chars()
+        .map(|c| match c {
+            '#' => char::from(b'0' + rng.gen_range(0..10)),
+            '?' => char::from(b'A' + rng.gen_range(0..26)),
+            c => c,
+        })
+        .collect()
+}
+
+/// Lowercases a name and strips its accents, so it can be used in an email address.
+fn ascii_fold(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    for c in name.to_lowercase().chars() {
+        match c {
+            'á' | 'à' | 'â' => folded.push('a'),
+            'ä' => folded.push_str("ae"),
+            'é' | 'è' | 'ê' | 'ë' => folded.push('e'),
+            'í' | 'î' | 'ï' => folded.push('i'),
+            'ó' | 'ô' => folded.push('o'),
+            'ö' => folded.push_str("oe"),
+            'ú' | 'û' => folded.push('u'),
+            'ü' => folded.push_str("ue"),
+            'ß' => folded.push_str("ss"),
+            'ç' => folded.push('c'),
+            'ñ' => folded.push('n'),
+            c if c.is_ascii_alphanumeric() => folded.push(c),
+            _ => (),
+        }
+    }
+    folded
+}
@@ -0,0 +1,297 @@
+use iced::{
+    widget::{
+        column, container, horizontal_space, pick_list, row, scrollable, text, text_editor, Column,
+    },
+    Color, Element, Font,
+    Length::{Fill, FillPortion},
+};
+use unicode_general_category::get_general_category;
+
+use charset::Encoding;
+use escape::EscapeStyle;
+
+mod charset;
+mod escape;
+
+pub const NAME: &str = "Text Encoding Converter";
+
+const ERROR_COLOR: Color = Color::from_rgb(0.75, 0.1, 0.1);
+// Listing every code point of a large paste would make the view unusable.
+const CODE_POINT_LIMIT: usize = 1000;
+const BYTES_PREVIEW_LIMIT: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Bytes(Encoding),
+    Escaped(EscapeStyle),
+}
+
+impl Format {
+    pub const ALL: [Format; 12] = [
+        Format::Text,
+        Format::Bytes(Encoding::Utf8),
+        Format::Bytes(Encoding::Utf16Le),
+        Format::Bytes(Encoding::Utf16Be),
+        Format::Bytes(Encoding::Utf32Le),
+        Format::Bytes(Encoding::Utf32Be),
+        Format::Bytes(Encoding::Latin1),
+        Format::Bytes(Encoding::Windows1252),
+        Format::Escaped(EscapeStyle::Rust),
+        Format::Escaped(EscapeStyle::Json),
+        Format::Escaped(EscapeStyle::JavaScript),
+        Format::Escaped(EscapeStyle::Python),
+    ];
+
+    fn parse(&self, input: &str) -> Result<String, String> {
+        match self {
+            Format::Text => Ok(input.to_string()),
+            Format::Bytes(encoding) => encoding.decode(&charset::from_hex(input)?),
+            Format::Escaped(_) => escape::unescape(input),
+        }
+    }
+
+    fn render(&self, text: &str) -> Result<String, String> {
+        match self {
+            Format::Text => Ok(text.to_string()),
+            Format::Bytes(encoding) => encoding.encode(text).map(|bytes| charset::to_hex(&bytes)),
+            Format::Escaped(style) => Ok(escape::escape(text, *style)),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Text => write!(f, "Text"),
+            Format::Bytes(encoding) => write!(f, "{} bytes (hex)", encoding),
+            Format::Escaped(style) => write!(f, "{} escapes", style),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    InputActionPerformed(text_editor::Action),
+    OutputActionPerformed(text_editor::Action),
+    InputFormatSelected(Format),
+    OutputFormatSelected(Format),
+}
+
+struct CodePoint {
+    label: String,
+    glyph: String,
+    category: &'static str,
+    name: String,
+}
+
+pub struct TextEncodingConverter {
+    tool_name: String,
+    input_content: text_editor::Content,
+    output_content: text_editor::Content,
+    input_format: Option<Format>,
+    output_format: Option<Format>,
+    text: String,
+    error_text: Option<String>,
+    // The details panel, rebuilt whenever `text` changes rather than on every frame.
+    bytes: Vec<(Encoding, Result<String, String>)>,
+    code_points: Vec<CodePoint>,
+    hidden_code_points: usize,
+}
+
+impl Default for TextEncodingConverter {
+    fn default() -> Self {
+        TextEncodingConverter::new()
+    }
+}
+
+impl TextEncodingConverter {
+    pub fn new() -> Self {
+        let mut converter = Self {
+            tool_name: NAME.to_string(),
+            input_content: text_editor::Content::new(),
+            output_content: text_editor::Content::new(),
+            input_format: Some(Format::Text),
+            output_format: Some(Format::Bytes(Encoding::Utf8)),
+            text: String::new(),
+            error_text: None,
+            bytes: Vec::new(),
+            code_points: Vec::new(),
+            hidden_code_points: 0,
+        };
+        converter.describe();
+        converter
+    }
+
+    pub fn title(&self) -> String {
+        self.tool_name.clone()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let configuration = row![
+            "From",
+            pick_list(
+                &Format::ALL[..],
+                self.input_format,
+                Message::InputFormatSelected
+            ),
+            "To",
+            pick_list(
+                &Format::ALL[..],
+                self.output_format,
+                Message::OutputFormatSelected
+            ),
+            text(self.error_text.clone().unwrap_or_default()).color(ERROR_COLOR),
+            horizontal_space(),
+        ]
+        .spacing(10)
+        .padding([10, 0])
+        .align_y(iced::Alignment::Center);
+
+        let editors = column![
+            "Input",
+            container(scrollable(
+                text_editor(&self.input_content).on_action(Message::InputActionPerformed)
+            ))
+            .height(Fill),
+            "Output",
+            container(scrollable(
+                text_editor(&self.output_content).on_action(Message::OutputActionPerformed)
+            ))
+            .height(Fill),
+        ]
+        .spacing(5)
+        .width(FillPortion(1));
+
+        let encodings = self.bytes.iter().fold(
+            column!["Bytes"].spacing(5),
+            |column: Column<Message>, (encoding, bytes)| {
+                let bytes = match bytes {
+                    Ok(preview) => text(preview).font(Font::MONOSPACE),
+                    Err(e) => text(e).color(ERROR_COLOR),
+                };
+                column.push(row![text(encoding.to_string()).width(120), bytes].spacing(10))
+            },
+        );
+
+        let code_points = self.code_points.iter().fold(
+            column!["Code points"].spacing(2),
+            |column: Column<Message>, code_point| {
+                column.push(
+                    row![
+                        text(&code_point.label).font(Font::MONOSPACE).width(90),
+                        text(&code_point.glyph).width(30),
+                        text(code_point.category).width(30),
+                        text(&code_point.name),
+                    ]
+                    .spacing(10),
+                )
+            },
+        );
+        let code_points = if self.hidden_code_points > 0 {
+            code_points.push(text(format!(
+                "... {} more code points not shown",
+                self.hidden_code_points
+            )))
+        } else {
+            code_points
+        };
+
+        let details = scrollable(column![encodings, code_points].spacing(20))
+            .width(FillPortion(1))
+            .height(Fill);
+
+        let content = column![configuration, row![editors, details].spacing(20)];
+        container(content).padding(10).into()
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::InputActionPerformed(action) => {
+                let is_edit = action.is_edit();
+                self.input_content.perform(action);
+                if is_edit {
+                    self.convert();
+                }
+            }
+            Message::OutputActionPerformed(action) => {
+                // The output is derived from the input, so only allow selecting and copying it.
+                if !action.is_edit() {
+                    self.output_content.perform(action);
+                }
+            }
+            Message::InputFormatSelected(format) => {
+                self.input_format = Some(format);
+                self.convert();
+            }
+            Message::OutputFormatSelected(format) => {
+                self.output_format = Some(format);
+                self.convert();
+            }
+        }
+    }
+
+    fn convert(&mut self) {
+        let input = self.input_content.text();
+        let input = input.strip_suffix('\n').unwrap_or(&input);
+        match self.input_format.unwrap_or(Format::Text).parse(input) {
+            Ok(text) => {
+                if text != self.text {
+                    self.text = text;
+                    self.describe();
+                }
+                match self
+                    .output_format
+                    .unwrap_or(Format::Text)
+                    .render(&self.text)
+                {
+                    Ok(output) => {
+                        self.output_content = text_editor::Content::with_text(&output);
+                        self.error_text = None;
+                    }
+                    Err(e) => self.error_text = Some(e),
+                }
+            }
+            Err(e) => self.error_text = Some(e),
+        }
+    }
+
+    fn describe(&mut self) {
+        self.bytes = Encoding::ALL
+            .iter()
+            .map(|encoding| {
+                let preview = encoding.encode(&self.text).map(|bytes| {
+                    let mut preview =
+                        charset::to_hex(&bytes[..bytes.len().min(BYTES_PREVIEW_LIMIT)]);
+                    if bytes.len() > BYTES_PREVIEW_LIMIT {
+                        preview.push_str(" ...");
+                    }
+                    format!("{} bytes: {}", bytes.len(), preview)
+                });
+                (*encoding, preview)
+            })
+            .collect();
+
+        self.code_points = self
+            .text
+            .chars()
+            .take(CODE_POINT_LIMIT)
+            .map(|c| {
+                let category = get_general_category(c);
+                CodePoint {
+                    label: format!("U+{:04X}", c as u32),
+                    glyph: if c.is_control() {
+                        String::new()
+                    } else {
+                        c.to_string()
+                    },
+                    category: category.abbreviation(),
+                    name: unicode_names2::name(c)
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| format!("<{:?}>", category).to_lowercase()),
+                }
+            })
+            .collect();
+        self.hidden_code_points = self.text.chars().count().saturating_sub(CODE_POINT_LIMIT);
+    }
+}
@@ -0,0 +1,190 @@
+// Windows-1252 differs from ISO-8859-1 only in 0x80..=0x9F. The five bytes it leaves undefined
+// are mapped to the matching C1 controls, like browsers do.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+    Latin1,
+    Windows1252,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 7] = [
+        Encoding::Utf8,
+        Encoding::Utf16Le,
+        Encoding::Utf16Be,
+        Encoding::Utf32Le,
+        Encoding::Utf32Be,
+        Encoding::Latin1,
+        Encoding::Windows1252,
+    ];
+
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            Encoding::Utf16Le => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            Encoding::Utf16Be => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            Encoding::Utf32Le => Ok(text
+                .chars()
+                .flat_map(|c| (c as u32).to_le_bytes())
+                .collect()),
+            Encoding::Utf32Be => Ok(text
+                .chars()
+                .flat_map(|c| (c as u32).to_be_bytes())
+                .collect()),
+            Encoding::Latin1 | Encoding::Windows1252 => text
+                .chars()
+                .enumerate()
+                .map(|(position, c)| {
+                    self.encode_single_byte(c).ok_or_else(|| {
+                        format!(
+                            "'{}' (U+{:04X}) at position {} can't be represented in {}",
+                            c,
+                            c as u32,
+                            position + 1,
+                            self
+                        )
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        match self {
+            Encoding::Utf8 => String::from_utf8(bytes.to_vec()).map_err(|e| {
+                let offset = e.utf8_error().valid_up_to();
+                format!(
+                    "Invalid UTF-8 sequence at byte {} (0x{:02x})",
+                    offset, bytes[offset]
+                )
+            }),
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                if !bytes.len().is_multiple_of(2) {
+                    return Err(format!(
+                        "{} needs an even number of bytes, got {}",
+                        self,
+                        bytes.len()
+                    ));
+                }
+                let units = bytes.chunks(2).map(|unit| match self {
+                    Encoding::Utf16Le => u16::from_le_bytes([unit[0], unit[1]]),
+                    _ => u16::from_be_bytes([unit[0], unit[1]]),
+                });
+                let mut offset = 0;
+                char::decode_utf16(units)
+                    .map(|c| {
+                        let position = offset;
+                        offset += c.as_ref().map_or(1, |c| c.len_utf16()) * 2;
+                        c.map_err(|e| {
+                            format!(
+                                "Unpaired surrogate 0x{:04x} at byte {}",
+                                e.unpaired_surrogate(),
+                                position
+                            )
+                        })
+                    })
+                    .collect()
+            }
+            Encoding::Utf32Le | Encoding::Utf32Be => {
+                if !bytes.len().is_multiple_of(4) {
+                    return Err(format!(
+                        "{} needs a multiple of 4 bytes, got {}",
+                        self,
+                        bytes.len()
+                    ));
+                }
+                bytes
+                    .chunks(4)
+                    .enumerate()
+                    .map(|(index, unit)| {
+                        let unit = [unit[0], unit[1], unit[2], unit[3]];
+                        let value = match self {
+                            Encoding::Utf32Le => u32::from_le_bytes(unit),
+                            _ => u32::from_be_bytes(unit),
+                        };
+                        char::from_u32(value).ok_or_else(|| {
+                            format!("Invalid code point 0x{:x} at byte {}", value, index * 4)
+                        })
+                    })
+                    .collect()
+            }
+            Encoding::Latin1 => Ok(bytes.iter().map(|&b| b as char).collect()),
+            Encoding::Windows1252 => Ok(bytes
+                .iter()
+                .map(|&b| match b {
+                    0x80..=0x9f => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+                    _ => b as char,
+                })
+                .collect()),
+        }
+    }
+
+    fn encode_single_byte(&self, c: char) -> Option<u8> {
+        if *self == Encoding::Windows1252 {
+            if let Some(index) = WINDOWS_1252_HIGH.iter().position(|&high| high == c) {
+                return Some(0x80 + index as u8);
+            }
+            if ('\u{80}'..='\u{9f}').contains(&c) {
+                return None;
+            }
+        }
+        u8::try_from(c).ok()
+    }
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Encoding::Utf8 => "UTF-8",
+                Encoding::Utf16Le => "UTF-16LE",
+                Encoding::Utf16Be => "UTF-16BE",
+                Encoding::Utf32Le => "UTF-32LE",
+                Encoding::Utf32Be => "UTF-32BE",
+                Encoding::Latin1 => "ISO-8859-1",
+                Encoding::Windows1252 => "Windows-1252",
+            }
+        )
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn from_hex(input: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<(usize, char)> = input
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_whitespace() && !matches!(c, ':' | '-' | ','))
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(String::from("Odd number of hex digits"));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let digit = |(position, c): (usize, char)| {
+                c.to_digit(16).ok_or_else(|| {
+                    format!("Invalid hex digit '{}' at position {}", c, position + 1)
+                })
+            };
+            Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
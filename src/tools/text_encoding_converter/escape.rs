@@ -0,0 +1,144 @@
+use std::{iter::Peekable, str::CharIndices};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapeStyle {
+    Rust,
+    Json,
+    JavaScript,
+    Python,
+}
+
+impl std::fmt::Display for EscapeStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                EscapeStyle::Rust => "Rust",
+                EscapeStyle::Json => "JSON",
+                EscapeStyle::JavaScript => "JavaScript",
+                EscapeStyle::Python => "Python",
+            }
+        )
+    }
+}
+
+/// Escapes everything but printable ASCII, using the shortest form `style` supports.
+pub fn escape(text: &str, style: EscapeStyle) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            ' '..='~' => output.push(c),
+            _ => {
+                let code = c as u32;
+                match style {
+                    EscapeStyle::Rust if code < 0x80 => {
+                        output.push_str(&format!("\\x{:02x}", code))
+                    }
+                    EscapeStyle::Rust => output.push_str(&format!("\\u{{{:x}}}", code)),
+                    EscapeStyle::JavaScript | EscapeStyle::Python if code < 0x100 => {
+                        output.push_str(&format!("\\x{:02x}", code))
+                    }
+                    EscapeStyle::Python if code > 0xffff => {
+                        output.push_str(&format!("\\U{:08x}", code))
+                    }
+                    EscapeStyle::JavaScript if code > 0xffff => {
+                        output.push_str(&format!("\\u{{{:x}}}", code))
+                    }
+                    // JSON has no astral escape, so those become a UTF-16 surrogate pair.
+                    _ => {
+                        let mut units = [0; 2];
+                        for unit in c.encode_utf16(&mut units) {
+                            output.push_str(&format!("\\u{:04x}", unit));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Resolves the escape sequences of any of the supported styles.
+pub fn unescape(input: &str) -> Result<String, String> {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.char_indices().peekable();
+    let position = |index: usize| input[..index].chars().count() + 1;
+
+    while let Some((index, c)) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        let invalid = || format!("Invalid escape sequence at position {}", position(index));
+        let (_, kind) = chars.next().ok_or_else(invalid)?;
+        let code = match kind {
+            'n' => '\n' as u32,
+            'r' => '\r' as u32,
+            't' => '\t' as u32,
+            '0' => 0,
+            'b' => 0x08,
+            'f' => 0x0c,
+            '\\' | '"' | '\'' | '/' => kind as u32,
+            'x' => hex(&mut chars, 2).ok_or_else(invalid)?,
+            'U' => hex(&mut chars, 8).ok_or_else(invalid)?,
+            'u' if chars.next_if(|(_, c)| *c == '{').is_some() => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, c)) if c.is_ascii_hexdigit() && value.len() < 6 => value.push(c),
+                        _ => return Err(invalid()),
+                    }
+                }
+                u32::from_str_radix(&value, 16).map_err(|_| invalid())?
+            }
+            'u' => {
+                let unit = hex(&mut chars, 4).ok_or_else(invalid)?;
+                // A high surrogate has to be followed by an escaped low surrogate.
+                if (0xd800..0xdc00).contains(&unit) {
+                    let low = match (chars.next(), chars.next()) {
+                        (Some((_, '\\')), Some((_, 'u'))) => {
+                            hex(&mut chars, 4).ok_or_else(invalid)?
+                        }
+                        _ => return Err(invalid()),
+                    };
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return Err(invalid());
+                    }
+                    0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00)
+                } else {
+                    unit
+                }
+            }
+            _ => return Err(invalid()),
+        };
+        output.push(char::from_u32(code).ok_or_else(|| {
+            format!(
+                "Escape at position {} isn't a valid code point (0x{:x})",
+                position(index),
+                code
+            )
+        })?);
+    }
+    Ok(output)
+}
+
+fn hex(chars: &mut Peekable<CharIndices>, digits: usize) -> Option<u32> {
+    let value: String = (0..digits)
+        .map_while(|_| {
+            chars
+                .next_if(|(_, c)| c.is_ascii_hexdigit())
+                .map(|(_, c)| c)
+        })
+        .collect();
+    if value.len() != digits {
+        return None;
+    }
+    u32::from_str_radix(&value, 16).ok()
+}
@@ -1,16 +1,35 @@
-use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
+use iced::task::Handle;
 use iced::widget::text_editor::Action;
 use iced::widget::{
-    button, checkbox, column, container, horizontal_space, pick_list, row, scrollable, text,
-    text_editor, text_input, Space,
+    button, checkbox, column, container, horizontal_space, pick_list, progress_bar, row,
+    scrollable, text, text_editor, text_input, Space,
 };
-use iced::{Element, Length, Task};
-use rfd::AsyncFileDialog;
+use iced::{Color, Element, Font, Length, Task};
 use uuid::Uuid;
 
+use crate::save::{self, SaveEvent};
+use format::{Case, Layout, Notation, OutputFormat, Quotes};
+use generate::Settings;
+use ids::{IdType, NanoIdOptions, SnowflakeOptions};
+use version::{Namespace, Version};
+
+mod format;
+mod generate;
+mod ids;
+mod inspect;
+mod version;
+
 pub const NAME: &str = "UUID Generator";
 
+const PREVIEW_AMOUNT: u32 = 3;
+// Larger batches make the result editor sluggish, so they can only be saved to a file.
+const DISPLAY_LIMIT: u32 = 1000;
+const WARNING_COLOR: Color = Color::from_rgb(0.8, 0.45, 0.0);
+
 impl Default for UuidGenerator {
     fn default() -> Self {
         UuidGenerator::new()
@@ -18,98 +37,548 @@ impl Default for UuidGenerator {
 }
 
 pub struct UuidGenerator {
+    selected_id_type: Option<IdType>,
     selected_version: Option<Version>,
     output: text_editor::Content,
     tool_name: String,
     raw_amount: String,
     parsed_amount: u32,
     parsing_error: String,
+    raw_seed: String,
     selected_quotes: Option<Quotes>,
     is_separated_by_comma: bool,
+    selected_notation: Option<Notation>,
+    selected_case: Option<Case>,
+    selected_layout: Option<Layout>,
+    csv_header: String,
+    template: String,
+    preview: String,
+    raw_node_id: String,
+    selected_namespace: Option<Namespace>,
+    raw_namespace: String,
+    names: text_editor::Content,
+    raw_custom_data: String,
+    raw_nanoid_alphabet: String,
+    raw_nanoid_length: String,
+    raw_snowflake_epoch: String,
+    raw_worker_bits: String,
+    raw_sequence_bits: String,
+    raw_worker_id: String,
+    mode: Option<Mode>,
+    inspect_input: text_editor::Content,
+    inspect_output: text_editor::Content,
+    saving: Option<(Handle, Arc<AtomicBool>)>,
+    save_total: u64,
+    save_written: u64,
+    save_status: String,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Generated,
+    IdTypeSelected(IdType),
     Selected(Version),
     OutputActionPerformed(text_editor::Action),
     AmountChanged(String),
+    SeedChanged(String),
     QuotesSelected(Quotes),
     CommaSelected(bool),
+    NotationSelected(Notation),
+    CaseSelected(Case),
+    LayoutSelected(Layout),
+    CsvHeaderChanged(String),
+    TemplateChanged(String),
     UuidList(String),
     SaveToFileClicked,
-    FilePicked(Option<bool>),
+    FilePicked(Option<PathBuf>),
+    SaveProgress(SaveEvent),
+    CancelSaveClicked,
+    NodeIdChanged(String),
+    RandomNodeIdClicked,
+    NamespaceSelected(Namespace),
+    CustomNamespaceChanged(String),
+    NamesActionPerformed(text_editor::Action),
+    CustomDataChanged(String),
+    NanoIdAlphabetChanged(String),
+    NanoIdLengthChanged(String),
+    SnowflakeEpochChanged(String),
+    WorkerBitsChanged(String),
+    SequenceBitsChanged(String),
+    WorkerIdChanged(String),
+    ModeSelected(Mode),
+    InspectInputActionPerformed(text_editor::Action),
+    InspectOutputActionPerformed(text_editor::Action),
 }
 
 impl UuidGenerator {
     pub fn new() -> Self {
-        Self {
-            selected_version: Some(Version::V4),
+        let snowflake = SnowflakeOptions::default();
+        let mut generator = Self {
+            selected_id_type: Some(IdType::default()),
+            selected_version: Some(Version::default()),
             output: text_editor::Content::with_text(""),
             tool_name: NAME.to_string(),
             raw_amount: String::from("1"),
             parsed_amount: 1,
             parsing_error: String::new(),
+            raw_seed: String::new(),
             selected_quotes: Some(Quotes::NoQuotes),
             is_separated_by_comma: false,
-        }
+            selected_notation: Some(Notation::default()),
+            selected_case: Some(Case::default()),
+            selected_layout: Some(Layout::default()),
+            csv_header: String::from("id"),
+            template: String::new(),
+            preview: String::new(),
+            raw_node_id: version::format_node_id(&version::random_node_id()),
+            selected_namespace: Some(Namespace::default()),
+            raw_namespace: String::new(),
+            names: text_editor::Content::new(),
+            raw_custom_data: String::new(),
+            raw_nanoid_alphabet: String::from(ids::NANOID_ALPHABET),
+            raw_nanoid_length: ids::NANOID_LENGTH.to_string(),
+            raw_snowflake_epoch: snowflake.epoch.to_string(),
+            raw_worker_bits: snowflake.worker_bits.to_string(),
+            raw_sequence_bits: snowflake.sequence_bits.to_string(),
+            raw_worker_id: snowflake.worker_id.to_string(),
+            mode: Some(Mode::default()),
+            inspect_input: text_editor::Content::new(),
+            inspect_output: text_editor::Content::new(),
+            saving: None,
+            save_total: 0,
+            save_written: 0,
+            save_status: String::new(),
+        };
+        generator.refresh_preview();
+        generator
     }
 
     pub fn title(&self) -> String {
         self.tool_name.clone()
     }
 
-    fn can_display(&self) -> bool {
-        if self.parsing_error.as_str() == "" && self.parsed_amount <= 1000 {
-            true
-        } else {
-            false
+    fn settings(&self) -> Result<Settings, String> {
+        let id_type = self.selected_id_type.unwrap_or_default();
+        // The version options only matter, and can only be invalid, when generating UUIDs.
+        let version = match id_type {
+            IdType::Uuid => self.selected_version.unwrap_or_default(),
+            _ => Version::default(),
+        };
+        let node_id = match version.is_time_based() {
+            true => version::parse_hex(&self.raw_node_id)
+                .map_err(|e| format!("Invalid node ID: {}", e))?,
+            false => [0; 6],
+        };
+        let namespace = match self.selected_namespace.unwrap_or_default().uuid() {
+            Some(namespace) => namespace,
+            None if version.is_name_based() => Uuid::parse_str(self.raw_namespace.trim())
+                .map_err(|e| format!("Invalid namespace: {}", e))?,
+            None => Uuid::nil(),
+        };
+        let names: Vec<String> = self.names.text().lines().map(String::from).collect();
+        if version.is_name_based() && names.iter().all(|name| name.is_empty()) {
+            return Err(String::from("Enter at least one name"));
+        }
+        let custom_data = match self.raw_custom_data.trim() {
+            _ if version != Version::V8 => None,
+            "" => None,
+            data => {
+                Some(version::parse_hex(data).map_err(|e| format!("Invalid custom data: {}", e))?)
+            }
+        };
+        let format = OutputFormat {
+            notation: self.selected_notation.unwrap_or_default(),
+            case: self.selected_case.unwrap_or_default(),
+            layout: self.selected_layout.unwrap_or_default(),
+            quotes: self.selected_quotes.unwrap_or_default(),
+            is_separated_by_comma: self.is_separated_by_comma,
+            csv_header: self.csv_header.clone(),
+            template: self.template.clone(),
+        };
+        format.validate()?;
+        let nanoid = match id_type {
+            IdType::NanoId => NanoIdOptions::new(
+                &self.raw_nanoid_alphabet,
+                self.raw_nanoid_length
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Cannot parse length '{}'", self.raw_nanoid_length))?,
+            )?,
+            _ => NanoIdOptions::default(),
+        };
+        let snowflake = match id_type {
+            IdType::Snowflake => self.snowflake_options()?,
+            _ => SnowflakeOptions::default(),
+        };
+        Ok(Settings {
+            id_type,
+            amount: self.parsed_amount,
+            version,
+            node_id,
+            namespace,
+            names,
+            custom_data,
+            nanoid,
+            snowflake,
+            seed: Some(self.raw_seed.clone()).filter(|seed| !seed.is_empty()),
+            format,
+        })
+    }
+
+    fn snowflake_options(&self) -> Result<SnowflakeOptions, String> {
+        let parse = |name: &str, value: &str| {
+            value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("Cannot parse {} '{}'", name, value))
+        };
+        SnowflakeOptions::new(
+            parse("epoch", &self.raw_snowflake_epoch)?,
+            parse("worker bits", &self.raw_worker_bits)?.min(63) as u32,
+            parse("sequence bits", &self.raw_sequence_bits)?.min(63) as u32,
+            parse("worker ID", &self.raw_worker_id)?,
+        )
+    }
+
+    /// Renders the first few IDs of the current configuration, so format changes can be
+    /// checked before generating a large batch.
+    fn refresh_preview(&mut self) {
+        self.preview = match self.settings() {
+            Ok(mut settings) => {
+                settings.amount = settings.amount.min(PREVIEW_AMOUNT);
+                settings.names = settings
+                    .names
+                    .into_iter()
+                    .filter(|name| !name.is_empty())
+                    .take(PREVIEW_AMOUNT as usize)
+                    .collect();
+                generate::render(&settings)
+            }
+            Err(_) => String::new(),
+        };
+    }
+
+    // Name-based UUIDs are counted by their names rather than the amount, so both go through
+    // `Settings::len`.
+    fn can_display(&self, settings: &Result<Settings, String>) -> bool {
+        self.can_export(settings)
+            && settings
+                .as_ref()
+                .is_ok_and(|settings| settings.len() <= DISPLAY_LIMIT as u64)
+    }
+
+    fn can_export(&self, settings: &Result<Settings, String>) -> bool {
+        self.saving.is_none() && self.parsing_error.is_empty() && settings.is_ok()
+    }
+
+    fn save_to_file(&mut self, settings: Settings, path: PathBuf) -> Task<Message> {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.save_total = settings.len();
+        self.save_written = 0;
+        self.save_status = String::new();
+        let (task, handle) = Task::run(
+            save::save_in_background(path, cancelled.clone(), move |file, cancelled, progress| {
+                generate::write(&settings, file, cancelled, progress)
+            }),
+            Message::SaveProgress,
+        )
+        .abortable();
+        self.saving = Some((handle, cancelled));
+        task
+    }
+
+    fn save_view(&self) -> Element<'_, Message> {
+        match self.saving {
+            Some(_) => row![
+                progress_bar(0.0..=self.save_total as f32, self.save_written as f32)
+                    .height(20)
+                    .width(300),
+                text(format!("{} / {}", self.save_written, self.save_total)),
+                button("Cancel").on_press(Message::CancelSaveClicked),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into(),
+            None => text(self.save_status.as_str()).into(),
         }
     }
 
-    fn can_export(&self) -> bool {
-        if self.parsing_error.as_str() == "" {
-            true
+    fn version_options(&self) -> Element<'_, Message> {
+        let version = self.selected_version.unwrap_or_default();
+        if version.is_time_based() {
+            row![
+                "Node ID: ",
+                text_input("12 hex digits", self.raw_node_id.as_str())
+                    .on_input(Message::NodeIdChanged),
+                button("Random node ID").on_press(Message::RandomNodeIdClicked),
+            ]
+            .spacing(10)
+            .into()
+        } else if version.is_name_based() {
+            let mut namespace = row![
+                "Namespace: ",
+                pick_list(
+                    &Namespace::ALL[..],
+                    self.selected_namespace,
+                    Message::NamespaceSelected
+                ),
+            ]
+            .spacing(10);
+            if self.selected_namespace == Some(Namespace::Custom) {
+                namespace = namespace.push(
+                    text_input("Namespace UUID", self.raw_namespace.as_str())
+                        .on_input(Message::CustomNamespaceChanged),
+                );
+            }
+            column![
+                namespace,
+                "Names (one UUID per line): ",
+                container(scrollable(
+                    text_editor(&self.names).on_action(Message::NamesActionPerformed)
+                ))
+                .height(100),
+            ]
+            .spacing(10)
+            .into()
+        } else if version == Version::V8 {
+            row![
+                "Custom data: ",
+                text_input(
+                    "32 hex digits, random when empty",
+                    self.raw_custom_data.as_str()
+                )
+                .on_input(Message::CustomDataChanged),
+            ]
+            .into()
         } else {
-            false
+            column![].into()
         }
     }
 
-    pub fn view(&self) -> Element<Message> {
+    fn id_options(&self) -> Element<'_, Message> {
+        match self.selected_id_type.unwrap_or_default() {
+            IdType::Uuid => column![
+                row![
+                    "Version: ",
+                    pick_list(&Version::ALL[..], self.selected_version, Message::Selected,)
+                        .placeholder("Choose a version")
+                ],
+                self.version_options(),
+            ]
+            .spacing(10)
+            .into(),
+            IdType::NanoId => row![
+                "Alphabet: ",
+                text_input(ids::NANOID_ALPHABET, self.raw_nanoid_alphabet.as_str())
+                    .on_input(Message::NanoIdAlphabetChanged),
+                "Length: ",
+                text_input("Length", self.raw_nanoid_length.as_str())
+                    .on_input(Message::NanoIdLengthChanged)
+                    .width(80),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into(),
+            IdType::Snowflake => row![
+                "Epoch (Unix ms): ",
+                text_input("Epoch", self.raw_snowflake_epoch.as_str())
+                    .on_input(Message::SnowflakeEpochChanged),
+                "Worker bits: ",
+                text_input("Bits", self.raw_worker_bits.as_str())
+                    .on_input(Message::WorkerBitsChanged)
+                    .width(60),
+                "Sequence bits: ",
+                text_input("Bits", self.raw_sequence_bits.as_str())
+                    .on_input(Message::SequenceBitsChanged)
+                    .width(60),
+                "Worker ID: ",
+                text_input("Worker ID", self.raw_worker_id.as_str())
+                    .on_input(Message::WorkerIdChanged)
+                    .width(100),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into(),
+            IdType::Ulid | IdType::Ksuid | IdType::Cuid2 => column![].into(),
+        }
+    }
+
+    fn seed_options(&self) -> Element<'_, Message> {
+        let seed = row![
+            "Seed: ",
+            text_input(
+                "Optional, makes the output reproducible",
+                self.raw_seed.as_str()
+            )
+            .on_input(Message::SeedChanged),
+        ];
+        if self.raw_seed.is_empty() {
+            return seed.into();
+        }
+        let embeds_time = match self.selected_id_type.unwrap_or_default() {
+            IdType::Uuid => matches!(
+                self.selected_version.unwrap_or_default(),
+                Version::V1 | Version::V6 | Version::V7
+            ),
+            IdType::NanoId => false,
+            IdType::Ulid | IdType::Ksuid | IdType::Snowflake | IdType::Cuid2 => true,
+        };
+        let mut warning = String::from(
+            "Not secure: anyone who knows the seed can reproduce these IDs, so never use them as secrets or tokens.",
+        );
+        if embeds_time {
+            warning.push_str(
+                " These IDs embed the current time, so only their random part is reproducible.",
+            );
+        }
+        column![seed, text(warning).color(WARNING_COLOR)]
+            .spacing(5)
+            .into()
+    }
+
+    fn format_options(&self) -> Element<'_, Message> {
+        let layout_options: Element<'_, Message> = match self.selected_layout.unwrap_or_default() {
+            Layout::Lines => row![
+                "Wrap with quotes",
+                pick_list(
+                    &Quotes::ALL[..],
+                    self.selected_quotes,
+                    Message::QuotesSelected
+                ),
+                checkbox("Separate by comma", self.is_separated_by_comma)
+                    .on_toggle(Message::CommaSelected),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into(),
+            Layout::Csv => row![
+                "Header: ",
+                text_input("Column name", self.csv_header.as_str())
+                    .on_input(Message::CsvHeaderChanged),
+            ]
+            .into(),
+            Layout::Template => column![
+                row![
+                    "Template: ",
+                    text_input(
+                        "INSERT INTO t (id) VALUES ('{uuid}');",
+                        self.template.as_str()
+                    )
+                    .on_input(Message::TemplateChanged),
+                ],
+                text(format!(
+                    "{} is replaced by the ID and {} by its position, starting at 1",
+                    format::UUID_PLACEHOLDER,
+                    format::INDEX_PLACEHOLDER
+                )),
+            ]
+            .spacing(5)
+            .into(),
+            Layout::JsonArray => column![].into(),
+        };
+
+        let mut output = row![].spacing(10).align_y(iced::Alignment::Center);
+        if self.selected_id_type == Some(IdType::Uuid) {
+            output = output.extend([
+                "Format: ".into(),
+                pick_list(
+                    &Notation::ALL[..],
+                    self.selected_notation,
+                    Message::NotationSelected,
+                )
+                .into(),
+                pick_list(&Case::ALL[..], self.selected_case, Message::CaseSelected).into(),
+            ]);
+        }
+        let output = output.push("Output: ").push(pick_list(
+            &Layout::ALL[..],
+            self.selected_layout,
+            Message::LayoutSelected,
+        ));
+
+        column![
+            output,
+            layout_options,
+            "Preview",
+            text(self.preview.as_str()).font(Font::MONOSPACE),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn inspect_view(&self) -> Element<'_, Message> {
+        let input = column![
+            "IDs to inspect (one per line): UUID, ULID, KSUID, Snowflake, CUID2 or NanoID",
+            container(scrollable(
+                text_editor(&self.inspect_input).on_action(Message::InspectInputActionPerformed)
+            ))
+            .height(Length::FillPortion(1))
+        ]
+        .padding(10)
+        .spacing(10);
+
+        let report = column![
+            "Report",
+            container(scrollable(
+                text_editor(&self.inspect_output).on_action(Message::InspectOutputActionPerformed)
+            ))
+            .height(Length::FillPortion(2))
+        ]
+        .padding(10)
+        .spacing(10);
+
+        column![input, report].into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mode = row![
+            "Mode: ",
+            pick_list(&Mode::ALL[..], self.mode, Message::ModeSelected)
+        ]
+        .padding([0, 10]);
+        if self.mode == Some(Mode::Inspect) {
+            return container(column![mode, self.inspect_view()])
+                .padding(10)
+                .height(Length::Fill)
+                .into();
+        }
+
+        let settings = self.settings();
         let configuration = column![
             "Configuration",
             row![
-                "Version: ",
-                pick_list(&Version::ALL[..], self.selected_version, Message::Selected,)
-                    .placeholder("Choose a version")
+                "ID type: ",
+                pick_list(
+                    &IdType::ALL[..],
+                    self.selected_id_type,
+                    Message::IdTypeSelected
+                ),
             ],
+            self.id_options(),
             row![
                 "Amount: ",
                 text_input("Amount", self.raw_amount.as_str()).on_input(Message::AmountChanged),
                 text(self.parsing_error.as_str()),
                 horizontal_space()
             ],
+            self.seed_options(),
+            self.format_options(),
+            text(match (self.parsing_error.is_empty(), &settings) {
+                (true, Err(e)) => e.clone(),
+                _ => String::new(),
+            }),
             row![
-                "Wrap with quotes",
-                pick_list(
-                    &Quotes::ALL[..],
-                    self.selected_quotes,
-                    Message::QuotesSelected
-                )
-            ],
-            row![checkbox("Separate by comma", self.is_separated_by_comma)
-                .on_toggle(Message::CommaSelected)],
-            row![
-                button("Generate UUID").on_press_maybe(match self.can_display() {
+                button("Generate").on_press_maybe(match self.can_display(&settings) {
                     true => Some(Message::Generated),
                     false => None,
                 }),
                 Space::with_width(10),
-                button("Save to file").on_press_maybe(match self.can_export() {
+                button("Save to file").on_press_maybe(match self.can_export(&settings) {
                     true => Some(Message::SaveToFileClicked),
                     false => None,
                 })
             ],
+            self.save_view(),
         ]
         .padding(10)
         .spacing(10);
@@ -121,7 +590,7 @@ impl UuidGenerator {
         .padding(10)
         .spacing(10);
 
-        let content = container(column![configuration, Space::with_height(20), result])
+        let content = container(column![mode, configuration, Space::with_height(20), result])
             .padding(10)
             .height(Length::Fill);
 
@@ -129,23 +598,35 @@ impl UuidGenerator {
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        let changes_settings = !matches!(
+            message,
+            Message::OutputActionPerformed(_)
+                | Message::UuidList(_)
+                | Message::FilePicked(_)
+                | Message::SaveProgress(_)
+                | Message::CancelSaveClicked
+                | Message::InspectInputActionPerformed(_)
+                | Message::InspectOutputActionPerformed(_)
+        );
+        let task = self.handle(message);
+        if changes_settings {
+            self.refresh_preview();
+        }
+        task
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::Generated => {
-                let parsed_amount = self.parsed_amount.clone();
-                let selected_version = self.selected_version.clone();
-                let selected_quotes = self.selected_quotes.clone();
-                let is_separated_by_comma = self.is_separated_by_comma.clone();
-                Task::perform(
-                    async move {
-                        generate_result(
-                            parsed_amount,
-                            selected_version,
-                            selected_quotes,
-                            is_separated_by_comma,
-                        )
-                    },
-                    |res| Message::UuidList(res),
-                )
+            Message::Generated => match self.settings() {
+                Ok(settings) => Task::perform(
+                    async move { generate::render(&settings) },
+                    Message::UuidList,
+                ),
+                Err(_) => Task::none(),
+            },
+            Message::IdTypeSelected(id_type) => {
+                self.selected_id_type = Some(id_type);
+                Task::none()
             }
             Message::Selected(version) => {
                 self.selected_version = Some(version);
@@ -182,6 +663,10 @@ impl UuidGenerator {
                 };
                 Task::none()
             }
+            Message::SeedChanged(value) => {
+                self.raw_seed = value;
+                Task::none()
+            }
             Message::QuotesSelected(quotes) => {
                 self.selected_quotes = Some(quotes);
                 Task::none()
@@ -190,140 +675,148 @@ impl UuidGenerator {
                 self.is_separated_by_comma = value;
                 Task::none()
             }
+            Message::NotationSelected(notation) => {
+                self.selected_notation = Some(notation);
+                Task::none()
+            }
+            Message::CaseSelected(case) => {
+                self.selected_case = Some(case);
+                Task::none()
+            }
+            Message::LayoutSelected(layout) => {
+                self.selected_layout = Some(layout);
+                Task::none()
+            }
+            Message::CsvHeaderChanged(value) => {
+                self.csv_header = value;
+                Task::none()
+            }
+            Message::TemplateChanged(value) => {
+                self.template = value;
+                Task::none()
+            }
             Message::UuidList(result) => {
                 self.output = text_editor::Content::with_text(result.as_str());
                 Task::none()
             }
-            Message::SaveToFileClicked => {
-                let parsed_amount = self.parsed_amount.clone();
-                let selected_version = self.selected_version.clone();
-                let selected_quotes = self.selected_quotes.clone();
-                let is_separated_by_comma = self.is_separated_by_comma.clone();
-                Task::perform(
-                    generate_and_save_to_file(
-                        parsed_amount,
-                        selected_version,
-                        selected_quotes,
-                        is_separated_by_comma,
-                    ),
-                    |res| Message::FilePicked(res),
-                )
+            Message::SaveToFileClicked => Task::perform(save::pick_file(), Message::FilePicked),
+            Message::FilePicked(path) => match (path, self.settings()) {
+                (Some(path), Ok(settings)) => self.save_to_file(settings, path),
+                _ => Task::none(),
+            },
+            Message::SaveProgress(SaveEvent::Written(written)) => {
+                self.save_written = written;
+                Task::none()
             }
-            Message::FilePicked(file_handle) => match file_handle {
-                Some(_) => {
-                    println!("File created!");
-                    Task::none()
+            Message::SaveProgress(SaveEvent::Finished(result)) => {
+                self.saving = None;
+                self.save_status = match result {
+                    Ok(written) => format!("Saved {} IDs", written),
+                    Err(e) => format!("Could not save the file: {}", e),
+                };
+                Task::none()
+            }
+            Message::CancelSaveClicked => {
+                if let Some((handle, cancelled)) = self.saving.take() {
+                    cancelled.store(true, Ordering::Relaxed);
+                    handle.abort();
+                    self.save_status = String::from("Saving cancelled");
                 }
-                None => Task::none(),
-            },
-        }
-    }
-}
-
-fn generate_result(
-    parsed_amount: u32,
-    selected_version: Option<Version>,
-    selected_quotes: Option<Quotes>,
-    is_separated_by_comma: bool,
-) -> String {
-    (0..parsed_amount)
-        .into_iter()
-        .map(|_| match selected_version {
-            Some(version) => match version {
-                Version::V4 => Uuid::new_v4().to_string(),
-                Version::V7 => Uuid::now_v7().to_string(),
-            },
-            None => Uuid::new_v4().to_string(),
-        })
-        .map(|v| wrap_with_quotes(v, selected_quotes))
-        .reduce(|cur: String, nxt: String| match is_separated_by_comma {
-            true => cur + ",\n" + &nxt,
-            false => cur + "\n" + &nxt,
-        })
-        .unwrap()
-}
-
-async fn generate_and_save_to_file(
-    parsed_amount: u32,
-    selected_version: Option<Version>,
-    selected_quotes: Option<Quotes>,
-    is_separated_by_comma: bool,
-) -> Option<bool> {
-    let file = AsyncFileDialog::new().set_directory("/").save_file().await;
-    match file {
-        Some(f) => {
-            let output = generate_result(
-                parsed_amount,
-                selected_version,
-                selected_quotes,
-                is_separated_by_comma,
-            );
-            match fs::write(f.path().as_os_str(), output) {
-                Ok(_) => Some(true),
-                Err(_) => None,
+                Task::none()
             }
-        }
-        None => None,
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum Version {
-    V4,
-    #[default]
-    V7,
-}
-
-impl Version {
-    pub const ALL: [Version; 2] = [Version::V4, Version::V7];
-}
-
-impl std::fmt::Display for Version {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Version::V4 => "Version 4",
-                Version::V7 => "Version 7",
+            Message::NodeIdChanged(value) => {
+                self.raw_node_id = value;
+                Task::none()
             }
-        )
+            Message::RandomNodeIdClicked => {
+                self.raw_node_id = version::format_node_id(&version::random_node_id());
+                Task::none()
+            }
+            Message::NamespaceSelected(namespace) => {
+                self.selected_namespace = Some(namespace);
+                Task::none()
+            }
+            Message::CustomNamespaceChanged(value) => {
+                self.raw_namespace = value;
+                Task::none()
+            }
+            Message::NamesActionPerformed(action) => {
+                self.names.perform(action);
+                Task::none()
+            }
+            Message::CustomDataChanged(value) => {
+                self.raw_custom_data = value;
+                Task::none()
+            }
+            Message::NanoIdAlphabetChanged(value) => {
+                self.raw_nanoid_alphabet = value;
+                Task::none()
+            }
+            Message::NanoIdLengthChanged(value) => {
+                self.raw_nanoid_length = value;
+                Task::none()
+            }
+            Message::SnowflakeEpochChanged(value) => {
+                self.raw_snowflake_epoch = value;
+                Task::none()
+            }
+            Message::WorkerBitsChanged(value) => {
+                self.raw_worker_bits = value;
+                Task::none()
+            }
+            Message::SequenceBitsChanged(value) => {
+                self.raw_sequence_bits = value;
+                Task::none()
+            }
+            Message::WorkerIdChanged(value) => {
+                self.raw_worker_id = value;
+                Task::none()
+            }
+            Message::ModeSelected(mode) => {
+                self.mode = Some(mode);
+                Task::none()
+            }
+            Message::InspectInputActionPerformed(action) => {
+                let is_edit = action.is_edit();
+                self.inspect_input.perform(action);
+                if is_edit {
+                    self.inspect_output = text_editor::Content::with_text(&inspect::inspect_all(
+                        &self.inspect_input.text(),
+                        &self.snowflake_options().unwrap_or_default(),
+                    ));
+                }
+                Task::none()
+            }
+            Message::InspectOutputActionPerformed(action) => {
+                if !action.is_edit() {
+                    self.inspect_output.perform(action);
+                }
+                Task::none()
+            }
+        }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum Quotes {
+pub enum Mode {
     #[default]
-    NoQuotes,
-    SingleQuotes,
-    DoubleQuotes,
+    Generate,
+    Inspect,
 }
 
-impl Quotes {
-    const ALL: [Quotes; 3] = [Quotes::NoQuotes, Quotes::SingleQuotes, Quotes::DoubleQuotes];
+impl Mode {
+    const ALL: [Mode; 2] = [Mode::Generate, Mode::Inspect];
 }
 
-impl std::fmt::Display for Quotes {
+impl std::fmt::Display for Mode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "{}",
             match self {
-                Quotes::NoQuotes => "No Quotes",
-                Quotes::SingleQuotes => "Single Quotes",
-                Quotes::DoubleQuotes => "Double Quotes",
+                Mode::Generate => "Generate",
+                Mode::Inspect => "Inspect",
             }
         )
     }
 }
-
-fn wrap_with_quotes(text: String, quote_type: Option<Quotes>) -> String {
-    match quote_type {
-        Some(v) => match v {
-            Quotes::NoQuotes => text,
-            Quotes::DoubleQuotes => format!("\"{}\"", text),
-            Quotes::SingleQuotes => format!("'{}'", text),
-        },
-        None => text,
-    }
-}
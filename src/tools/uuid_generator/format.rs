@@ -0,0 +1,228 @@
+use std::io::{self, Write};
+
+use uuid::Uuid;
+
+pub const UUID_PLACEHOLDER: &str = "{uuid}";
+pub const INDEX_PLACEHOLDER: &str = "{index}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    #[default]
+    Hyphenated,
+    Simple,
+    Urn,
+    Braced,
+}
+
+impl Notation {
+    pub const ALL: [Notation; 4] = [
+        Notation::Hyphenated,
+        Notation::Simple,
+        Notation::Urn,
+        Notation::Braced,
+    ];
+}
+
+impl std::fmt::Display for Notation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Notation::Hyphenated => "Hyphenated",
+                Notation::Simple => "Simple (no hyphens)",
+                Notation::Urn => "URN",
+                Notation::Braced => "Braced",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+    #[default]
+    Lower,
+    Upper,
+}
+
+impl Case {
+    pub const ALL: [Case; 2] = [Case::Lower, Case::Upper];
+}
+
+impl std::fmt::Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Case::Lower => "Lowercase",
+                Case::Upper => "Uppercase",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    #[default]
+    Lines,
+    JsonArray,
+    Csv,
+    Template,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 4] = [
+        Layout::Lines,
+        Layout::JsonArray,
+        Layout::Csv,
+        Layout::Template,
+    ];
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Layout::Lines => "One per line",
+                Layout::JsonArray => "JSON array",
+                Layout::Csv => "CSV column",
+                Layout::Template => "Custom template",
+            }
+        )
+    }
+}
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Quotes {
+    #[default]
+    NoQuotes,
+    SingleQuotes,
+    DoubleQuotes,
+}
+
+impl Quotes {
+    pub const ALL: [Quotes; 3] = [Quotes::NoQuotes, Quotes::SingleQuotes, Quotes::DoubleQuotes];
+}
+
+impl std::fmt::Display for Quotes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Quotes::NoQuotes => "No Quotes",
+                Quotes::SingleQuotes => "Single Quotes",
+                Quotes::DoubleQuotes => "Double Quotes",
+            }
+        )
+    }
+}
+
+/// How a batch of UUIDs is written out. The output is split into an optional header, one item
+/// per UUID and an optional footer, so a batch can be streamed without holding all of it in
+/// memory.
+#[derive(Debug, Clone, Default)]
+pub struct OutputFormat {
+    pub notation: Notation,
+    pub case: Case,
+    pub layout: Layout,
+    pub quotes: Quotes,
+    pub is_separated_by_comma: bool,
+    pub csv_header: String,
+    pub template: String,
+}
+
+impl OutputFormat {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.layout == Layout::Template && !self.template.contains(UUID_PLACEHOLDER) {
+            return Err(format!("The template must contain {}", UUID_PLACEHOLDER));
+        }
+        Ok(())
+    }
+
+    pub fn header(&self) -> Option<String> {
+        match self.layout {
+            Layout::JsonArray => Some(String::from("[")),
+            Layout::Csv => Some(csv_field(&self.csv_header)),
+            Layout::Lines | Layout::Template => None,
+        }
+    }
+
+    /// Writes the ID at the zero-based `index` of the batch.
+    pub fn write_item(&self, writer: &mut impl Write, index: usize, id: &str) -> io::Result<()> {
+        let id = id.as_bytes();
+        match self.layout {
+            Layout::Lines => match self.quotes {
+                Quotes::NoQuotes => writer.write_all(id),
+                Quotes::SingleQuotes => quoted(writer, b"'", id, b"'"),
+                Quotes::DoubleQuotes => quoted(writer, b"\"", id, b"\""),
+            },
+            Layout::JsonArray => quoted(writer, b"  \"", id, b"\""),
+            Layout::Csv => writer.write_all(id),
+            Layout::Template => {
+                let mut rest = self.template.as_str();
+                while let Some(start) = rest.find('{') {
+                    writer.write_all(&rest.as_bytes()[..start])?;
+                    rest = &rest[start..];
+                    if let Some(after) = rest.strip_prefix(UUID_PLACEHOLDER) {
+                        writer.write_all(id)?;
+                        rest = after;
+                    } else if let Some(after) = rest.strip_prefix(INDEX_PLACEHOLDER) {
+                        write!(writer, "{}", index + 1)?;
+                        rest = after;
+                    } else {
+                        writer.write_all(b"{")?;
+                        rest = &rest[1..];
+                    }
+                }
+                writer.write_all(rest.as_bytes())
+            }
+        }
+    }
+
+    pub fn separator(&self) -> &'static str {
+        match self.layout {
+            Layout::Lines if self.is_separated_by_comma => ",\n",
+            Layout::JsonArray => ",\n",
+            _ => "\n",
+        }
+    }
+
+    pub fn footer(&self) -> Option<String> {
+        match self.layout {
+            Layout::JsonArray => Some(String::from("]")),
+            _ => None,
+        }
+    }
+
+    pub fn encode_uuid<'a>(&self, uuid: &Uuid, buffer: &'a mut [u8]) -> &'a str {
+        match (self.notation, self.case) {
+            (Notation::Hyphenated, Case::Lower) => uuid.hyphenated().encode_lower(buffer),
+            (Notation::Hyphenated, Case::Upper) => uuid.hyphenated().encode_upper(buffer),
+            (Notation::Simple, Case::Lower) => uuid.simple().encode_lower(buffer),
+            (Notation::Simple, Case::Upper) => uuid.simple().encode_upper(buffer),
+            (Notation::Urn, Case::Lower) => uuid.urn().encode_lower(buffer),
+            (Notation::Urn, Case::Upper) => uuid.urn().encode_upper(buffer),
+            (Notation::Braced, Case::Lower) => uuid.braced().encode_lower(buffer),
+            (Notation::Braced, Case::Upper) => uuid.braced().encode_upper(buffer),
+        }
+    }
+}
+
+fn quoted(writer: &mut impl Write, open: &[u8], id: &[u8], close: &[u8]) -> io::Result<()> {
+    writer.write_all(open)?;
+    writer.write_all(id)?;
+    writer.write_all(close)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
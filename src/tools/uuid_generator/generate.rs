@@ -0,0 +1,207 @@
+use std::cell::Cell;
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::Rng;
+use uuid::{Builder, Timestamp, Uuid};
+
+use super::format::OutputFormat;
+use super::ids::{IdGenerator, IdType, NanoIdOptions, SnowflakeOptions};
+use super::version::Version;
+use crate::random::Random;
+
+// How many IDs are written between two progress reports.
+const PROGRESS_STEP: u64 = 100_000;
+
+/// Everything needed to generate a batch, validated and detached from the UI state so it can
+/// be moved into a background task.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub id_type: IdType,
+    pub amount: u32,
+    pub version: Version,
+    pub node_id: [u8; 6],
+    pub namespace: Uuid,
+    pub names: Vec<String>,
+    pub custom_data: Option<[u8; 16]>,
+    pub nanoid: NanoIdOptions,
+    pub snowflake: SnowflakeOptions,
+    pub seed: Option<String>,
+    pub format: OutputFormat,
+}
+
+impl Settings {
+    /// The number of IDs in the batch: one per non-empty name for name-based UUIDs.
+    pub fn len(&self) -> u64 {
+        match self.id_type == IdType::Uuid && self.version.is_name_based() {
+            true => self.names.iter().filter(|name| !name.is_empty()).count() as u64,
+            false => self.amount as u64,
+        }
+    }
+
+    fn next_uuid(&self, random: &mut Random, clock: &ClockSequence, v7: &mut V7Counter) -> Uuid {
+        match self.version {
+            Version::V1 => Uuid::new_v1(Timestamp::now(clock), &self.node_id),
+            Version::V6 => Uuid::new_v6(Timestamp::now(clock), &self.node_id),
+            Version::V7 => v7.next(random),
+            Version::V8 => Uuid::new_v8(self.custom_data.unwrap_or_else(|| random.gen())),
+            Version::Nil => Uuid::nil(),
+            Version::Max => Uuid::max(),
+            Version::V3 | Version::V4 | Version::V5 => {
+                Builder::from_random_bytes(random.gen()).into_uuid()
+            }
+        }
+    }
+}
+
+/// The 14-bit clock sequence of version 1 and 6 UUIDs. It starts at a value drawn from the
+/// batch's random source, so a seed fixes it too.
+struct ClockSequence(Cell<u16>);
+
+impl uuid::ClockSequence for ClockSequence {
+    type Output = u16;
+
+    fn generate_sequence(&self, _seconds: u64, _subsec_nanos: u32) -> u16 {
+        let sequence = self.0.get();
+        self.0.set(sequence.wrapping_add(1));
+        sequence & (u16::MAX >> 2)
+    }
+
+    fn usable_bits(&self) -> usize {
+        14
+    }
+}
+
+/// Keeps version 7 UUIDs created in the same millisecond in creation order by incrementing
+/// their 74 random bits instead of drawing new ones, as RFC 9562 suggests.
+#[derive(Default)]
+struct V7Counter {
+    millis: u64,
+    counter: u128,
+}
+
+impl V7Counter {
+    fn next(&mut self, random: &mut Random) -> Uuid {
+        let millis = super::ids::now_millis();
+        if millis == self.millis {
+            self.counter += 1;
+        } else {
+            // Starting below 2^73 leaves room to increment without overflowing the 74 bits.
+            self.millis = millis;
+            self.counter = random.gen::<u128>() >> 55;
+        }
+        // The builder takes the top 12 bits from the first two bytes and the rest from the
+        // last eight, skipping the bits it overwrites with the version and variant.
+        let packed = (self.counter >> 62) << 64 | (self.counter & ((1 << 62) - 1));
+        let mut bytes = [0; 10];
+        bytes.copy_from_slice(&packed.to_be_bytes()[6..]);
+        Builder::from_unix_timestamp_millis(millis, &bytes).into_uuid()
+    }
+}
+
+/// Lazily generates the batch, so it never has to be held in memory as a whole.
+pub fn uuids(settings: &Settings) -> Box<dyn Iterator<Item = Uuid> + '_> {
+    let names = settings.names.iter().filter(|name| !name.is_empty());
+    match settings.version {
+        Version::V3 => {
+            Box::new(names.map(|name| Uuid::new_v3(&settings.namespace, name.as_bytes())))
+        }
+        Version::V5 => {
+            Box::new(names.map(|name| Uuid::new_v5(&settings.namespace, name.as_bytes())))
+        }
+        _ => {
+            let mut random = Random::new(settings.seed.as_deref());
+            let clock = ClockSequence(Cell::new(random.gen()));
+            let mut v7 = V7Counter::default();
+            Box::new(
+                (0..settings.amount).map(move |_| settings.next_uuid(&mut random, &clock, &mut v7)),
+            )
+        }
+    }
+}
+
+pub fn render(settings: &Settings) -> String {
+    let mut output = Vec::new();
+    match write(settings, &mut output, &AtomicBool::new(false), |_| ()) {
+        Ok(_) => String::from_utf8_lossy(&output).into_owned(),
+        Err(e) => e,
+    }
+}
+
+/// Streams the formatted batch into `writer`, calling `progress` with the number of IDs
+/// written so far every few thousand IDs. Stops early once `cancelled` is set.
+pub fn write(
+    settings: &Settings,
+    writer: impl Write,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(u64),
+) -> Result<u64, String> {
+    let mut writer = BufWriter::new(writer);
+    let written = write_batch(settings, &mut writer, cancelled, &mut progress)
+        .and_then(|written| writer.flush().map(|_| written))
+        .map_err(|e| e.to_string())?;
+    progress(written);
+    Ok(written)
+}
+
+// The header, the items and the footer each go on their own line.
+fn write_batch<W: Write>(
+    settings: &Settings,
+    writer: &mut W,
+    cancelled: &AtomicBool,
+    progress: &mut impl FnMut(u64),
+) -> io::Result<u64> {
+    let format = &settings.format;
+    let header = format.header();
+    if let Some(header) = &header {
+        writer.write_all(header.as_bytes())?;
+    }
+
+    let mut written = 0;
+    let mut write_id = |writer: &mut W, id: &str| -> io::Result<()> {
+        match written {
+            0 if header.is_some() => writer.write_all(b"\n")?,
+            0 => (),
+            _ => writer.write_all(format.separator().as_bytes())?,
+        }
+        format.write_item(writer, written as usize, id)?;
+        written += 1;
+        if written % PROGRESS_STEP == 0 {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "Saving cancelled",
+                ));
+            }
+            progress(written);
+        }
+        Ok(())
+    };
+    match settings.id_type {
+        IdType::Uuid => {
+            let mut buffer = Uuid::encode_buffer();
+            for uuid in uuids(settings) {
+                write_id(writer, format.encode_uuid(&uuid, &mut buffer))?;
+            }
+        }
+        id_type => {
+            let mut generator = IdGenerator::new(
+                id_type,
+                settings.nanoid.clone(),
+                settings.snowflake,
+                Random::new(settings.seed.as_deref()),
+            );
+            for _ in 0..settings.amount {
+                write_id(writer, &generator.next_id())?;
+            }
+        }
+    }
+
+    if let Some(footer) = format.footer() {
+        if header.is_some() || written > 0 {
+            writer.write_all(b"\n")?;
+        }
+        writer.write_all(footer.as_bytes())?;
+    }
+    Ok(written)
+}
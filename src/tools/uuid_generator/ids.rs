@@ -0,0 +1,570 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+use sha3::{Digest, Sha3_512};
+use uuid::Builder;
+
+use crate::random::Random;
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE36: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+pub const NANOID_ALPHABET: &str =
+    "useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+pub const NANOID_LENGTH: usize = 21;
+// KSUID timestamps count seconds from 2014-05-13, which buys them another century of range.
+const KSUID_EPOCH: u64 = 1_400_000_000;
+const KSUID_LENGTH: usize = 27;
+const CUID2_LENGTH: usize = 24;
+pub const TWITTER_EPOCH: u64 = 1_288_834_974_657;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdType {
+    #[default]
+    Uuid,
+    Ulid,
+    Ksuid,
+    NanoId,
+    Snowflake,
+    Cuid2,
+}
+
+impl IdType {
+    pub const ALL: [IdType; 6] = [
+        IdType::Uuid,
+        IdType::Ulid,
+        IdType::Ksuid,
+        IdType::NanoId,
+        IdType::Snowflake,
+        IdType::Cuid2,
+    ];
+}
+
+impl std::fmt::Display for IdType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                IdType::Uuid => "UUID",
+                IdType::Ulid => "ULID",
+                IdType::Ksuid => "KSUID",
+                IdType::NanoId => "NanoID",
+                IdType::Snowflake => "Snowflake",
+                IdType::Cuid2 => "CUID2",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NanoIdOptions {
+    pub alphabet: Vec<char>,
+    pub length: usize,
+}
+
+impl Default for NanoIdOptions {
+    fn default() -> Self {
+        Self {
+            alphabet: NANOID_ALPHABET.chars().collect(),
+            length: NANOID_LENGTH,
+        }
+    }
+}
+
+impl NanoIdOptions {
+    pub fn new(alphabet: &str, length: usize) -> Result<Self, String> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        if !(2..=256).contains(&alphabet.len()) {
+            return Err(String::from(
+                "The alphabet needs between 2 and 256 characters",
+            ));
+        }
+        if let Some(c) = alphabet
+            .iter()
+            .enumerate()
+            .find_map(|(i, c)| alphabet[..i].contains(c).then_some(c))
+        {
+            return Err(format!("The alphabet contains '{}' more than once", c));
+        }
+        if length == 0 {
+            return Err(String::from("The length must be at least 1"));
+        }
+        Ok(Self { alphabet, length })
+    }
+}
+
+/// A Snowflake ID is a 63-bit number made of a millisecond timestamp, a worker ID and a
+/// per-millisecond sequence, from the most to the least significant bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeOptions {
+    pub epoch: u64,
+    pub worker_bits: u32,
+    pub sequence_bits: u32,
+    pub worker_id: u64,
+}
+
+impl Default for SnowflakeOptions {
+    fn default() -> Self {
+        Self {
+            epoch: TWITTER_EPOCH,
+            worker_bits: 10,
+            sequence_bits: 12,
+            worker_id: 0,
+        }
+    }
+}
+
+impl SnowflakeOptions {
+    pub fn new(
+        epoch: u64,
+        worker_bits: u32,
+        sequence_bits: u32,
+        worker_id: u64,
+    ) -> Result<Self, String> {
+        if worker_bits + sequence_bits > 22 {
+            return Err(String::from(
+                "Worker and sequence bits can't exceed 22, the timestamp needs at least 41",
+            ));
+        }
+        if worker_id >= 1 << worker_bits {
+            return Err(format!(
+                "The worker ID must be below {} with {} worker bits",
+                1u64 << worker_bits,
+                worker_bits
+            ));
+        }
+        if epoch > now_millis() {
+            return Err(String::from("The epoch can't be in the future"));
+        }
+        Ok(Self {
+            epoch,
+            worker_bits,
+            sequence_bits,
+            worker_id,
+        })
+    }
+
+    fn timestamp_bits(&self) -> u32 {
+        63 - self.worker_bits - self.sequence_bits
+    }
+}
+
+/// Generates IDs of any type but UUID, keeping the state some of them need between calls.
+pub struct IdGenerator {
+    id_type: IdType,
+    nanoid: NanoIdOptions,
+    snowflake: SnowflakeOptions,
+    rng: Random,
+    last_millis: u64,
+    last_ulid: u128,
+    sequence: u64,
+    counter: u64,
+    fingerprint: String,
+}
+
+impl IdGenerator {
+    pub fn new(
+        id_type: IdType,
+        nanoid: NanoIdOptions,
+        snowflake: SnowflakeOptions,
+        mut rng: Random,
+    ) -> Self {
+        let counter = rng.gen_range(0..476_782_367);
+        let entropy: String = (0..32).map(|_| random_char(&mut rng, BASE36)).collect();
+        // The process ID tells apart generators started at the same time, but it would make
+        // seeded output differ between runs.
+        let process = match rng.is_seeded() {
+            true => String::new(),
+            false => std::process::id().to_string(),
+        };
+        let fingerprint = cuid2_hash(&format!("{}{}", process, entropy))
+            .chars()
+            .take(32)
+            .collect();
+        Self {
+            id_type,
+            nanoid,
+            snowflake,
+            rng,
+            last_millis: 0,
+            last_ulid: 0,
+            sequence: 0,
+            counter,
+            fingerprint,
+        }
+    }
+
+    pub fn next_id(&mut self) -> String {
+        match self.id_type {
+            IdType::Uuid => Builder::from_random_bytes(self.rng.gen())
+                .into_uuid()
+                .to_string(),
+            IdType::Ulid => self.ulid(),
+            IdType::Ksuid => self.ksuid(),
+            IdType::NanoId => self.nanoid(),
+            IdType::Snowflake => self.snowflake().to_string(),
+            IdType::Cuid2 => self.cuid2(),
+        }
+    }
+
+    // IDs created in the same millisecond increment the random part, so they still sort in
+    // creation order, as the ULID spec recommends.
+    fn ulid(&mut self) -> String {
+        let millis = now_millis();
+        let value = if millis == self.last_millis {
+            self.last_ulid.wrapping_add(1)
+        } else {
+            (millis as u128) << 80 | self.rng.gen::<u128>() >> 48
+        };
+        self.last_millis = millis;
+        self.last_ulid = value;
+        encode_ulid(value)
+    }
+
+    fn ksuid(&mut self) -> String {
+        let seconds = now_millis() / 1000 - KSUID_EPOCH;
+        let mut bytes = [0; 20];
+        bytes[..4].copy_from_slice(&(seconds as u32).to_be_bytes());
+        self.rng.fill(&mut bytes[4..]);
+        encode_ksuid(&bytes)
+    }
+
+    // Masking a random byte and retrying when it falls outside the alphabet keeps every
+    // character equally likely, unlike a plain modulo.
+    fn nanoid(&mut self) -> String {
+        let alphabet = &self.nanoid.alphabet;
+        let mask = alphabet.len().next_power_of_two() - 1;
+        let mut id = String::with_capacity(self.nanoid.length);
+        let mut length = 0;
+        while length < self.nanoid.length {
+            let index = self.rng.gen::<u8>() as usize & mask;
+            if let Some(c) = alphabet.get(index) {
+                id.push(*c);
+                length += 1;
+            }
+        }
+        id
+    }
+
+    // Once the sequence of a millisecond is exhausted, wait for the next one.
+    fn snowflake(&mut self) -> u64 {
+        let options = self.snowflake;
+        let mut millis = now_millis();
+        if millis <= self.last_millis {
+            millis = self.last_millis;
+            self.sequence += 1;
+            if self.sequence >= 1 << options.sequence_bits {
+                while millis <= self.last_millis {
+                    std::thread::sleep(Duration::from_micros(100));
+                    millis = now_millis();
+                }
+                self.sequence = 0;
+            }
+        } else {
+            self.sequence = 0;
+        }
+        self.last_millis = millis;
+        let timestamp = (millis - options.epoch) & ((1 << options.timestamp_bits()) - 1);
+        timestamp << (options.worker_bits + options.sequence_bits)
+            | options.worker_id << options.sequence_bits
+            | self.sequence
+    }
+
+    fn cuid2(&mut self) -> String {
+        self.counter += 1;
+        let entropy: String = (0..CUID2_LENGTH)
+            .map(|_| random_char(&mut self.rng, BASE36))
+            .collect();
+        let input = format!(
+            "{}{}{}{}",
+            to_base(&now_millis().to_be_bytes(), BASE36),
+            entropy,
+            to_base(&self.counter.to_be_bytes(), BASE36),
+            self.fingerprint
+        );
+        let first = random_char(&mut self.rng, &BASE36[10..]);
+        std::iter::once(first)
+            .chain(cuid2_hash(&input).chars().skip(1).take(CUID2_LENGTH - 1))
+            .collect()
+    }
+}
+
+pub fn encode_ulid(value: u128) -> String {
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD[(value >> (i * 5)) as usize & 0x1f] as char)
+        .collect()
+}
+
+/// Decodes a ULID, accepting either case and the Crockford aliases for `0` and `1`.
+pub fn decode_ulid(input: &str) -> Result<u128, String> {
+    if input.len() != 26 {
+        return Err(format!("A ULID has 26 characters, got {}", input.len()));
+    }
+    let mut value: u128 = 0;
+    for (i, c) in input.chars().enumerate() {
+        let c = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        let digit = CROCKFORD
+            .iter()
+            .position(|d| *d as char == c)
+            .ok_or_else(|| format!("'{}' isn't a Crockford Base32 digit", c))?;
+        if i == 0 && digit > 7 {
+            return Err(String::from("The ULID overflows 128 bits"));
+        }
+        value = value << 5 | digit as u128;
+    }
+    Ok(value)
+}
+
+/// The millisecond timestamp of a ULID.
+pub fn ulid_millis(value: u128) -> u64 {
+    (value >> 80) as u64
+}
+
+pub fn encode_ksuid(bytes: &[u8; 20]) -> String {
+    let encoded = to_base(bytes, BASE62);
+    format!("{:0>width$}", encoded, width = KSUID_LENGTH)
+}
+
+pub fn decode_ksuid(input: &str) -> Result<[u8; 20], String> {
+    if input.len() != KSUID_LENGTH {
+        return Err(format!(
+            "A KSUID has {} characters, got {}",
+            KSUID_LENGTH,
+            input.len()
+        ));
+    }
+    let mut bytes = [0u8; 20];
+    for c in input.chars() {
+        let digit = BASE62
+            .iter()
+            .position(|d| *d as char == c)
+            .ok_or_else(|| format!("'{}' isn't a Base62 digit", c))?;
+        let mut carry = digit as u32;
+        for byte in bytes.iter_mut().rev() {
+            let value = *byte as u32 * 62 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry > 0 {
+            return Err(String::from("The KSUID overflows 160 bits"));
+        }
+    }
+    Ok(bytes)
+}
+
+/// The Unix time in seconds of a KSUID.
+pub fn ksuid_seconds(bytes: &[u8; 20]) -> u64 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64 + KSUID_EPOCH
+}
+
+/// Splits a Snowflake ID into its Unix time in milliseconds, worker ID and sequence. Fails when
+/// the timestamp doesn't fit in a `u64` once the epoch is added.
+pub fn decode_snowflake(id: u64, options: &SnowflakeOptions) -> Result<(u64, u64, u64), String> {
+    let sequence = id & ((1 << options.sequence_bits) - 1);
+    let worker = (id >> options.sequence_bits) & ((1 << options.worker_bits) - 1);
+    let millis = (id >> (options.worker_bits + options.sequence_bits))
+        .checked_add(options.epoch)
+        .ok_or_else(|| String::from("Snowflake ID out of range for this epoch"))?;
+    Ok((millis, worker, sequence))
+}
+
+/// Whether `input` has the shape of a CUID2: a lowercase letter followed by base 36 digits.
+pub fn is_cuid2(input: &str) -> bool {
+    input.len() == CUID2_LENGTH
+        && input.starts_with(|c: char| c.is_ascii_lowercase())
+        && input.bytes().all(|b| BASE36.contains(&b))
+}
+
+fn cuid2_hash(input: &str) -> String {
+    let digest = Sha3_512::digest(input.as_bytes());
+    to_base(&digest, BASE36).chars().skip(1).collect()
+}
+
+fn random_char(rng: &mut impl Rng, alphabet: &[u8]) -> char {
+    alphabet[rng.gen_range(0..alphabet.len())] as char
+}
+
+/// Encodes a big-endian number of any size in the base of `alphabet`, without leading zeros.
+fn to_base(bytes: &[u8], alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u32;
+    let mut number = bytes.to_vec();
+    let mut digits = Vec::new();
+    while number.iter().any(|b| *b != 0) {
+        let mut remainder = 0;
+        for byte in number.iter_mut() {
+            let value = remainder << 8 | *byte as u32;
+            *byte = (value / base) as u8;
+            remainder = value % base;
+        }
+        digits.push(alphabet[remainder as usize]);
+    }
+    if digits.is_empty() {
+        digits.push(alphabet[0]);
+    }
+    digits.iter().rev().map(|d| *d as char).collect()
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generator(id_type: IdType, seed: Option<&str>) -> IdGenerator {
+        IdGenerator::new(
+            id_type,
+            NanoIdOptions::default(),
+            SnowflakeOptions::default(),
+            Random::new(seed),
+        )
+    }
+
+    #[test]
+    fn ulid_timestamp_and_aliases() {
+        let ulid = "01ARYZ6S41TSV4RRFFQ69G5FAV";
+        let value = decode_ulid(ulid).unwrap();
+        assert_eq!(ulid_millis(value), 1_469_918_176_385);
+        assert_eq!(encode_ulid(value), ulid);
+        assert_eq!(decode_ulid("O1aryz6s41tsv4rrffq69g5fav").unwrap(), value);
+        assert_eq!(
+            decode_ulid("7ZZZZZZZZZZZZZZZZZZZZZZZZZ").unwrap(),
+            u128::MAX
+        );
+        assert!(decode_ulid("80000000000000000000000000").is_err());
+        assert!(decode_ulid("01ARYZ6S41TSV4RRFFQ69G5FA").is_err());
+        assert!(decode_ulid("01ARYZ6S41TSV4RRFFQ69G5FAU").is_err());
+    }
+
+    #[test]
+    fn ksuid_timestamp_and_payload() {
+        let ksuid = "0ujtsYcgvSTl8PAuAdqWYSMnLOv";
+        let bytes = decode_ksuid(ksuid).unwrap();
+        assert_eq!(ksuid_seconds(&bytes), 1_507_608_047);
+        assert_eq!(
+            bytes[4..],
+            [
+                0xb5, 0xa1, 0xcd, 0x34, 0xb5, 0xf9, 0x9d, 0x11, 0x54, 0xfb, 0x68, 0x53, 0x34, 0x5c,
+                0x97, 0x35
+            ]
+        );
+        assert_eq!(encode_ksuid(&bytes), ksuid);
+        assert_eq!(encode_ksuid(&[0; 20]), "000000000000000000000000000");
+        assert_eq!(
+            decode_ksuid("aWgEPTl1tmebfsQzFP4bxwgy80V").unwrap(),
+            [0xff; 20]
+        );
+        assert!(decode_ksuid("aWgEPTl1tmebfsQzFP4bxwgy80W").is_err());
+        assert!(decode_ksuid("0ujtsYcgvSTl8PAuAdqWYSMnLO_").is_err());
+    }
+
+    #[test]
+    fn snowflake_layout() {
+        let options = SnowflakeOptions::default();
+        let id = 5 << 22 | 3 << 12 | 7;
+        assert_eq!(
+            decode_snowflake(id, &options),
+            Ok((TWITTER_EPOCH + 5, 3, 7))
+        );
+
+        let mut generator = IdGenerator::new(
+            IdType::Snowflake,
+            NanoIdOptions::default(),
+            SnowflakeOptions::new(TWITTER_EPOCH, 4, 12, 9).unwrap(),
+            Random::new(None),
+        );
+        let before = now_millis();
+        let id: u64 = generator.next_id().parse().unwrap();
+        let (millis, worker, _) = decode_snowflake(id, &generator.snowflake).unwrap();
+        assert!((before..=now_millis()).contains(&millis));
+        assert_eq!(worker, 9);
+    }
+
+    #[test]
+    fn snowflake_timestamp_overflow_is_an_error() {
+        let options = SnowflakeOptions::new(TWITTER_EPOCH, 0, 0, 0).unwrap();
+        assert!(decode_snowflake(u64::MAX, &options).is_err());
+        assert!(decode_snowflake(u64::MAX - TWITTER_EPOCH, &options).is_ok());
+    }
+
+    #[test]
+    fn snowflake_options_are_validated() {
+        assert!(SnowflakeOptions::new(TWITTER_EPOCH, 12, 11, 0).is_err());
+        assert!(SnowflakeOptions::new(TWITTER_EPOCH, 2, 12, 4).is_err());
+        assert!(SnowflakeOptions::new(now_millis() + 60_000, 10, 12, 0).is_err());
+        assert!(SnowflakeOptions::new(0, 10, 12, 1023).is_ok());
+    }
+
+    #[test]
+    fn snowflakes_increase_when_the_sequence_runs_out() {
+        let mut generator = IdGenerator::new(
+            IdType::Snowflake,
+            NanoIdOptions::default(),
+            SnowflakeOptions::new(TWITTER_EPOCH, 10, 2, 0).unwrap(),
+            Random::new(None),
+        );
+        let ids: Vec<u64> = (0..50)
+            .map(|_| generator.next_id().parse().unwrap())
+            .collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn ulids_sort_in_creation_order() {
+        let mut generator = generator(IdType::Ulid, None);
+        let ids: Vec<String> = (0..1000).map(|_| generator.next_id()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn nanoid_options_are_validated() {
+        assert!(NanoIdOptions::new("a", 10).is_err());
+        assert!(NanoIdOptions::new("abca", 10).is_err());
+        assert!(NanoIdOptions::new("ab", 0).is_err());
+        assert!(NanoIdOptions::new("ab", 1).is_ok());
+    }
+
+    #[test]
+    fn nanoids_use_the_alphabet_and_length() {
+        let nanoid = NanoIdOptions::new("abc", 50).unwrap();
+        let mut generator = IdGenerator::new(
+            IdType::NanoId,
+            nanoid,
+            SnowflakeOptions::default(),
+            Random::new(None),
+        );
+        let id = generator.next_id();
+        assert_eq!(id.chars().count(), 50);
+        assert!(id.chars().all(|c| "abc".contains(c)));
+    }
+
+    #[test]
+    fn seeded_ids_are_reproducible() {
+        for id_type in [IdType::Uuid, IdType::NanoId] {
+            let mut first = generator(id_type, Some("seed"));
+            let mut second = generator(id_type, Some("seed"));
+            assert_eq!(first.next_id(), second.next_id());
+        }
+    }
+
+    #[test]
+    fn cuid2s_have_their_shape() {
+        let mut generator = generator(IdType::Cuid2, None);
+        for _ in 0..100 {
+            let id = generator.next_id();
+            assert!(is_cuid2(&id), "{}", id);
+        }
+        assert!(!is_cuid2("0bcdefghijklmnopqrstuvwx"));
+        assert!(!is_cuid2("abc"));
+    }
+}
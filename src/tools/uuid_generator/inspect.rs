@@ -0,0 +1,185 @@
+use chrono::{DateTime, Local, Utc};
+use uuid::{Uuid, Variant};
+
+use super::ids::{self, SnowflakeOptions};
+use super::version;
+
+// 100-nanosecond intervals between the Gregorian epoch (1582-10-15) and the Unix epoch.
+const GREGORIAN_TO_UNIX_TICKS: i64 = 0x01B2_1DD2_1381_4000;
+
+/// Inspects every non-empty line of `input` and returns a report with one section per line.
+/// Snowflake IDs are split according to `snowflake`, as their layout isn't self-describing.
+pub fn inspect_all(input: &str, snowflake: &SnowflakeOptions) -> String {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| match inspect(line, snowflake) {
+            Ok(report) => report,
+            Err(e) => format!("Line {}: '{}'\n  Invalid: {}", index + 1, line.trim(), e),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Describes a single ID, trying the UUID formats `Uuid::parse_str` accepts first and then the
+/// other supported ID types.
+pub fn inspect(input: &str, snowflake: &SnowflakeOptions) -> Result<String, String> {
+    let input = input.trim();
+    let uuid = match Uuid::parse_str(input) {
+        Ok(uuid) => uuid,
+        Err(e) => return inspect_other(input, snowflake).ok_or_else(|| e.to_string()),
+    };
+    let mut report = vec![
+        uuid.hyphenated().to_string(),
+        format!("  Version: {}", describe_version(&uuid)),
+    ];
+    if !uuid.is_nil() && !uuid.is_max() {
+        report.push(format!(
+            "  Variant: {}",
+            describe_variant(uuid.get_variant())
+        ));
+    }
+
+    if let Some(timestamp) = timestamp(&uuid) {
+        report.push(describe_timestamp(timestamp));
+    }
+    if matches!(uuid.get_version_num(), 1 | 6) {
+        let bytes = uuid.as_bytes();
+        let clock_sequence = u16::from_be_bytes([bytes[8], bytes[9]]) & 0x3fff;
+        let mut node_id = [0; 6];
+        node_id.copy_from_slice(&bytes[10..]);
+        report.push(format!("  Clock sequence: {}", clock_sequence));
+        report.push(format!(
+            "  Node ID: {}{}",
+            version::format_node_id(&node_id),
+            match node_id[0] & 0x01 {
+                0 => " (MAC address)",
+                _ => " (random)",
+            }
+        ));
+    }
+    report.push(format!("  As ULID: {}", ids::encode_ulid(uuid.as_u128())));
+    Ok(report.join("\n"))
+}
+
+fn inspect_other(input: &str, snowflake: &SnowflakeOptions) -> Option<String> {
+    let report = if let Ok(value) = ids::decode_ulid(input) {
+        vec![
+            ids::encode_ulid(value),
+            String::from("  Type: ULID"),
+            describe_timestamp(DateTime::from_timestamp_millis(
+                ids::ulid_millis(value) as i64
+            )?),
+            format!("  Randomness: {:020x}", value & ((1 << 80) - 1)),
+            format!("  As UUID: {}", Uuid::from_u128(value)),
+        ]
+    } else if let Ok(bytes) = ids::decode_ksuid(input) {
+        vec![
+            input.to_string(),
+            String::from("  Type: KSUID"),
+            describe_timestamp(DateTime::from_timestamp(
+                ids::ksuid_seconds(&bytes) as i64,
+                0,
+            )?),
+            format!(
+                "  Payload: {}",
+                bytes[4..]
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<String>()
+            ),
+        ]
+    } else if let Ok(id) = input.parse::<u64>() {
+        let (millis, worker, sequence) = match ids::decode_snowflake(id, snowflake) {
+            Ok(parts) => parts,
+            Err(e) => return Some(format!("{}\n  Type: Snowflake\n  Invalid: {}", input, e)),
+        };
+        let timestamp = i64::try_from(millis)
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .map(describe_timestamp)
+            .unwrap_or_else(|| String::from("  Timestamp: out of range"));
+        vec![
+            input.to_string(),
+            String::from("  Type: Snowflake"),
+            timestamp,
+            format!("  Worker ID: {}", worker),
+            format!("  Sequence: {}", sequence),
+        ]
+    } else if ids::is_cuid2(input) {
+        vec![
+            input.to_string(),
+            String::from("  Type: CUID2 (no embedded data)"),
+        ]
+    } else if input.chars().count() == ids::NANOID_LENGTH
+        && input.chars().all(|c| ids::NANOID_ALPHABET.contains(c))
+    {
+        vec![
+            input.to_string(),
+            String::from("  Type: NanoID (no embedded data)"),
+        ]
+    } else {
+        return None;
+    };
+    Some(report.join("\n"))
+}
+
+fn describe_timestamp(timestamp: DateTime<Utc>) -> String {
+    format!(
+        "  Timestamp: {} ({})",
+        timestamp.format("%Y-%m-%d %H:%M:%S%.f UTC"),
+        timestamp
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S%.f %:z local")
+    )
+}
+
+fn describe_version(uuid: &Uuid) -> String {
+    if uuid.is_nil() {
+        return String::from("Nil");
+    }
+    if uuid.is_max() {
+        return String::from("Max");
+    }
+    let description = match uuid.get_version_num() {
+        1 => "time and node ID",
+        2 => "DCE security",
+        3 => "name-based, MD5",
+        4 => "random",
+        5 => "name-based, SHA-1",
+        6 => "reordered time and node ID",
+        7 => "Unix time",
+        8 => "custom",
+        _ => "unknown",
+    };
+    format!("{} ({})", uuid.get_version_num(), description)
+}
+
+fn describe_variant(variant: Variant) -> &'static str {
+    match variant {
+        Variant::NCS => "NCS (reserved)",
+        Variant::RFC4122 => "RFC 9562",
+        Variant::Microsoft => "Microsoft (reserved)",
+        _ => "Future (reserved)",
+    }
+}
+
+fn timestamp(uuid: &Uuid) -> Option<DateTime<Utc>> {
+    let timestamp = uuid.get_timestamp()?;
+    match uuid.get_version_num() {
+        // Gregorian timestamps can predate the Unix epoch, which `Timestamp::to_unix` can't express.
+        1 | 6 => {
+            let (ticks, _) = timestamp.to_gregorian();
+            let unix_ticks = ticks as i64 - GREGORIAN_TO_UNIX_TICKS;
+            DateTime::from_timestamp(
+                unix_ticks.div_euclid(10_000_000),
+                (unix_ticks.rem_euclid(10_000_000) * 100) as u32,
+            )
+        }
+        _ => {
+            let (seconds, nanos) = timestamp.to_unix();
+            DateTime::from_timestamp(seconds as i64, nanos)
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Version {
+    V1,
+    V3,
+    #[default]
+    V4,
+    V5,
+    V6,
+    V7,
+    V8,
+    Nil,
+    Max,
+}
+
+impl Version {
+    pub const ALL: [Version; 9] = [
+        Version::V1,
+        Version::V3,
+        Version::V4,
+        Version::V5,
+        Version::V6,
+        Version::V7,
+        Version::V8,
+        Version::Nil,
+        Version::Max,
+    ];
+
+    pub fn is_time_based(&self) -> bool {
+        matches!(self, Version::V1 | Version::V6)
+    }
+
+    pub fn is_name_based(&self) -> bool {
+        matches!(self, Version::V3 | Version::V5)
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Version::V1 => "Version 1 (time and node ID)",
+                Version::V3 => "Version 3 (name-based, MD5)",
+                Version::V4 => "Version 4 (random)",
+                Version::V5 => "Version 5 (name-based, SHA-1)",
+                Version::V6 => "Version 6 (reordered time and node ID)",
+                Version::V7 => "Version 7 (Unix time)",
+                Version::V8 => "Version 8 (custom)",
+                Version::Nil => "Nil",
+                Version::Max => "Max",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Namespace {
+    #[default]
+    Dns,
+    Url,
+    Oid,
+    X500,
+    Custom,
+}
+
+impl Namespace {
+    pub const ALL: [Namespace; 5] = [
+        Namespace::Dns,
+        Namespace::Url,
+        Namespace::Oid,
+        Namespace::X500,
+        Namespace::Custom,
+    ];
+
+    /// The well-known namespace UUID, or `None` for a custom namespace.
+    pub fn uuid(&self) -> Option<Uuid> {
+        match self {
+            Namespace::Dns => Some(Uuid::NAMESPACE_DNS),
+            Namespace::Url => Some(Uuid::NAMESPACE_URL),
+            Namespace::Oid => Some(Uuid::NAMESPACE_OID),
+            Namespace::X500 => Some(Uuid::NAMESPACE_X500),
+            Namespace::Custom => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Namespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Namespace::Dns => "DNS",
+                Namespace::Url => "URL",
+                Namespace::Oid => "OID",
+                Namespace::X500 => "X.500",
+                Namespace::Custom => "Custom",
+            }
+        )
+    }
+}
+
+/// A random node ID with the multicast bit set, as RFC 9562 asks for IDs that aren't a MAC address.
+pub fn random_node_id() -> [u8; 6] {
+    let bytes = Uuid::new_v4().into_bytes();
+    let mut node_id = [0; 6];
+    node_id.copy_from_slice(&bytes[..6]);
+    node_id[0] |= 0x01;
+    node_id
+}
+
+pub fn format_node_id(node_id: &[u8; 6]) -> String {
+    node_id
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Parses exactly `N` bytes of hex, ignoring whitespace and `:`/`-` separators.
+pub fn parse_hex<const N: usize>(input: &str) -> Result<[u8; N], String> {
+    let digits: Vec<u8> = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, ':' | '-'))
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or_else(|| format!("'{}' is not a hex digit", c))
+        })
+        .collect::<Result<_, _>>()?;
+    if digits.len() != N * 2 {
+        return Err(format!(
+            "Expected {} hex digits, got {}",
+            N * 2,
+            digits.len()
+        ));
+    }
+    let mut bytes = [0; N];
+    for (byte, pair) in bytes.iter_mut().zip(digits.chunks(2)) {
+        *byte = pair[0] << 4 | pair[1];
+    }
+    Ok(bytes)
+}
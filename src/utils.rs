@@ -3,23 +3,43 @@ use std::str::FromStr;
 use iced::Event;
 
 use crate::launcher;
-use crate::tools::{base64_converter, json_beautifier, random_data_generator, uuid_generator};
+use crate::tools::{
+    base64_converter, encoding_converter, hash_generator, hmac_generator, json_beautifier,
+    json_diff, jwt_debugger, password_analyzer, password_generator, random_data_generator,
+    text_encoding_converter, uuid_generator,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Tool {
     #[default]
     UuidGenerator,
     JsonBeautifier,
+    JsonDiff,
     Base64Converter,
+    EncodingConverter,
+    TextEncodingConverter,
     RandomDataGenerator,
+    PasswordGenerator,
+    PasswordAnalyzer,
+    HashGenerator,
+    HmacGenerator,
+    JwtDebugger,
 }
 
 impl Tool {
-    pub const ALL: [&str; 4] = [
+    pub const ALL: [&str; 12] = [
         uuid_generator::NAME,
         json_beautifier::NAME,
+        json_diff::NAME,
         base64_converter::NAME,
+        encoding_converter::NAME,
+        text_encoding_converter::NAME,
         random_data_generator::NAME,
+        password_generator::NAME,
+        password_analyzer::NAME,
+        hash_generator::NAME,
+        hmac_generator::NAME,
+        jwt_debugger::NAME,
     ];
 }
 
@@ -31,8 +51,16 @@ impl std::fmt::Display for Tool {
             match self {
                 Tool::UuidGenerator => uuid_generator::NAME,
                 Tool::JsonBeautifier => json_beautifier::NAME,
+                Tool::JsonDiff => json_diff::NAME,
                 Tool::Base64Converter => base64_converter::NAME,
+                Tool::EncodingConverter => encoding_converter::NAME,
+                Tool::TextEncodingConverter => text_encoding_converter::NAME,
                 Tool::RandomDataGenerator => random_data_generator::NAME,
+                Tool::PasswordGenerator => password_generator::NAME,
+                Tool::PasswordAnalyzer => password_analyzer::NAME,
+                Tool::HashGenerator => hash_generator::NAME,
+                Tool::HmacGenerator => hmac_generator::NAME,
+                Tool::JwtDebugger => jwt_debugger::NAME,
             }
         )
     }
@@ -45,8 +73,16 @@ impl FromStr for Tool {
         match input {
             uuid_generator::NAME => Ok(Tool::UuidGenerator),
             json_beautifier::NAME => Ok(Tool::JsonBeautifier),
+            json_diff::NAME => Ok(Tool::JsonDiff),
             base64_converter::NAME => Ok(Tool::Base64Converter),
+            encoding_converter::NAME => Ok(Tool::EncodingConverter),
+            text_encoding_converter::NAME => Ok(Tool::TextEncodingConverter),
             random_data_generator::NAME => Ok(Tool::RandomDataGenerator),
+            password_generator::NAME => Ok(Tool::PasswordGenerator),
+            password_analyzer::NAME => Ok(Tool::PasswordAnalyzer),
+            hash_generator::NAME => Ok(Tool::HashGenerator),
+            hmac_generator::NAME => Ok(Tool::HmacGenerator),
+            jwt_debugger::NAME => Ok(Tool::JwtDebugger),
             _ => Err(()),
         }
     }
@@ -58,7 +94,15 @@ pub enum Message {
     UuidGenerator(uuid_generator::Message),
     Launcher(launcher::Message),
     JsonBeautifier(json_beautifier::Message),
+    JsonDiff(json_diff::Message),
     Base64Converter(base64_converter::Message),
+    EncodingConverter(encoding_converter::Message),
+    TextEncodingConverter(text_encoding_converter::Message),
     RandomDataGenerator(random_data_generator::Message),
+    PasswordGenerator(password_generator::Message),
+    PasswordAnalyzer(password_analyzer::Message),
+    HashGenerator(hash_generator::Message),
+    HmacGenerator(hmac_generator::Message),
+    JwtDebugger(jwt_debugger::Message),
     Event(Event),
 }